@@ -0,0 +1,326 @@
+use crate::audit;
+use crate::cleaner::{self, DriftedTarget, ProtectedPaths};
+use crate::format::shell_quote;
+use crate::scanner::CleanableProject;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use jwalk::WalkDir;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// zstd compression level used for archives (favors speed over ratio)
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Maximum number of files sampled per project when estimating compression
+const SAMPLE_FILES: usize = 64;
+
+/// Maximum bytes read from each sampled file
+const SAMPLE_BYTES_PER_FILE: u64 = 256 * 1024;
+
+/// Directory inside archives holding targets outside the project root
+/// (global caches, a shared Cargo target-dir, AVDs), by their absolute path
+const EXTERNAL_PREFIX: &str = "external";
+
+/// Archives written by this process, so names stay unique within a second
+static ARCHIVES_WRITTEN: AtomicUsize = AtomicUsize::new(0);
+
+/// Estimated outcome of archiving a project
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompressionEstimate {
+    pub original_size: u64,
+    pub compressed_size: u64,
+    pub seconds: f64,
+}
+
+impl CompressionEstimate {
+    /// Combines two estimates (e.g., across several projects)
+    pub fn combine(self, other: Self) -> Self {
+        Self {
+            original_size: self.original_size + other.original_size,
+            compressed_size: self.compressed_size + other.compressed_size,
+            seconds: self.seconds + other.seconds,
+        }
+    }
+
+    /// Compressed size as a percentage of the original
+    pub fn ratio_percent(&self) -> f64 {
+        if self.original_size == 0 {
+            return 0.0;
+        }
+        self.compressed_size as f64 * 100.0 / self.original_size as f64
+    }
+}
+
+/// Estimates compressed size and time by compressing a sample of the
+/// project's files and extrapolating to the full target size
+pub fn estimate_compression(project: &CleanableProject) -> CompressionEstimate {
+    let mut sample = Vec::new();
+    let mut sampled_files = 0;
+
+    'targets: for target in &project.targets {
+        for entry in WalkDir::new(&target.path)
+            .skip_hidden(false)
+            .parallelism(jwalk::Parallelism::Serial)
+            .into_iter()
+            .flatten()
+        {
+            if sampled_files >= SAMPLE_FILES {
+                break 'targets;
+            }
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            if let Ok(file) = File::open(entry.path()) {
+                if file.take(SAMPLE_BYTES_PER_FILE).read_to_end(&mut sample).is_ok() {
+                    sampled_files += 1;
+                }
+            }
+        }
+    }
+
+    if sample.is_empty() {
+        return CompressionEstimate {
+            original_size: project.total_size,
+            compressed_size: project.total_size,
+            seconds: 0.0,
+        };
+    }
+
+    let started = Instant::now();
+    let compressed_len = zstd::bulk::compress(&sample, COMPRESSION_LEVEL)
+        .map(|c| c.len())
+        .unwrap_or(sample.len());
+    let elapsed = started.elapsed().as_secs_f64().max(f64::EPSILON);
+
+    let ratio = compressed_len as f64 / sample.len() as f64;
+    let throughput = sample.len() as f64 / elapsed;
+
+    CompressionEstimate {
+        original_size: project.total_size,
+        compressed_size: (project.total_size as f64 * ratio) as u64,
+        seconds: project.total_size as f64 / throughput,
+    }
+}
+
+/// Describes an archive so its contents can be restored later
+#[derive(Debug, Serialize)]
+struct ArchiveManifest {
+    /// Project root the archived targets belong to
+    project_root: PathBuf,
+    strategy: String,
+    /// Targets relative to `project_root`, as stored in the archive
+    targets: Vec<PathBuf>,
+    /// Targets outside `project_root` (or the root itself), stored under `external/`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    external_targets: Vec<ExternalTarget>,
+    total_size: u64,
+    created_at: u64,
+    restore_command: String,
+}
+
+/// A target outside the project root, and where it is in the archive
+#[derive(Debug, Serialize)]
+struct ExternalTarget {
+    original: PathBuf,
+    archived: PathBuf,
+}
+
+/// Default archive location: `<data dir>/spektr/archives`
+pub fn default_archive_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spektr").join("archives"))
+}
+
+/// Compresses a project's targets into
+/// `<archive_dir>/<name>-<strategy>-<timestamp>-<pid>-<n>.tar.zst`, writes a
+/// JSON manifest next to it, then removes the original targets. Never
+/// overwrites an existing archive.
+/// Targets that changed since the scan are left alone. Returns the path of
+/// the archive and the targets left alone.
+pub fn archive_project(
+    project: &CleanableProject,
+    archive_dir: &Path,
+    protected: &ProtectedPaths,
+) -> Result<(PathBuf, Vec<DriftedTarget>)> {
+    protected.check(project)?;
+
+    let drifted = cleaner::drifted_targets(project);
+    let project = &cleaner::without_drifted(project, &drifted);
+    if project.targets.is_empty() && !drifted.is_empty() {
+        anyhow::bail!(
+            "Not archiving {}: every target changed since the scan ({})",
+            project.root_path.display(),
+            drifted[0].reason
+        );
+    }
+
+    fs::create_dir_all(archive_dir)
+        .with_context(|| format!("Failed to create archive directory {}", archive_dir.display()))?;
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let name = project
+        .root_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "project".to_string());
+    let strategy: String = project
+        .strategy_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    let stem = format!(
+        "{}-{}-{}-{}-{}",
+        name,
+        strategy,
+        created_at,
+        std::process::id(),
+        ARCHIVES_WRITTEN.fetch_add(1, Ordering::Relaxed)
+    );
+    let archive_path = archive_dir.join(format!("{}.tar.zst", stem));
+    let manifest_path = archive_dir.join(format!("{}.json", stem));
+
+    let targets: Vec<&Path> = project
+        .targets
+        .iter()
+        .map(|t| t.path.as_path())
+        .filter(|p| p.exists())
+        .collect();
+
+    let file = create_new(&archive_path)?;
+    let (relative_targets, external_targets) = match write_archive(file, &project.root_path, &targets) {
+        Ok(written) => written,
+        Err(e) => {
+            // A partial archive can't restore anything
+            let _ = fs::remove_file(&archive_path);
+            return Err(e);
+        }
+    };
+
+    let manifest = ArchiveManifest {
+        restore_command: restore_command(&archive_path, &project.root_path, &relative_targets, &external_targets),
+        project_root: project.root_path.clone(),
+        strategy: project.strategy_name.clone(),
+        targets: relative_targets,
+        external_targets,
+        total_size: project.total_size,
+        created_at,
+    };
+    // Without its manifest the archive is never listed or restored
+    let mut manifest_file = create_new(&manifest_path).inspect_err(|_| {
+        let _ = fs::remove_file(&archive_path);
+    })?;
+    let written = serde_json::to_string_pretty(&manifest)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| {
+            manifest_file
+                .write_all(json.as_bytes())
+                .with_context(|| format!("Failed to write manifest {}", manifest_path.display()))
+        });
+    if let Err(e) = written {
+        let _ = fs::remove_file(&archive_path);
+        let _ = fs::remove_file(&manifest_path);
+        return Err(e);
+    }
+
+    // Only remove originals once the archive and manifest are safely written
+    for target in targets {
+        if target.is_dir() {
+            fs::remove_dir_all(target)?;
+        } else {
+            fs::remove_file(target)?;
+        }
+    }
+
+    // Best effort: the history is informational
+    let _ = audit::record(project, "archive");
+
+    Ok((archive_path, drifted))
+}
+
+/// Writes the targets into a compressed tarball, returning where the ones
+/// under `root` and the external ones were stored
+fn write_archive(file: File, root: &Path, targets: &[&Path]) -> Result<(Vec<PathBuf>, Vec<ExternalTarget>)> {
+    let encoder = zstd::Encoder::new(file, COMPRESSION_LEVEL)?;
+    let mut builder = tar::Builder::new(encoder);
+    builder.follow_symlinks(false);
+
+    let mut relative_targets = Vec::new();
+    let mut external_targets = Vec::new();
+    for target in targets {
+        let relative = target
+            .strip_prefix(root)
+            .ok()
+            .filter(|relative| !relative.as_os_str().is_empty());
+        let archived = match relative {
+            Some(relative) => relative.to_path_buf(),
+            // tar only stores relative paths, and a target that is the root
+            // itself has no parent to extract into once it's removed
+            None => Path::new(EXTERNAL_PREFIX).join(
+                target
+                    .components()
+                    .filter(|c| matches!(c, Component::Normal(_)))
+                    .collect::<PathBuf>(),
+            ),
+        };
+        let appended = if target.is_dir() {
+            builder.append_dir_all(&archived, target)
+        } else {
+            builder.append_path_with_name(target, &archived)
+        };
+        appended.with_context(|| format!("Failed to archive {}", target.display()))?;
+        if relative.is_some() {
+            relative_targets.push(archived);
+        } else {
+            external_targets.push(ExternalTarget {
+                original: target.to_path_buf(),
+                archived,
+            });
+        }
+    }
+
+    builder.into_inner()?.finish()?;
+    Ok((relative_targets, external_targets))
+}
+
+/// Creates `path`, failing if anything already exists there
+fn create_new(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .with_context(|| format!("Failed to create {} (it may already exist)", path.display()))
+}
+
+/// Extracts the targets under the project root into it, and each external
+/// target back to its absolute path
+fn restore_command(archive: &Path, root: &Path, targets: &[PathBuf], external: &[ExternalTarget]) -> String {
+    let mut commands = Vec::new();
+    if !targets.is_empty() {
+        let members: Vec<String> = targets.iter().map(|t| shell_quote(t)).collect();
+        commands.push(format!(
+            "tar --zstd -xf {} -C {} {}",
+            shell_quote(archive),
+            shell_quote(root),
+            members.join(" ")
+        ));
+    }
+    for target in external {
+        let anchor = target
+            .original
+            .ancestors()
+            .last()
+            .unwrap_or(Path::new("/"));
+        commands.push(format!(
+            "tar --zstd -xf {} -C {} --strip-components=1 {}",
+            shell_quote(archive),
+            shell_quote(anchor),
+            shell_quote(&target.archived)
+        ));
+    }
+    commands.join(" && ")
+}
@@ -0,0 +1,398 @@
+use crate::format::{format_age, format_size, SizeUnits};
+use crate::mounts::mount_point;
+use crate::scanner::CleanableProject;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Size the size history may grow to before it's compacted
+const SIZES_COMPACT_BYTES: u64 = 4 * 1024 * 1024;
+
+/// One cleaning action, appended to `<data dir>/spektr/audit.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: SystemTime,
+    /// "delete" or "archive"
+    pub action: String,
+    pub project: PathBuf,
+    pub strategy: String,
+    pub freed: u64,
+}
+
+/// Free-space change on one volume across a clean, appended to
+/// `<data dir>/spektr/space.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpaceDelta {
+    pub timestamp: SystemTime,
+    /// "delete" or "archive"
+    pub action: String,
+    pub mount: PathBuf,
+    pub free_before: u64,
+    pub free_after: u64,
+    /// Scanned size of the cleaned projects on this volume
+    pub estimated: u64,
+}
+
+/// One enforcement of an artifact budget by `spektr watch-space`, appended
+/// to `<data dir>/spektr/budgets.jsonl`; its cleans are in the audit log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BudgetEnforcement {
+    pub timestamp: SystemTime,
+    /// Always "budget"
+    pub action: String,
+    pub root: PathBuf,
+    pub budget: u64,
+    /// Total the clean aimed for, below the budget so it doesn't retrigger at once
+    pub low_water: u64,
+    pub total_before: u64,
+    pub total_after: u64,
+    pub cleaned: usize,
+    pub failures: usize,
+}
+
+/// Size of one project at the end of a scan, appended to `<data dir>/spektr/sizes.jsonl`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SizeSample {
+    pub timestamp: SystemTime,
+    pub project: PathBuf,
+    pub strategy: String,
+    pub size: u64,
+}
+
+/// Location of the audit log: `<data dir>/spektr/audit.jsonl`
+pub fn audit_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spektr").join("audit.jsonl"))
+}
+
+/// Location of the free-space history: `<data dir>/spektr/space.jsonl`.
+/// Older versions wrote it into the audit log, whose lines it now skips.
+pub fn space_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spektr").join("space.jsonl"))
+}
+
+/// Location of the budget enforcement log: `<data dir>/spektr/budgets.jsonl`
+pub fn budgets_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spektr").join("budgets.jsonl"))
+}
+
+/// Location of the size history: `<data dir>/spektr/sizes.jsonl`
+pub fn sizes_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spektr").join("sizes.jsonl"))
+}
+
+/// Appends one JSON line per item to a log under the data directory
+fn append_lines<T: Serialize>(path: Option<PathBuf>, items: &[T]) -> Result<()> {
+    let path = path.context("Failed to determine data directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    for item in items {
+        writeln!(file, "{}", serde_json::to_string(item)?)?;
+    }
+    Ok(())
+}
+
+/// Reads a JSON-lines log, skipping lines that don't parse
+fn read_lines<T: for<'de> Deserialize<'de>>(path: Option<PathBuf>) -> Vec<T> {
+    path.and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends a cleaning action to the audit log
+pub fn record(project: &CleanableProject, action: &str) -> Result<()> {
+    let entry = AuditEntry {
+        timestamp: SystemTime::now(),
+        action: action.to_string(),
+        project: project.root_path.clone(),
+        strategy: project.strategy_name.clone(),
+        freed: project.total_size,
+    };
+    append_lines(audit_path(), &[entry])
+}
+
+/// Free space on the volumes holding some projects, taken before cleaning them
+pub struct SpaceSnapshot {
+    free: Vec<(PathBuf, u64)>,
+    /// Mount point of each project root, resolved while the roots still exist
+    mounts: HashMap<PathBuf, PathBuf>,
+}
+
+impl SpaceSnapshot {
+    pub fn take(projects: &[CleanableProject]) -> Self {
+        let mounts: HashMap<PathBuf, PathBuf> = projects
+            .iter()
+            .map(|p| (p.root_path.clone(), mount_point(&p.root_path)))
+            .collect();
+        let mut distinct: Vec<&PathBuf> = mounts.values().collect();
+        distinct.sort();
+        distinct.dedup();
+
+        let free = distinct
+            .into_iter()
+            .filter_map(|mount| {
+                let free = fs4::available_space(mount).ok()?;
+                Some((mount.clone(), free))
+            })
+            .collect();
+        Self { free, mounts }
+    }
+}
+
+/// Appends the free-space change on each volume of `before` since it was taken
+pub fn record_space(action: &str, before: &SpaceSnapshot, cleaned: &[CleanableProject]) -> Result<()> {
+    let timestamp = SystemTime::now();
+    let deltas: Vec<SpaceDelta> = before
+        .free
+        .iter()
+        .filter_map(|(mount, free_before)| {
+            let free_after = fs4::available_space(mount).ok()?;
+            Some(SpaceDelta {
+                timestamp,
+                action: action.to_string(),
+                mount: mount.clone(),
+                free_before: *free_before,
+                free_after,
+                estimated: cleaned
+                    .iter()
+                    .filter(|p| before.mounts.get(&p.root_path) == Some(mount))
+                    .map(|p| p.total_size)
+                    .sum(),
+            })
+        })
+        .collect();
+    append_lines(space_path(), &deltas)
+}
+
+/// Appends a budget enforcement to the budget log
+pub fn record_budget(enforcement: &BudgetEnforcement) -> Result<()> {
+    append_lines(budgets_path(), std::slice::from_ref(enforcement))
+}
+
+/// Estimated versus actually reclaimed space of one clean on one volume
+#[derive(Debug, Clone, Serialize)]
+pub struct Reclaimed {
+    /// Unix time of the clean
+    pub timestamp: u64,
+    pub action: String,
+    pub mount: PathBuf,
+    /// Scanned size of the cleaned projects
+    pub estimated: u64,
+    /// Change in free space; negative if something else filled the volume
+    /// meanwhile (or an archive landed on it)
+    pub reclaimed: i64,
+}
+
+/// Recorded free-space changes, newest first
+pub fn reclaimed_report() -> Vec<Reclaimed> {
+    let mut report: Vec<Reclaimed> = read_lines::<SpaceDelta>(space_path())
+        .into_iter()
+        .map(|delta| Reclaimed {
+            timestamp: unix_secs(delta.timestamp),
+            action: delta.action,
+            mount: delta.mount,
+            estimated: delta.estimated,
+            reclaimed: delta.free_after as i64 - delta.free_before as i64,
+        })
+        .collect();
+    report.reverse();
+    report
+}
+
+/// Appends the sizes found by a completed scan to the size history, then
+/// compacts it once it outgrows `SIZES_COMPACT_BYTES`
+pub fn record_sizes(projects: &[CleanableProject]) -> Result<()> {
+    let timestamp = SystemTime::now();
+    let samples: Vec<SizeSample> = projects
+        .iter()
+        .filter(|project| project.total_size > 0)
+        .map(|project| SizeSample {
+            timestamp,
+            project: project.root_path.clone(),
+            strategy: project.strategy_name.clone(),
+            size: project.total_size,
+        })
+        .collect();
+    append_lines(sizes_path(), &samples)?;
+
+    let path = sizes_path().context("Failed to determine data directory")?;
+    if std::fs::metadata(&path).is_ok_and(|metadata| metadata.len() > SIZES_COMPACT_BYTES) {
+        compact_sizes(&path)?;
+    }
+    Ok(())
+}
+
+/// Keeps only the latest sample of each project: the regrowth report never
+/// looks further back
+fn compact_sizes(path: &Path) -> Result<()> {
+    let mut latest: HashMap<(PathBuf, String), SizeSample> = HashMap::new();
+    for sample in read_lines::<SizeSample>(Some(path.to_path_buf())) {
+        latest.insert((sample.project.clone(), sample.strategy.clone()), sample);
+    }
+    let mut samples: Vec<SizeSample> = latest.into_values().collect();
+    samples.sort_by_key(|sample| sample.timestamp);
+
+    // Written aside and renamed over, so a crash midway loses nothing
+    let compacted = path.with_extension("jsonl.tmp");
+    let mut content = String::new();
+    for sample in &samples {
+        content.push_str(&serde_json::to_string(sample)?);
+        content.push('\n');
+    }
+    std::fs::write(&compacted, content).with_context(|| format!("Failed to write {}", compacted.display()))?;
+    std::fs::rename(&compacted, path).with_context(|| format!("Failed to replace {}", path.display()))
+}
+
+/// Most recent clean of each project, from the audit log
+#[derive(Debug, Default)]
+pub struct CleanHistory {
+    last_cleaned: HashMap<(PathBuf, String), AuditEntry>,
+}
+
+impl CleanHistory {
+    /// Loads the audit log; a missing or unreadable log yields an empty history
+    pub fn load() -> Self {
+        let mut last_cleaned = HashMap::new();
+        // The log is append-only, so later lines are newer
+        for entry in read_lines::<AuditEntry>(audit_path()) {
+            last_cleaned.insert((entry.project.clone(), entry.strategy.clone()), entry);
+        }
+
+        Self { last_cleaned }
+    }
+
+    pub fn last_clean(&self, project: &CleanableProject) -> Option<&AuditEntry> {
+        self.last_cleaned
+            .get(&(project.root_path.clone(), project.strategy_name.clone()))
+    }
+
+    /// "cleaned 12 days ago, regrew to 3.10 GB" for projects cleaned before
+    pub fn describe(&self, project: &CleanableProject, units: SizeUnits) -> Option<String> {
+        let entry = self.last_clean(project)?;
+        let age = SystemTime::now()
+            .duration_since(entry.timestamp)
+            .unwrap_or_default();
+
+        Some(format!(
+            "{} {} ago, regrew to {}",
+            if entry.action == "archive" { "archived" } else { "cleaned" },
+            format_age(age),
+            format_size(project.total_size, units)
+        ))
+    }
+}
+
+/// How fast a cleaned project's artifacts grew back
+#[derive(Debug, Clone, Serialize)]
+pub struct Regrowth {
+    pub project: PathBuf,
+    pub strategy: String,
+    /// Number of times the project was cleaned or archived
+    pub cleans: usize,
+    /// Unix time of the most recent clean
+    pub last_cleaned: u64,
+    /// Bytes freed by the most recent clean
+    pub freed: u64,
+    /// Size at the latest scan after that clean
+    pub current_size: u64,
+    /// Unix time of that scan
+    pub measured_at: u64,
+    pub bytes_per_day: f64,
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// A project that keeps being cleaned
+#[derive(Debug, Clone)]
+pub struct RepeatedClean {
+    pub project: PathBuf,
+    pub strategy: String,
+    pub cleans: usize,
+    /// Bytes freed across all of its cleans
+    pub freed: u64,
+}
+
+/// Projects cleaned or archived at least `min_cleans` times, most freed first
+pub fn repeated_cleans(min_cleans: usize) -> Vec<RepeatedClean> {
+    let mut totals: HashMap<(PathBuf, String), (usize, u64)> = HashMap::new();
+    for entry in read_lines::<AuditEntry>(audit_path()) {
+        let total = totals.entry((entry.project, entry.strategy)).or_default();
+        total.0 += 1;
+        total.1 += entry.freed;
+    }
+
+    let mut repeated: Vec<RepeatedClean> = totals
+        .into_iter()
+        .filter(|(_, (cleans, _))| *cleans >= min_cleans)
+        .map(|((project, strategy), (cleans, freed))| RepeatedClean { project, strategy, cleans, freed })
+        .collect();
+    repeated.sort_by(|a, b| b.freed.cmp(&a.freed).then_with(|| a.project.cmp(&b.project)));
+    repeated
+}
+
+/// Projects that were cleaned and scanned again since, fastest regrowth first
+pub fn regrowth_report() -> Vec<Regrowth> {
+    let mut cleans: HashMap<(PathBuf, String), (usize, AuditEntry)> = HashMap::new();
+    for entry in read_lines::<AuditEntry>(audit_path()) {
+        let key = (entry.project.clone(), entry.strategy.clone());
+        let count = cleans.get(&key).map_or(0, |(count, _)| *count);
+        cleans.insert(key, (count + 1, entry));
+    }
+
+    // Latest sample of each project taken after its last clean
+    let mut latest: HashMap<(PathBuf, String), SizeSample> = HashMap::new();
+    for sample in read_lines::<SizeSample>(sizes_path()) {
+        let key = (sample.project.clone(), sample.strategy.clone());
+        let after_clean = cleans
+            .get(&key)
+            .is_some_and(|(_, entry)| sample.timestamp > entry.timestamp);
+        if after_clean {
+            latest.insert(key, sample);
+        }
+    }
+
+    let mut report: Vec<Regrowth> = latest
+        .into_iter()
+        .filter_map(|(key, sample)| {
+            let (count, entry) = cleans.remove(&key)?;
+            let elapsed = sample
+                .timestamp
+                .duration_since(entry.timestamp)
+                .unwrap_or_default()
+                .as_secs_f64()
+                // An hour minimum keeps scans right after a clean from dominating
+                .max(3_600.0);
+
+            Some(Regrowth {
+                project: sample.project,
+                strategy: sample.strategy,
+                cleans: count,
+                last_cleaned: unix_secs(entry.timestamp),
+                freed: entry.freed,
+                current_size: sample.size,
+                measured_at: unix_secs(sample.timestamp),
+                bytes_per_day: sample.size as f64 * 86_400.0 / elapsed,
+            })
+        })
+        .collect();
+
+    report.sort_by(|a, b| b.bytes_per_day.total_cmp(&a.bytes_per_day));
+    report
+}
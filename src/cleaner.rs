@@ -0,0 +1,239 @@
+use crate::audit;
+use crate::scanner::strategy::{default_strategies, expand_target, CleanCommand};
+use crate::scanner::{holds_keep_marker, CleanableProject, RiskLevel, KEEP_MARKER};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Which targets an unattended clean is allowed to touch.
+/// High-risk targets are never deleted unattended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CleanProfile {
+    /// Only low-risk artifacts that rebuild without side effects
+    Safe,
+    /// Low- and medium-risk artifacts
+    #[default]
+    Full,
+}
+
+impl CleanProfile {
+    /// Highest target risk level this profile may delete
+    pub fn max_risk(&self) -> RiskLevel {
+        match self {
+            Self::Safe => RiskLevel::Low,
+            Self::Full => RiskLevel::Medium,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Safe => "safe",
+            Self::Full => "full",
+        }
+    }
+}
+
+/// Directories no target may equal or contain: the scan root, the working
+/// directory, and the directory of the spektr binary. Defense in depth
+/// against strategy or config mistakes.
+pub struct ProtectedPaths {
+    paths: Vec<(PathBuf, &'static str)>,
+}
+
+impl ProtectedPaths {
+    pub fn new(scan_root: &Path) -> Self {
+        let candidates = [
+            (Some(scan_root.to_path_buf()), "the scan root"),
+            (std::env::current_dir().ok(), "the working directory"),
+            (
+                std::env::current_exe()
+                    .ok()
+                    .and_then(|exe| exe.parent().map(Path::to_path_buf)),
+                "the spektr binary's directory",
+            ),
+        ];
+
+        let paths = candidates
+            .into_iter()
+            .filter_map(|(path, label)| Some((canonical(&path?), label)))
+            .collect();
+
+        Self { paths }
+    }
+
+    /// Fails if any target of the project is, or contains, a protected directory
+    pub fn check(&self, project: &CleanableProject) -> Result<()> {
+        for target in &project.targets {
+            let target_path = canonical(&target.path);
+            if let Some((path, label)) = self.paths.iter().find(|(path, _)| path.starts_with(&target_path)) {
+                anyhow::bail!(
+                    "Refusing to delete {}: it contains {} ({})",
+                    target.path.display(),
+                    label,
+                    path.display()
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// A target left in place because it changed since the scan
+#[derive(Debug, Clone)]
+pub struct DriftedTarget {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+/// Targets of a project that no longer match their scan snapshot, or that
+/// hold a keep marker somewhere inside
+pub fn drifted_targets(project: &CleanableProject) -> Vec<DriftedTarget> {
+    project
+        .targets
+        .iter()
+        .filter_map(|target| {
+            let reason = if holds_keep_marker(&target.path) {
+                format!("it holds a {} marker", KEEP_MARKER)
+            } else {
+                target.snapshot?.drift(&target.path, target.size)?
+            };
+            Some(DriftedTarget {
+                path: target.path.clone(),
+                reason,
+            })
+        })
+        .collect()
+}
+
+/// The project without its drifted targets: what actually gets cleaned
+pub fn without_drifted(project: &CleanableProject, drifted: &[DriftedTarget]) -> CleanableProject {
+    let mut cleaned = project.clone();
+    cleaned.targets.retain(|target| !drifted.iter().any(|d| d.path == target.path));
+    cleaned.total_size = cleaned.targets.iter().map(|t| t.size).sum();
+    cleaned
+}
+
+/// A toolchain clean command that ran for a project
+#[derive(Debug, Clone)]
+pub struct NativeClean {
+    pub command: String,
+    pub success: bool,
+    /// Its stdout and stderr, or why it couldn't start
+    pub output: String,
+}
+
+/// What deleting a project did
+#[derive(Debug, Default)]
+pub struct Deletion {
+    /// Targets left in place because they changed since the scan
+    pub drifted: Vec<DriftedTarget>,
+    /// The toolchain's clean command, when `native_clean` ran one
+    pub native: Option<NativeClean>,
+}
+
+/// The project's clean command, if its strategy has one that applies (with
+/// `native_clean`, or always for installs their tool tracks) and every target
+/// the command removes, in the project and its members, is being cleaned
+fn clean_command(project: &CleanableProject, native_clean: bool) -> Option<CleanCommand> {
+    let strategy = default_strategies()
+        .into_iter()
+        .find(|strategy| strategy.name() == project.strategy_name)
+        .filter(|strategy| native_clean || strategy.prefers_clean_command())?;
+    let command = strategy.clean_command(&project.root_path)?;
+    let covered = std::iter::once(&project.root_path)
+        .chain(&project.members)
+        .flat_map(|root| command.removes.iter().flat_map(|target| expand_target(root, target)))
+        .filter(|path| path.exists())
+        .all(|path| project.targets.iter().any(|target| path.starts_with(&target.path)));
+    covered.then_some(command)
+}
+
+/// Whether the project's strategy removes its targets only with its tool,
+/// which keeps track of them (e.g., rustup toolchains)
+fn requires_clean_command(project: &CleanableProject) -> bool {
+    default_strategies()
+        .into_iter()
+        .find(|strategy| strategy.name() == project.strategy_name)
+        .is_some_and(|strategy| strategy.prefers_clean_command())
+}
+
+fn run_clean_command(command: &CleanCommand, root: &Path) -> NativeClean {
+    let (program, args) = command.argv.split_first().expect("clean commands name a program");
+    // Not from inside a directory the command removes (e.g., a toolchain)
+    let dir = if command.removes.iter().any(|target| target == ".") {
+        root.parent().unwrap_or(root)
+    } else {
+        root
+    };
+    let result = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .output();
+    match result {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            NativeClean {
+                command: command.display(),
+                success: output.status.success(),
+                output: text.trim().to_string(),
+            }
+        }
+        Err(e) => NativeClean {
+            command: command.display(),
+            success: false,
+            output: format!("Failed to run {}: {}", program, e),
+        },
+    }
+}
+
+/// Permanently removes all existing targets of a project, except those that
+/// changed since the scan. With `native_clean`, the toolchain's clean command
+/// runs first; whatever it leaves (or everything, if it fails) is deleted.
+/// Installs their tool keeps track of are only removed by the tool: nothing
+/// is deleted when its command is unavailable or fails.
+pub fn delete_project(project: &CleanableProject, protected: &ProtectedPaths, native_clean: bool) -> Result<Deletion> {
+    protected.check(project)?;
+
+    let drifted = drifted_targets(project);
+    let project = &without_drifted(project, &drifted);
+    let native = clean_command(project, native_clean).map(|command| run_clean_command(&command, &project.root_path));
+    if requires_clean_command(project) && !project.targets.is_empty() {
+        match &native {
+            None => anyhow::bail!(
+                "{} can only be removed by its tool, which has no command for it",
+                project.root_path.display()
+            ),
+            Some(native) if !native.success => anyhow::bail!(
+                "`{}` failed, so {} was left in place: {}",
+                native.command,
+                project.root_path.display(),
+                native.output
+            ),
+            Some(_) => {}
+        }
+    }
+    for target in &project.targets {
+        if target.path.exists() {
+            // Most targets are directories, but some caches are single files (e.g., `.eslintcache`)
+            let result = if target.path.is_dir() {
+                std::fs::remove_dir_all(&target.path)
+            } else {
+                std::fs::remove_file(&target.path)
+            };
+            result.with_context(|| format!("Failed to delete {}", target.path.display()))?;
+        }
+    }
+
+    // Best effort: the history is informational
+    let _ = audit::record(project, "delete");
+    Ok(Deletion { drifted, native })
+}
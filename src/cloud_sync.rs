@@ -0,0 +1,76 @@
+use std::path::{Component, Path};
+
+/// A file-sync client whose folder contains a project. Deleting large
+/// artifacts there also makes the client upload (or trash) thousands of files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncService {
+    Dropbox,
+    OneDrive,
+    GoogleDrive,
+    ICloud,
+}
+
+impl SyncService {
+    /// Detects the sync service from the folder names on `path`
+    pub fn detect(path: &Path) -> Option<Self> {
+        let names: Vec<String> = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+
+        names.iter().enumerate().find_map(|(idx, name)| {
+            let next = names.get(idx + 1).map(String::as_str);
+            match name.as_str() {
+                "Dropbox" => Some(Self::Dropbox),
+                name if name == "OneDrive" || name.starts_with("OneDrive - ") => Some(Self::OneDrive),
+                "Google Drive" | "My Drive" => Some(Self::GoogleDrive),
+                "iCloud Drive" => Some(Self::ICloud),
+                // macOS: ~/Library/Mobile Documents holds iCloud Drive, and
+                // ~/Library/CloudStorage holds File Provider based clients
+                "Mobile Documents" => Some(Self::ICloud),
+                "CloudStorage" => next.and_then(|provider| {
+                    if provider.starts_with("Dropbox") {
+                        Some(Self::Dropbox)
+                    } else if provider.starts_with("OneDrive") {
+                        Some(Self::OneDrive)
+                    } else if provider.starts_with("GoogleDrive") {
+                        Some(Self::GoogleDrive)
+                    } else {
+                        None
+                    }
+                }),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Dropbox => "Dropbox",
+            Self::OneDrive => "OneDrive",
+            Self::GoogleDrive => "Google Drive",
+            Self::ICloud => "iCloud Drive",
+        }
+    }
+
+    /// Command that keeps a target out of sync so it doesn't churn after the
+    /// next build, or `None` if the client can't ignore single folders
+    pub fn ignore_hint(&self, target: &Path) -> Option<String> {
+        let target = target.display();
+        match self {
+            Self::Dropbox if cfg!(target_os = "macos") => {
+                Some(format!("xattr -w com.dropbox.ignored 1 '{}'", target))
+            }
+            Self::Dropbox if cfg!(windows) => Some(format!(
+                "Set-Content -Path '{}' -Stream com.dropbox.ignored -Value 1",
+                target
+            )),
+            Self::Dropbox => Some(format!("attr -s com.dropbox.ignored -V 1 '{}'", target)),
+            Self::ICloud => Some(format!("mv '{0}' '{0}.nosync' && ln -s '{0}.nosync' '{0}'", target)),
+            Self::OneDrive | Self::GoogleDrive => None,
+        }
+    }
+}
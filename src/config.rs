@@ -0,0 +1,175 @@
+use crate::cleaner::CleanProfile;
+use crate::format::SizeUnits;
+use crate::icons::IconSet;
+use crate::tui::Theme;
+use crate::retention::RetentionRule;
+use crate::rules::PathRule;
+use crate::scanner::strategy::{CustomStrategyConfig, StrategySettings, TargetOverride};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// User configuration loaded from `<config dir>/spektr/config.toml`
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Preferred display units for sizes
+    pub units: Option<SizeUnits>,
+
+    /// Icons next to projects: "emoji", "ascii", or "nerd"
+    pub icons: Option<IconSet>,
+
+    /// TUI colors: "auto" (ask the terminal), "dark", or "light"
+    pub theme: Option<Theme>,
+
+    /// Projects the TUI keeps in memory before spilling the smallest to disk
+    pub max_projects: Option<usize>,
+
+    /// Clean with the toolchains' own commands (`cargo clean`, `gradle clean`,
+    /// ...) where there is one (`--native-clean`)
+    pub native_clean: bool,
+
+    /// Where archived targets are stored (defaults to `<data dir>/spektr/archives`)
+    pub archive_dir: Option<PathBuf>,
+
+    /// SMTP relay used by `--report-email`
+    pub smtp: Option<SmtpConfig>,
+
+    /// Built-in strategies that never run (e.g., `["Android"]`)
+    pub disabled_strategies: Vec<String>,
+
+    /// Opt-in strategies to run (e.g., `["Leftover temp"]`)
+    pub enabled_strategies: Vec<String>,
+
+    /// Directory name globs for the "Leftover temp" strategy, replacing the
+    /// built-in ones (`*-unpacked`, `tmp-extract-*`, `*.extracted`, `_extract_*`)
+    pub leftover_temp_patterns: Vec<String>,
+
+    /// Reuse sizes from earlier scans for targets whose top level is
+    /// unchanged (`--cached-sizes`); such sizes are marked as estimates
+    pub cached_sizes: bool,
+
+    /// Newest versions of each tool in versioned caches (Playwright browsers,
+    /// Gradle wrapper distributions, ...) that `--global` doesn't offer (`--keep-latest`)
+    pub keep_latest_versions: Option<usize>,
+
+    /// Same as `keep_latest_versions = 1`, from before other versioned caches
+    /// were listed; `keep_latest_versions` wins when both are set
+    pub keep_latest_binaries: bool,
+
+    /// Only offer projects whose build output hasn't changed in this many
+    /// days (`--older-than`)
+    pub older_than_days: Option<u64>,
+
+    /// Strategies for project types spektr doesn't know (`[[strategies]]`
+    /// tables with `name`, `marker_files`, `targets`, `risk`, and `icon`)
+    pub strategies: Vec<CustomStrategyConfig>,
+
+    /// Targets added to or removed from built-in strategies, keyed by strategy
+    /// name (e.g., `[target_overrides.node] add = [".angular"], remove = ["dist"]`)
+    pub target_overrides: HashMap<String, TargetOverride>,
+
+    /// Risk and auto-selection rules for projects under path globs
+    /// (`[[path_rules]]` tables)
+    pub path_rules: Vec<PathRule>,
+
+    /// Rules for `--policy`, which proposes cleaning only what they select
+    /// (`[[retention]]` tables)
+    pub retention: Vec<RetentionRule>,
+
+    /// Defaults for `spektr watch-space`
+    pub space_watch: Option<SpaceWatchConfig>,
+
+    /// Don't descend into hidden directories during discovery (`--skip-hidden`)
+    pub skip_hidden: bool,
+
+    /// Hidden directory names still walked when skipping hidden directories,
+    /// in addition to `.cache` (e.g., `[".config"]`)
+    pub walk_hidden: Vec<String>,
+
+    /// Directory on a scratch disk that `spektr relocate` moves build output to
+    pub scratch_dir: Option<PathBuf>,
+
+    /// Include paths (last scanned directory, panic messages) in crash dumps
+    pub crash_dump_paths: bool,
+}
+
+/// Free-space monitoring: when the volume holding one of `paths` drops below
+/// `threshold_percent` free, that path is cleaned (or the user is notified)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SpaceWatchConfig {
+    /// Directories to clean; each one's volume is monitored
+    pub paths: Vec<PathBuf>,
+    pub threshold_percent: Option<f64>,
+    /// Only notify instead of cleaning
+    pub notify_only: bool,
+    pub profile: Option<CleanProfile>,
+    /// Seconds between checks
+    pub interval_secs: Option<u64>,
+    /// Artifact budgets for directory trees, enforced on every check
+    /// (`[[space_watch.budgets]]` tables)
+    pub budgets: Vec<BudgetConfig>,
+    /// Percentage of a budget that enforcing it cleans down to, so the tree
+    /// doesn't go over again right away (default 90)
+    pub budget_low_water_percent: Option<f64>,
+}
+
+/// Most artifact bytes allowed under a directory, e.g.
+/// `{ path = "~/work", max_total = "100 GB" }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BudgetConfig {
+    /// A leading `~` is the home directory
+    pub path: String,
+    pub max_total: String,
+}
+
+/// SMTP settings for emailing reports
+#[derive(Debug, Deserialize)]
+pub struct SmtpConfig {
+    pub host: String,
+    #[serde(default = "default_smtp_port")]
+    pub port: u16,
+    pub from: String,
+    pub username: Option<String>,
+    pub password: Option<String>,
+}
+
+fn default_smtp_port() -> u16 {
+    587
+}
+
+impl Config {
+    /// Strategy adjustments from the config, before any CLI flags
+    pub fn strategy_settings(&self) -> StrategySettings {
+        StrategySettings {
+            disabled: self.disabled_strategies.clone(),
+            enabled: self.enabled_strategies.clone(),
+            overrides: self.target_overrides.clone(),
+            leftover_temp_patterns: self.leftover_temp_patterns.clone(),
+            custom: self.strategies.clone(),
+        }
+    }
+
+    /// Location of the config file, if the platform has a config directory
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("spektr").join("config.toml"))
+    }
+
+    /// Loads the config file, falling back to defaults when it doesn't exist
+    pub fn load() -> Result<Self> {
+        let Some(path) = Self::path() else {
+            return Ok(Self::default());
+        };
+
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))
+    }
+}
@@ -0,0 +1,178 @@
+use crate::scanner::ScanEvent;
+use crossterm::terminal::{disable_raw_mode, LeaveAlternateScreen};
+use serde::Serialize;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What spektr was doing, kept up to date for the crash dump. Only counts and
+/// event kinds unless the user opted in to paths (`crash_dump_paths`).
+#[derive(Debug, Default, Clone, Serialize)]
+struct CrashState {
+    mode: Option<String>,
+    tui_active: bool,
+    phase: Option<String>,
+    last_event: Option<&'static str>,
+    events: u64,
+    projects_found: usize,
+    projects_pending: usize,
+    already_clean: usize,
+    directories_discovered: u64,
+    #[serde(skip)]
+    include_paths: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_path: Option<String>,
+}
+
+/// Written to `<data dir>/spektr/crashes/` when spektr panics
+#[derive(Debug, Serialize)]
+struct CrashDump {
+    version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    /// Unix time of the crash
+    timestamp: u64,
+    thread: Option<String>,
+    message: String,
+    location: Option<String>,
+    state: CrashState,
+}
+
+static STATE: Mutex<CrashState> = Mutex::new(CrashState {
+    mode: None,
+    tui_active: false,
+    phase: None,
+    last_event: None,
+    events: 0,
+    projects_found: 0,
+    projects_pending: 0,
+    already_clean: 0,
+    directories_discovered: 0,
+    include_paths: false,
+    last_path: None,
+});
+
+/// Where crash dumps go: `<data dir>/spektr/crashes`
+pub fn dump_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("spektr").join("crashes"))
+}
+
+/// A panic elsewhere must not keep the dump from being written
+fn state() -> MutexGuard<'static, CrashState> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Installs the panic hook that restores the terminal and writes the dump
+pub fn install() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let state = state().clone();
+        if state.tui_active {
+            // Otherwise the panic message lands on the alternate screen, in raw mode
+            let _ = disable_raw_mode();
+            let _ = crossterm::execute!(std::io::stdout(), LeaveAlternateScreen);
+        }
+        default_hook(info);
+
+        match write_dump(info, state) {
+            Some(path) => eprintln!(
+                "💥 spektr crashed. A state dump was written to {}\n   Please attach it to a bug report: https://github.com/jcyrus/spektr/issues",
+                path.display()
+            ),
+            None => eprintln!("💥 spektr crashed, and the state dump couldn't be written"),
+        }
+    }));
+}
+
+/// Run mode (or command) shown in the dump
+pub fn set_mode(mode: impl Into<String>) {
+    state().mode = Some(mode.into());
+}
+
+/// Include the last scanned path and unredacted panic messages in the dump
+pub fn set_include_paths(include: bool) {
+    state().include_paths = include;
+}
+
+/// Whether the TUI owns the terminal, which the hook then restores
+pub fn set_tui_active(active: bool) {
+    state().tui_active = active;
+}
+
+/// Notes a scan event: its kind, and the counts it changes
+pub fn record_event(event: &ScanEvent) {
+    let mut state = state();
+    state.events += 1;
+    state.last_event = Some(match event {
+        ScanEvent::Scanning(path) => {
+            if state.include_paths {
+                state.last_path = Some(path.clone());
+            }
+            "scanning"
+        }
+        ScanEvent::Analyzing(_) => "analyzing",
+        ScanEvent::Discovered(stats) => {
+            state.directories_discovered = stats.directories;
+            "discovered"
+        }
+        ScanEvent::Phase(phase) => {
+            state.phase = Some(phase.label());
+            "phase"
+        }
+        ScanEvent::ProjectPending(_) => {
+            state.projects_pending += 1;
+            "project_pending"
+        }
+        ScanEvent::ProjectFound(_) => {
+            state.projects_pending = state.projects_pending.saturating_sub(1);
+            state.projects_found += 1;
+            "project_found"
+        }
+        ScanEvent::AlreadyClean(roots) => {
+            state.already_clean = roots.len();
+            "already_clean"
+        }
+        ScanEvent::Complete => "complete",
+        ScanEvent::Failed(_) => "failed",
+    });
+}
+
+fn write_dump(info: &PanicHookInfo, state: CrashState) -> Option<PathBuf> {
+    let message = info
+        .payload()
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| info.payload().downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(non-string panic payload)".to_string());
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    let dump = CrashDump {
+        version: env!("CARGO_PKG_VERSION"),
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        timestamp,
+        thread: std::thread::current().name().map(str::to_string),
+        message: if state.include_paths { message } else { redact_paths(&message) },
+        location: info.location().map(|l| format!("{}:{}", l.file(), l.line())),
+        state,
+    };
+
+    let dir = dump_dir()?;
+    std::fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(format!("crash-{}-{}.json", timestamp, std::process::id()));
+    std::fs::write(&path, serde_json::to_string_pretty(&dump).ok()?).ok()?;
+    Some(path)
+}
+
+/// Replaces every word that looks like a path with `<path>`
+fn redact_paths(message: &str) -> String {
+    message
+        .split(' ')
+        .map(|word| if word.contains(['/', '\\']) { "<path>" } else { word })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,194 @@
+use crate::archive;
+use crate::audit;
+use crate::config::Config;
+use crate::crash;
+use crate::format::{format_age, format_size, SizeUnits};
+use crate::index;
+use crate::scanner::strategy::configured_strategies;
+use crate::scanner::SizeCache;
+use crate::tui;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Prints the environment details that bug reports need: platform, terminal,
+/// config, strategies, caches, and whether spektr's directories are writable.
+/// Plain text on stdout, so it can be pasted into an issue as is.
+pub fn run() {
+    println!("spektr {}", env!("CARGO_PKG_VERSION"));
+
+    section("Platform");
+    item("OS", format!("{} ({})", std::env::consts::OS, std::env::consts::ARCH));
+    item("CPUs", num_cpus::get().to_string());
+
+    section("Terminal");
+    item("stdout", if std::io::stdout().is_terminal() { "terminal" } else { "not a terminal" });
+    item("TERM", env_or_unset("TERM"));
+    item("COLORTERM", env_or_unset("COLORTERM"));
+    item("NO_COLOR", env_or_unset("NO_COLOR"));
+    item(
+        "TUI",
+        if tui::is_supported() {
+            "supported"
+        } else {
+            "not supported (falls back to pick mode)"
+        },
+    );
+
+    section("Configuration");
+    let config = match Config::path() {
+        None => {
+            item("File", "no config directory on this platform");
+            Some(Config::default())
+        }
+        Some(path) if !path.exists() => {
+            item("File", format!("{} (not found, using defaults)", path.display()));
+            Some(Config::default())
+        }
+        Some(path) => {
+            item("File", path.display().to_string());
+            match Config::load() {
+                Ok(config) => {
+                    item("Status", "parsed");
+                    Some(config)
+                }
+                Err(e) => {
+                    item("Status", format!("error: {:#}", e));
+                    None
+                }
+            }
+        }
+    };
+
+    section("Strategies");
+    match &config {
+        Some(config) => {
+            let settings = config.strategy_settings();
+            if let Err(e) = settings.validate() {
+                item("Settings", format!("error: {:#}", e));
+            }
+            let enabled: Vec<String> = configured_strategies(&settings)
+                .iter()
+                .map(|s| s.name().to_string())
+                .collect();
+            let disabled: Vec<String> = settings
+                .all_strategies()
+                .iter()
+                .map(|s| s.name().to_string())
+                .filter(|name| !enabled.contains(name))
+                .collect();
+            item("Enabled", format!("{} ({})", enabled.len(), enabled.join(", ")));
+            if !disabled.is_empty() {
+                item("Disabled", disabled.join(", "));
+            }
+            if !config.target_overrides.is_empty() {
+                let mut overridden: Vec<&String> = config.target_overrides.keys().collect();
+                overridden.sort();
+                item(
+                    "Target overrides",
+                    overridden.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", "),
+                );
+            }
+            if !config.path_rules.is_empty() {
+                item("Path rules", config.path_rules.len().to_string());
+            }
+            if !config.retention.is_empty() {
+                item("Retention rules", config.retention.len().to_string());
+            }
+        }
+        None => item("Enabled", "unknown (the config file doesn't parse)"),
+    }
+
+    section("Caches and history");
+    item("Size cache", describe_file(SizeCache::path()));
+    item("Artifact indexes", describe_dir(index::index_dir()));
+    item("Audit log", describe_file(audit::audit_path()));
+    item("Size history", describe_file(audit::sizes_path()));
+    item("Space history", describe_file(audit::space_path()));
+    item("Budget log", describe_file(audit::budgets_path()));
+    item("Crash dumps", describe_dir(crash::dump_dir()));
+
+    section("Directories");
+    let archive_dir = config
+        .as_ref()
+        .and_then(|c| c.archive_dir.clone())
+        .or_else(archive::default_archive_dir);
+    item("Config", describe_access(dirs::config_dir().map(|d| d.join("spektr"))));
+    item("Data", describe_access(dirs::data_dir().map(|d| d.join("spektr"))));
+    item("Cache", describe_access(dirs::cache_dir().map(|d| d.join("spektr"))));
+    item("Archives", describe_access(archive_dir));
+}
+
+fn section(title: &str) {
+    println!();
+    println!("{}:", title);
+}
+
+fn item(label: &str, value: impl AsRef<str>) {
+    println!("   {:<17} {}", format!("{}:", label), value.as_ref());
+}
+
+fn env_or_unset(name: &str) -> String {
+    std::env::var(name).unwrap_or_else(|_| "(unset)".to_string())
+}
+
+/// "<path> (12.30 KB, updated 3 days ago)", or why there's nothing to describe
+fn describe_file(path: Option<PathBuf>) -> String {
+    let Some(path) = path else {
+        return "no directory for it on this platform".to_string();
+    };
+    match path.metadata() {
+        Ok(metadata) => {
+            let age = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .map(|age| format!(", updated {} ago", format_age(age)))
+                .unwrap_or_default();
+            format!("{} ({}{})", path.display(), format_size(metadata.len(), SizeUnits::Auto), age)
+        }
+        Err(_) => format!("{} (not created yet)", path.display()),
+    }
+}
+
+/// "<path> (3 entries)", or why there's nothing to describe
+fn describe_dir(path: Option<PathBuf>) -> String {
+    let Some(path) = path else {
+        return "no directory for it on this platform".to_string();
+    };
+    match std::fs::read_dir(&path) {
+        Ok(entries) => format!("{} ({} entries)", path.display(), entries.count()),
+        Err(_) => format!("{} (not created yet)", path.display()),
+    }
+}
+
+/// Whether spektr can write to a directory it uses
+fn describe_access(path: Option<PathBuf>) -> String {
+    let Some(path) = path else {
+        return "no directory for it on this platform".to_string();
+    };
+    if !path.is_dir() {
+        let parent_writable = path
+            .ancestors()
+            .find(|ancestor| ancestor.is_dir())
+            .is_some_and(is_writable);
+        return format!(
+            "{} (not created yet; {})",
+            path.display(),
+            if parent_writable { "can be created" } else { "parent is not writable" }
+        );
+    }
+    format!(
+        "{} ({})",
+        path.display(),
+        if is_writable(&path) { "writable" } else { "not writable" }
+    )
+}
+
+/// Checks by creating (and removing) a probe file
+fn is_writable(dir: &Path) -> bool {
+    let probe = dir.join(format!(".spektr-doctor-{}", std::process::id()));
+    let writable = std::fs::File::create(&probe).is_ok();
+    let _ = std::fs::remove_file(&probe);
+    writable
+}
@@ -0,0 +1,29 @@
+use crate::config::SmtpConfig;
+use anyhow::{Context, Result};
+use lettre::message::Message;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{SmtpTransport, Transport};
+
+/// Sends a plain-text email through the configured SMTP relay (STARTTLS)
+pub fn send_email(smtp: &SmtpConfig, to: &str, subject: &str, body: String) -> Result<()> {
+    let message = Message::builder()
+        .from(smtp.from.parse().context("Invalid `from` address in SMTP config")?)
+        .to(to.parse().context("Invalid report email address")?)
+        .subject(subject)
+        .body(body)?;
+
+    let mut transport = SmtpTransport::starttls_relay(&smtp.host)
+        .with_context(|| format!("Failed to connect to SMTP host {}", smtp.host))?
+        .port(smtp.port);
+
+    if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+        transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    transport
+        .build()
+        .send(&message)
+        .context("Failed to send report email")?;
+
+    Ok(())
+}
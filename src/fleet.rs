@@ -0,0 +1,178 @@
+use crate::scanner::CleanableProject;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Format version of [`ScanReport`] files
+const REPORT_VERSION: u32 = 1;
+
+/// Scan results of one user on one machine, written by `--json-report` and
+/// combined across a fleet by `spektr report --merge`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanReport {
+    pub version: u32,
+    pub host: String,
+    pub user: String,
+    pub scan_root: PathBuf,
+    /// Unix time of the scan
+    pub generated_at: u64,
+    pub projects: Vec<ReportedProject>,
+}
+
+/// One project in a [`ScanReport`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportedProject {
+    pub path: PathBuf,
+    pub strategy: String,
+    pub size: u64,
+}
+
+impl ScanReport {
+    pub fn new(scan_root: &Path, projects: &[CleanableProject]) -> Self {
+        Self {
+            version: REPORT_VERSION,
+            host: hostname(),
+            user: username(),
+            scan_root: scan_root.to_path_buf(),
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            projects: projects
+                .iter()
+                .map(|p| ReportedProject {
+                    path: p.root_path.clone(),
+                    strategy: p.strategy_name.clone(),
+                    size: p.total_size,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write report to {}", path.display()))
+    }
+
+    fn total_size(&self) -> u64 {
+        self.projects.iter().map(|p| p.size).sum()
+    }
+}
+
+/// Machine name from the environment or `/etc/hostname`
+fn hostname() -> String {
+    std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn username() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Totals for one host, user, or ecosystem across the merged reports
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupTotal {
+    pub name: String,
+    pub reports: usize,
+    pub projects: usize,
+    pub total_size: u64,
+}
+
+/// Aggregate of a directory of scan reports
+#[derive(Debug, Default, Serialize)]
+pub struct FleetSummary {
+    pub reports: usize,
+    pub projects: usize,
+    pub total_size: u64,
+    pub hosts: Vec<GroupTotal>,
+    pub users: Vec<GroupTotal>,
+    pub ecosystems: Vec<GroupTotal>,
+    /// Files that weren't scan reports, with the reason
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// Reads every `*.json` report in `dir` and totals them per host, user, and
+/// ecosystem. When one user exported the same scan root on the same host
+/// more than once, only the newest report counts.
+pub fn merge(dir: &Path) -> Result<FleetSummary> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read {}", dir.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+
+    let mut summary = FleetSummary::default();
+    let mut latest: HashMap<(String, String, PathBuf), ScanReport> = HashMap::new();
+    for file in files {
+        let parsed = std::fs::read_to_string(&file)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| serde_json::from_str::<ScanReport>(&content).map_err(anyhow::Error::from));
+        let report = match parsed {
+            Ok(report) if report.version == REPORT_VERSION => report,
+            Ok(report) => {
+                summary.skipped.push((file, format!("unsupported report version {}", report.version)));
+                continue;
+            }
+            Err(e) => {
+                summary.skipped.push((file, format!("{:#}", e)));
+                continue;
+            }
+        };
+        let key = (report.host.clone(), report.user.clone(), report.scan_root.clone());
+        if latest.get(&key).is_none_or(|kept| report.generated_at > kept.generated_at) {
+            latest.insert(key, report);
+        }
+    }
+
+    let reports: Vec<ScanReport> = latest.into_values().collect();
+    summary.reports = reports.len();
+    summary.projects = reports.iter().map(|r| r.projects.len()).sum();
+    summary.total_size = reports.iter().map(ScanReport::total_size).sum();
+    summary.hosts = group(&reports, |report| vec![(report.host.clone(), report.projects.iter().collect())]);
+    summary.users = group(&reports, |report| vec![(report.user.clone(), report.projects.iter().collect())]);
+    summary.ecosystems = group(&reports, |report| {
+        let mut by_strategy: HashMap<String, Vec<&ReportedProject>> = HashMap::new();
+        for project in &report.projects {
+            by_strategy.entry(project.strategy.clone()).or_default().push(project);
+        }
+        by_strategy.into_iter().collect()
+    });
+    Ok(summary)
+}
+
+/// Totals of the groups that `split` assigns each report's projects to,
+/// largest first
+fn group<'a>(
+    reports: &'a [ScanReport],
+    split: impl Fn(&'a ScanReport) -> Vec<(String, Vec<&'a ReportedProject>)>,
+) -> Vec<GroupTotal> {
+    let mut groups: HashMap<String, GroupTotal> = HashMap::new();
+    for report in reports {
+        for (name, projects) in split(report) {
+            let total = groups.entry(name.clone()).or_insert_with(|| GroupTotal {
+                name,
+                reports: 0,
+                projects: 0,
+                total_size: 0,
+            });
+            total.reports += 1;
+            total.projects += projects.len();
+            total.total_size += projects.iter().map(|p| p.size).sum::<u64>();
+        }
+    }
+
+    let mut totals: Vec<GroupTotal> = groups.into_values().collect();
+    totals.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.name.cmp(&b.name)));
+    totals
+}
@@ -0,0 +1,125 @@
+use anyhow::{anyhow, bail, Result};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::path::Path;
+
+/// Display units for sizes (multiples of 1024)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeUnits {
+    /// Pick the largest unit that fits (B, KB, MB, GB)
+    #[default]
+    Auto,
+    /// Always megabytes
+    #[value(alias = "mib")]
+    #[serde(alias = "mib")]
+    Mb,
+    /// Always gigabytes
+    #[value(alias = "gib")]
+    #[serde(alias = "gib")]
+    Gb,
+    /// Exact byte counts
+    Bytes,
+}
+
+const KB: u64 = 1024;
+const MB: u64 = KB * 1024;
+const GB: u64 = MB * 1024;
+
+/// Formats a byte count using the given display units
+pub fn format_size(bytes: u64, units: SizeUnits) -> String {
+    match units {
+        SizeUnits::Auto => {
+            if bytes >= GB {
+                format!("{:.2} GB", bytes as f64 / GB as f64)
+            } else if bytes >= MB {
+                format!("{:.2} MB", bytes as f64 / MB as f64)
+            } else if bytes >= KB {
+                format!("{:.2} KB", bytes as f64 / KB as f64)
+            } else {
+                format!("{} B", bytes)
+            }
+        }
+        SizeUnits::Mb => format!("{:.2} MB", bytes as f64 / MB as f64),
+        SizeUnits::Gb => format!("{:.2} GB", bytes as f64 / GB as f64),
+        SizeUnits::Bytes => format!("{} B", bytes),
+    }
+}
+
+/// Formats an exact byte count with thousands separators (e.g., "1,234,567 bytes")
+pub fn format_bytes_exact(bytes: u64) -> String {
+    let digits = bytes.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    format!("{} bytes", grouped)
+}
+
+/// Formats an elapsed time in its largest whole unit (e.g., "12 days", "3 hours")
+pub fn format_age(age: std::time::Duration) -> String {
+    let secs = age.as_secs();
+    if secs < 60 {
+        return "less than a minute".to_string();
+    }
+    let (count, unit) = match secs {
+        60..=3_599 => (secs / 60, "minute"),
+        3_600..=86_399 => (secs / 3_600, "hour"),
+        _ => (secs / 86_400, "day"),
+    };
+    format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" })
+}
+
+/// Formats a short elapsed time (e.g., "120 ms", "4.2 s", "3m 05s")
+pub fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs_f64();
+    if secs < 1.0 {
+        format!("{} ms", elapsed.as_millis())
+    } else if secs < 60.0 {
+        format!("{:.1} s", secs)
+    } else {
+        format!("{}m {:02}s", elapsed.as_secs() / 60, elapsed.as_secs() % 60)
+    }
+}
+
+/// Formats a count compactly (e.g., "950", "12.3K", "1.9M")
+pub fn format_count(count: u64) -> String {
+    match count {
+        0..=9_999 => count.to_string(),
+        10_000..=999_999 => format!("{:.1}K", count as f64 / 1_000.0),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+/// Single-quotes a path for a POSIX shell, so nothing in it expands; each
+/// `'` ends the quotes for an escaped one (`'\''`)
+pub fn shell_quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', "'\\''"))
+}
+
+/// Parses a size such as "100 GB", "1.5T", "500 MiB", or "4096" (bytes).
+/// Units are multiples of 1024, like the ones sizes are shown in.
+pub fn parse_size(input: &str) -> Result<u64> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid size '{}' (expected e.g. \"100 GB\")", input))?;
+    let multiplier = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => KB,
+        "m" | "mb" | "mib" => MB,
+        "g" | "gb" | "gib" => GB,
+        "t" | "tb" | "tib" => GB * 1024,
+        _ => bail!("Unknown size unit in '{}' (use B, KB, MB, GB, or TB)", input),
+    };
+    Ok((number * multiplier as f64) as u64)
+}
@@ -0,0 +1,90 @@
+use crate::scanner::strategy::{default_strategies, CustomStrategyConfig};
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Icons shown next to projects in scan output and the TUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IconSet {
+    /// Emoji (double width; misaligns columns on some Windows terminals)
+    #[default]
+    Emoji,
+    /// A single ASCII letter per project type
+    Ascii,
+    /// Language glyphs from a Nerd Font (single width; needs a patched font)
+    Nerd,
+}
+
+/// A strategy's icon in each icon set
+#[derive(Debug, Clone, Copy)]
+pub struct StrategyIcons {
+    pub emoji: &'static str,
+    pub ascii: &'static str,
+    pub nerd: &'static str,
+}
+
+impl StrategyIcons {
+    pub fn get(&self, set: IconSet) -> &'static str {
+        match set {
+            IconSet::Emoji => self.emoji,
+            IconSet::Ascii => self.ascii,
+            IconSet::Nerd => self.nerd,
+        }
+    }
+}
+
+/// Icon of directories without a project (tree view folders) and of unknown strategies
+const FOLDER: StrategyIcons = StrategyIcons {
+    emoji: "📁",
+    ascii: "/",
+    nerd: "\u{f07b}",
+};
+
+/// Icons of the built-in strategies, keyed by strategy name
+fn registry() -> &'static HashMap<String, StrategyIcons> {
+    static REGISTRY: OnceLock<HashMap<String, StrategyIcons>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        default_strategies()
+            .iter()
+            .map(|strategy| (strategy.name().to_string(), strategy.icons()))
+            .collect()
+    })
+}
+
+/// Icons of the strategies declared in the config, set once at startup
+static CUSTOM: OnceLock<HashMap<String, StrategyIcons>> = OnceLock::new();
+
+/// Makes the icons of custom strategies known; only the first call counts
+pub fn register_custom(strategies: &[CustomStrategyConfig]) {
+    let _ = CUSTOM.set(
+        strategies
+            .iter()
+            .map(|strategy| (strategy.name.clone(), strategy.leak_icons()))
+            .collect(),
+    );
+}
+
+/// Icons of a custom strategy, or the folder icons if it wasn't registered
+pub fn custom_icons(strategy_name: &str) -> StrategyIcons {
+    CUSTOM
+        .get()
+        .and_then(|custom| custom.get(strategy_name))
+        .copied()
+        .unwrap_or(FOLDER)
+}
+
+/// Icon shown next to a project of the named strategy
+pub fn strategy_icon(strategy_name: &str, set: IconSet) -> &'static str {
+    registry()
+        .get(strategy_name)
+        .or_else(|| CUSTOM.get()?.get(strategy_name))
+        .unwrap_or(&FOLDER)
+        .get(set)
+}
+
+/// Icon shown next to a directory that isn't a project
+pub fn folder_icon(set: IconSet) -> &'static str {
+    FOLDER.get(set)
+}
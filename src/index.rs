@@ -0,0 +1,113 @@
+use crate::lock::root_key;
+use crate::rules::PathRules;
+use crate::scanner::strategy::{configured_strategies, StrategySettings};
+use crate::scanner::{CleanableProject, Scanner, SizeCache};
+use anyhow::{Context, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+/// Quiet period after a filesystem change before the index is rebuilt
+const DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// Snapshot of the projects under a scan root, maintained by `spektr index`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArtifactIndex {
+    pub root: PathBuf,
+    pub updated_at: SystemTime,
+    pub projects: Vec<CleanableProject>,
+}
+
+/// Directory holding the artifact indexes: `<cache dir>/spektr/index`
+pub fn index_dir() -> Option<PathBuf> {
+    dirs::cache_dir().map(|dir| dir.join("spektr").join("index"))
+}
+
+/// Index file location for a scan root: `<cache dir>/spektr/index/<hash>.json`
+fn index_path(root: &Path) -> Option<PathBuf> {
+    index_dir().map(|dir| dir.join(format!("{}.json", root_key(root))))
+}
+
+impl ArtifactIndex {
+    /// Loads the index for `root`, if an indexer has written one
+    pub fn load(root: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(index_path(root)?).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = index_path(&self.root).context("Failed to determine cache directory")?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+            .with_context(|| format!("Failed to write index {}", path.display()))
+    }
+}
+
+/// Scans `root` and writes a fresh index
+fn rebuild(root: &Path, settings: &StrategySettings, rules: &PathRules) -> Result<usize> {
+    // Events are not consumed; the receiver only has to outlive the scan
+    let (tx, _rx) = mpsc::channel();
+    let scanner = Scanner::new(configured_strategies(settings))
+        .with_size_cache(SizeCache::load())
+        .with_path_rules(rules.clone());
+    let projects = scanner.scan(root, tx)?;
+    let count = projects.len();
+
+    ArtifactIndex {
+        root: root.to_path_buf(),
+        updated_at: SystemTime::now(),
+        projects,
+    }
+    .save()?;
+
+    Ok(count)
+}
+
+/// Keeps the index for `root` up to date, rebuilding it whenever the tree
+/// changes. Runs until interrupted.
+pub fn run_indexer(root: &Path, settings: &StrategySettings, rules: &PathRules) -> Result<()> {
+    let root = root.canonicalize().context("Failed to resolve path to index")?;
+    let ignored = index_dir().and_then(|dir| dir.parent().map(Path::to_path_buf));
+
+    println!("👀 Indexing {} (Ctrl+C to stop)", root.display());
+    println!("   Indexed {} projects", rebuild(&root, settings, rules)?);
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch {}", root.display()))?;
+
+    let is_relevant = |event: &notify::Result<notify::Event>| match event {
+        Ok(event) => {
+            !matches!(event.kind, EventKind::Access(_))
+                && event
+                    .paths
+                    .iter()
+                    .any(|p| ignored.as_ref().is_none_or(|dir| !p.starts_with(dir)))
+        }
+        Err(_) => false,
+    };
+
+    while let Ok(event) = rx.recv() {
+        if !is_relevant(&event) {
+            continue;
+        }
+
+        // Wait for the burst of changes to settle before rescanning
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+        let count = rebuild(&root, settings, rules)?;
+        println!(
+            "   {} Re-indexed {} projects",
+            humantime::format_rfc3339_seconds(SystemTime::now()),
+            count
+        );
+    }
+
+    Ok(())
+}
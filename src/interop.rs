@@ -0,0 +1,155 @@
+use crate::scanner::CleanableProject;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Result formats of similar tools, for `--export` and `--paths-format`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum InteropFormat {
+    /// kondo's listing: a "<path> <type> project" line per project,
+    /// followed by "  └─ <artifact> (<size>)" lines
+    Kondo,
+    /// npkill's JSON report: `{"version": 1, "results": [{"path", "size", ...}]}`
+    /// with one result per artifact directory
+    Npkill,
+}
+
+/// Path list format accepted by `--paths-from`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum PathsFormat {
+    /// One project root per line (or NUL-separated)
+    #[default]
+    Plain,
+    /// kondo's listing; project lines are used, artifact lines ignored
+    Kondo,
+    /// npkill's JSON report (or one path per line); each result's parent
+    /// directory is a project root
+    Npkill,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NpkillResult {
+    path: PathBuf,
+    #[serde(default)]
+    size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    modification_time: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NpkillReport {
+    version: u32,
+    results: Vec<NpkillResult>,
+}
+
+/// Renders scan results in another tool's format
+pub fn export(projects: &[CleanableProject], format: InteropFormat) -> Result<String> {
+    match format {
+        InteropFormat::Kondo => {
+            let mut text = String::new();
+            for project in projects {
+                writeln!(text, "{} {} project", project.root_path.display(), project.strategy_name)?;
+                for target in &project.targets {
+                    // Self-contained caches are their own target: show the full path
+                    let name = match target.path.strip_prefix(&project.root_path) {
+                        Ok(relative) if !relative.as_os_str().is_empty() => relative,
+                        _ => target.path.as_path(),
+                    };
+                    writeln!(text, "  └─ {} ({})", name.display(), kondo_size(target.size))?;
+                }
+            }
+            Ok(text)
+        }
+        InteropFormat::Npkill => {
+            let results = projects
+                .iter()
+                .flat_map(|project| {
+                    project.targets.iter().map(|target| NpkillResult {
+                        path: target.path.clone(),
+                        size: target.size,
+                        modification_time: target
+                            .path
+                            .metadata()
+                            .and_then(|m| m.modified())
+                            .ok()
+                            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                            .map(|age| age.as_secs()),
+                    })
+                })
+                .collect();
+            let report = NpkillReport { version: 1, results };
+            Ok(serde_json::to_string_pretty(&report)? + "\n")
+        }
+    }
+}
+
+/// Extracts project roots from a path list. The roots are checked by the
+/// scanner like any `--paths-from` entry, so stale or foreign entries are ignored.
+pub fn parse_paths(content: &str, format: PathsFormat) -> Result<Vec<PathBuf>> {
+    let lines = || {
+        content
+            .split(['\n', '\0'])
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.trim().is_empty())
+    };
+
+    let mut roots: Vec<PathBuf> = match format {
+        PathsFormat::Plain => lines().map(PathBuf::from).collect(),
+        PathsFormat::Kondo => lines()
+            .filter(|line| !line.starts_with(' '))
+            .filter_map(kondo_project_root)
+            .collect(),
+        PathsFormat::Npkill => {
+            let targets: Vec<PathBuf> = if content.trim_start().starts_with('{') {
+                let report: NpkillReport =
+                    serde_json::from_str(content).context("Failed to parse npkill JSON report")?;
+                report.results.into_iter().map(|r| r.path).collect()
+            } else {
+                lines().map(PathBuf::from).collect()
+            };
+            targets
+                .iter()
+                .filter_map(|target| target.parent().map(Path::to_path_buf))
+                .collect()
+        }
+    };
+
+    let mut seen = HashSet::new();
+    roots.retain(|root| seen.insert(root.clone()));
+    Ok(roots)
+}
+
+/// The path of a kondo project line ("<path> <type> project"). Project types
+/// can contain spaces (e.g., "Godot 4"), so words are dropped from the end
+/// until the rest names an existing directory.
+fn kondo_project_root(line: &str) -> Option<PathBuf> {
+    let mut rest = line.strip_suffix(" project")?;
+    while let Some((path, _)) = rest.rsplit_once(' ') {
+        if Path::new(path).is_dir() {
+            return Some(PathBuf::from(path));
+        }
+        rest = path;
+    }
+    None
+}
+
+/// Sizes as kondo prints them (binary units, one decimal)
+fn kondo_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", size, UNITS[unit])
+    }
+}
@@ -0,0 +1,159 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Who holds the lock on a scan root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockHolder {
+    pub pid: u32,
+    pub mode: String,
+    pub started_at: String,
+    /// Canonical scan root the lock was taken for
+    #[serde(default)]
+    pub root: Option<PathBuf>,
+}
+
+/// Advisory lock on a scan root, released when dropped
+pub struct RootLock {
+    _file: File,
+}
+
+/// Result of trying to lock a scan root
+pub enum LockStatus {
+    Acquired(RootLock),
+    /// Another instance holds the lock (holder info is best effort)
+    Held(Option<LockHolder>),
+}
+
+fn canonical(root: &Path) -> PathBuf {
+    root.canonicalize().unwrap_or_else(|_| root.to_path_buf())
+}
+
+/// Stable file-name-safe key for a scan root: FNV-1a of its canonical path,
+/// which (unlike `DefaultHasher`) doesn't change between Rust releases
+pub fn root_key(root: &Path) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in canonical(root).as_os_str().as_encoded_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+fn locks_dir() -> Result<PathBuf> {
+    dirs::data_dir()
+        .map(|dir| dir.join("spektr").join("locks"))
+        .context("Failed to determine data directory")
+}
+
+/// Lock file location for a scan root: `<data dir>/spektr/locks/<hash>.lock`
+fn lock_path(root: &Path) -> Result<PathBuf> {
+    Ok(locks_dir()?.join(format!("{}.lock", root_key(root))))
+}
+
+/// Reads the holder of a lock file
+fn read_holder(file: &mut File) -> Option<LockHolder> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut content).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Finds another instance holding a lock on a root that contains `root` or
+/// sits inside it, since cleaning either one touches the other's projects.
+/// `own` is this instance's lock file, which is skipped.
+fn overlapping_holder(root: &Path, own: &Path) -> Result<Option<LockHolder>> {
+    let entries = match std::fs::read_dir(locks_dir()?) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(None),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path == own || path.extension().is_none_or(|ext| ext != "lock") {
+            continue;
+        }
+        let Ok(mut file) = OpenOptions::new().read(true).write(true).open(&path) else {
+            continue;
+        };
+        match file.try_lock() {
+            // Nobody holds it; dropping the file releases it again
+            Ok(()) => continue,
+            Err(TryLockError::WouldBlock) => {}
+            Err(TryLockError::Error(_)) => continue,
+        }
+        let Some(holder) = read_holder(&mut file) else {
+            continue;
+        };
+        let overlaps = holder
+            .root
+            .as_deref()
+            .is_some_and(|other| other.starts_with(root) || root.starts_with(other));
+        if overlaps {
+            return Ok(Some(holder));
+        }
+    }
+    Ok(None)
+}
+
+impl RootLock {
+    /// Tries to take the lock for `root` without blocking. The lock counts as
+    /// held when another instance has `root`, or a root above or below it.
+    /// `mode` describes this instance to anyone who finds the lock held.
+    pub fn try_acquire(root: &Path, mode: &str) -> Result<LockStatus> {
+        let path = lock_path(root)?;
+        let root = canonical(root);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open lock file {}", path.display()))?;
+
+        match file.try_lock() {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => return Ok(LockStatus::Held(read_holder(&mut file))),
+            Err(TryLockError::Error(e)) => {
+                return Err(e).with_context(|| format!("Failed to lock {}", path.display()));
+            }
+        }
+
+        let holder = LockHolder {
+            pid: std::process::id(),
+            mode: mode.to_string(),
+            started_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+            root: Some(root.clone()),
+        };
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(serde_json::to_string(&holder)?.as_bytes())?;
+        file.flush()?;
+
+        // Checked only once our own lock is written, so of two instances
+        // starting on overlapping roots at once, at least one sees the other
+        if let Some(holder) = overlapping_holder(&root, &path)? {
+            return Ok(LockStatus::Held(Some(holder)));
+        }
+
+        Ok(LockStatus::Acquired(RootLock { _file: file }))
+    }
+}
+
+impl LockHolder {
+    pub fn describe(holder: Option<&LockHolder>) -> String {
+        match holder {
+            Some(h) => match &h.root {
+                Some(root) => format!("pid {} ({} mode on {}, since {})", h.pid, h.mode, root.display(), h.started_at),
+                None => format!("pid {} ({} mode, since {})", h.pid, h.mode, h.started_at),
+            },
+            None => "another spektr instance".to_string(),
+        }
+    }
+}
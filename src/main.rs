@@ -1,24 +1,135 @@
+mod archive;
+mod audit;
+mod cleaner;
+mod cloud_sync;
+mod config;
+mod crash;
+mod doctor;
+mod email;
+mod fleet;
+mod format;
+mod icons;
+mod index;
+mod interop;
+mod lock;
+mod mounts;
+mod output;
+mod picker;
+mod progress;
+mod relocate;
+mod replay;
+mod report;
+mod retention;
+mod rules;
 mod scanner;
+mod schedule;
+mod space_watch;
 mod tui;
 
 use anyhow::{Context, Result};
-use clap::{Parser, ValueEnum};
-use scanner::{Scanner, ScanEvent};
-use scanner::strategy::default_strategies;
+use audit::{CleanHistory, SpaceSnapshot};
+use clap::{Parser, Subcommand, ValueEnum};
+use cleaner::{CleanProfile, ProtectedPaths};
+use config::Config;
+use format::{format_count, format_elapsed, SizeUnits};
+use icons::IconSet;
+use index::ArtifactIndex;
+use interop::{InteropFormat, PathsFormat};
+use lock::{LockHolder, LockStatus, RootLock};
+use output::{ColorChoice, Output};
+use progress::{ProgressFormat, ProgressReporter};
+use relocate::{EnvTool, WriteOutcome};
+use report::{CleanReport, ProjectSort, ReportFormat};
+use retention::RetentionPolicy;
+use rules::PathRules;
+use scanner::{CleanableProject, Scanner, ScanEvent, SizeCache, SkipSizing};
+use scanner::strategy::{configured_strategies, StrategySettings};
+use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
-use std::sync::mpsc;
-use std::thread;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
 
 #[derive(Parser)]
 #[command(name = "spektr")]
 #[command(about = "A blazing-fast TUI utility for cleaning development artifacts", long_about = None)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Directory to scan (defaults to current directory)
     #[arg(value_name = "PATH")]
     path: Option<PathBuf>,
 
-    /// Run mode: scan output or interactive TUI
+    /// Read project roots from a file ('-' for stdin) instead of walking PATH.
+    /// Entries are separated by newlines or NUL bytes (find -print0)
+    #[arg(long, value_name = "FILE")]
+    paths_from: Option<PathBuf>,
+
+    /// Format of the --paths-from list, e.g. results saved from kondo or npkill
+    #[arg(long, value_enum, default_value = "plain", requires = "paths_from")]
+    paths_format: PathsFormat,
+
+    /// Also offer tool-wide caches outside PATH (e.g., Xcode DerivedData and
+    /// Archives, the Deno module cache)
+    #[arg(long, visible_alias = "include-global-caches")]
+    global: bool,
+
+    /// Skip a built-in strategy entirely (repeatable, e.g. `--disable android`).
+    /// Adds to `disabled_strategies` in the config
+    #[arg(long, value_name = "STRATEGY")]
+    disable: Vec<String>,
+
+    /// Run an opt-in strategy (repeatable, e.g. `--enable leftover-temp`).
+    /// Adds to `enabled_strategies` in the config
+    #[arg(long, value_name = "STRATEGY")]
+    enable: Vec<String>,
+
+    /// Also offer large `build`, `dist`, `out`, and `tmp` directories that no
+    /// strategy recognizes (high risk, never selected automatically).
+    /// Same as `--enable heuristic`
+    #[arg(long)]
+    heuristic: bool,
+
+    /// With `--global`, leave the newest N versions of each tool in versioned
+    /// caches (Playwright browsers, Gradle wrapper distributions, ...) and
+    /// offer only older ones. Overrides `keep_latest_versions` in the config
+    #[arg(long, value_name = "N")]
+    keep_latest: Option<usize>,
+
+    /// Same as `--keep-latest 1`
+    #[arg(long, hide = true, conflicts_with = "keep_latest")]
+    keep_latest_binaries: bool,
+
+    /// Only offer projects whose build output hasn't changed in this many
+    /// days, leaving alone what's still being built. Overrides
+    /// `older_than_days` in the config
+    #[arg(long, value_name = "DAYS")]
+    older_than: Option<u64>,
+
+    /// Don't descend into hidden directories (except `.cache` and the config's
+    /// `walk_hidden`) while looking for projects
+    #[arg(long)]
+    skip_hidden: bool,
+
+    /// Only include projects on the same mounted volume as this path
+    /// (e.g., the nearly full disk)
+    #[arg(long, value_name = "PATH")]
+    device: Option<PathBuf>,
+
+    /// Recompute all sizes instead of reusing cached sizes and the background index
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Reuse sizes from earlier scans for targets whose top level is
+    /// unchanged, instead of walking them. Faster, but misses changes deeper
+    /// inside, so these sizes are marked as estimates
+    #[arg(long, conflicts_with = "no_cache")]
+    cached_sizes: bool,
+
+    /// Run mode: scan output, unattended clean, or interactive TUI
     #[arg(short, long, value_enum, default_value = "tui")]
     mode: Mode,
 
@@ -26,6 +137,112 @@ struct Cli {
     #[arg(long)]
     dry_run: bool,
 
+    /// Only print (scan mode) or clean (clean mode) the N largest projects
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
+
+    /// Order of the project list in scan and clean modes
+    #[arg(long, value_enum)]
+    sort: Option<ProjectSort>,
+
+    /// Directory depth for the reclaimable space breakdown in scan mode
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    breakdown_depth: usize,
+
+    /// Size display units (overrides the config file)
+    #[arg(long, value_enum)]
+    units: Option<SizeUnits>,
+
+    /// Icons next to projects; `ascii` keeps columns aligned on terminals
+    /// that misrender emoji widths (overrides the config file)
+    #[arg(long, value_enum, value_name = "SET")]
+    icons: Option<IconSet>,
+
+    /// TUI colors; `auto` asks the terminal for its background color
+    /// (overrides the config file)
+    #[arg(long, value_enum)]
+    theme: Option<tui::Theme>,
+
+    /// Projects the TUI keeps in memory; beyond this the smallest move to a
+    /// file on disk, and a filter brings them back (overrides the config file)
+    #[arg(long, value_name = "N")]
+    max_projects: Option<usize>,
+
+    /// Directory for archived targets (overrides the config file)
+    #[arg(long, value_name = "DIR")]
+    archive_dir: Option<PathBuf>,
+
+    /// Colors and emoji in scan/clean output; plain output is tab-separated
+    #[arg(long, value_enum, default_value = "auto")]
+    color: ColorChoice,
+
+    /// Print only NUL-terminated project paths (e.g., for `fzf --read0 --print0`)
+    #[arg(long)]
+    print0: bool,
+
+    /// List the projects and repositories that have nothing to clean (scan mode)
+    #[arg(long)]
+    list_clean: bool,
+
+    /// Print only per-strategy totals, reusing sizes from earlier scans
+    /// as estimates, for a quick check of whether a cleanup is worth it (scan mode)
+    #[arg(long, conflicts_with = "export")]
+    summary_only: bool,
+
+    /// Print scan results in another tool's format instead (scan mode)
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    export: Option<InteropFormat>,
+
+    /// Progress output on stderr for scripting (scan and clean modes)
+    #[arg(long, value_enum, default_value = "none")]
+    progress: ProgressFormat,
+
+    /// Which projects clean mode may delete
+    #[arg(long, value_enum, default_value = "full")]
+    profile: CleanProfile,
+
+    /// Clean with the toolchain's own command (`cargo clean`, `gradle clean`,
+    /// `flutter clean`, ...) where there is one, deleting only what it leaves.
+    /// Same as `native_clean` in the config
+    #[arg(long)]
+    native_clean: bool,
+
+    /// Propose cleaning only what the config's `[[retention]]` rules select:
+    /// scan mode lists the plan, clean mode deletes it, the TUI preselects it
+    #[arg(long, conflicts_with_all = ["export", "summary_only"])]
+    policy: bool,
+
+    /// Print the deletion plan and wait for its confirmation token from this
+    /// source before deleting (clean mode)
+    #[arg(long, value_enum, value_name = "SOURCE")]
+    confirm_from: Option<ConfirmSource>,
+
+    /// Write a clean report to this file (clean mode)
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// Write the scan results with this machine's host and user name as JSON,
+    /// for combining with other machines' with `spektr report --merge` (scan mode)
+    #[arg(long, value_name = "FILE")]
+    json_report: Option<PathBuf>,
+
+    /// Email a clean report to this address using the `[smtp]` config (clean mode)
+    #[arg(long, value_name = "ADDRESS")]
+    report_email: Option<String>,
+
+    /// Save the scan's event stream to this file, for --replay-events (TUI mode)
+    #[arg(long, value_name = "FILE")]
+    record_events: Option<PathBuf>,
+
+    /// Show a recorded event stream in a read-only TUI instead of scanning,
+    /// e.g. to reproduce a display problem without the filesystem it came from
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["record_events", "paths_from", "policy"])]
+    replay_events: Option<PathBuf>,
+
+    /// Replay this many times as fast as the events were recorded
+    #[arg(long, value_name = "FACTOR", default_value_t = 1.0, requires = "replay_events")]
+    replay_speed: f64,
+
     /// Show version information
     #[arg(short = 'v', long)]
     version: bool,
@@ -35,12 +252,263 @@ struct Cli {
 enum Mode {
     /// Simple scan mode (prints to stdout)
     Scan,
+    /// Unattended mode: deletes every project found (honors --dry-run)
+    Clean,
+    /// Pick projects to delete from a numbered list
+    Pick,
     /// Interactive TUI mode
     Tui,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    /// Install a recurring unattended clean with the platform scheduler
+    InstallSchedule {
+        /// Directory to clean (defaults to current directory)
+        #[arg(value_name = "PATH")]
+        path: Option<PathBuf>,
+
+        /// Run every day
+        #[arg(long, conflicts_with = "weekly")]
+        daily: bool,
+
+        /// Run every week (default)
+        #[arg(long)]
+        weekly: bool,
+
+        /// Which projects the scheduled clean may delete
+        #[arg(long, value_enum, default_value = "safe")]
+        profile: CleanProfile,
+    },
+    /// Remove the recurring clean installed by install-schedule
+    UninstallSchedule,
+    /// Keep an artifact index up to date in the background, so the TUI
+    /// opens with complete results
+    Index {
+        /// Directory to index (defaults to current directory)
+        #[arg(value_name = "PATH")]
+        path: Option<PathBuf>,
+    },
+    /// Watch free space and clean (or notify) when a volume runs low
+    WatchSpace {
+        /// Directories to clean; each one's volume is monitored
+        /// (defaults to `[space_watch] paths` in the config)
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+
+        /// Trigger below this percentage of free space [default: 10]
+        #[arg(long, value_name = "PERCENT")]
+        threshold: Option<f64>,
+
+        /// Send a desktop notification instead of cleaning
+        #[arg(long)]
+        notify_only: bool,
+
+        /// Which projects the triggered clean may delete [default: safe]
+        #[arg(long, value_enum)]
+        profile: Option<CleanProfile>,
+
+        /// Seconds between checks [default: 300]
+        #[arg(long, value_name = "SECONDS")]
+        interval: Option<u64>,
+    },
+    /// Suggest moving the build output of repeatedly cleaned projects to a
+    /// scratch disk with direnv or mise, instead of cleaning them again
+    Relocate {
+        /// Scratch directory that receives the build output
+        /// (defaults to `scratch_dir` in the config)
+        #[arg(long, value_name = "DIR")]
+        scratch_dir: Option<PathBuf>,
+
+        /// Only suggest for projects cleaned at least this many times
+        #[arg(long, value_name = "N", default_value_t = RELOCATE_MIN_CLEANS)]
+        min_cleans: usize,
+
+        /// Add the variables to each project's config for this tool
+        #[arg(long, value_enum, value_name = "TOOL")]
+        write: Option<EnvTool>,
+    },
+    /// Print platform, terminal, config, and cache details for bug reports
+    Doctor,
+    /// Reports built from the clean and size history, or from other
+    /// machines' scan reports
+    #[command(args_conflicts_with_subcommands = true)]
+    Report {
+        #[command(subcommand)]
+        report: Option<ReportCommand>,
+
+        /// Combine the `--json-report` files in this directory into per-host,
+        /// per-user, and per-ecosystem totals
+        #[arg(long, value_name = "DIR")]
+        merge: Option<PathBuf>,
+
+        /// Output format of the merged report
+        #[arg(long, value_enum, default_value = "table")]
+        format: ReportFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommand {
+    /// Rank cleaned projects by how fast their artifacts grew back
+    Regrowth {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: ReportFormat,
+    },
+    /// Compare estimated and actually reclaimed space of past cleans, per volume
+    Reclaimed {
+        /// Output format
+        #[arg(long, value_enum, default_value = "table")]
+        format: ReportFormat,
+    },
+}
+
+/// Where clean mode reads its confirmation token from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ConfirmSource {
+    Stdin,
+}
+
+/// Which projects scan and clean modes list, and in what order
+#[derive(Clone, Copy)]
+struct Listing {
+    top: Option<usize>,
+    sort: Option<ProjectSort>,
+}
+
+impl Listing {
+    /// Projects can be printed as they're found, in discovery order
+    fn is_streaming(&self) -> bool {
+        self.top.is_none() && self.sort.is_none()
+    }
+}
+
+/// What to scan: a directory tree, or an explicit list of project roots
+struct ScanSource {
+    /// Scan root, used for display, grouping, and locking
+    root: PathBuf,
+    /// Externally supplied project roots (skips discovery)
+    paths: Option<Vec<PathBuf>>,
+    /// Reuse cached sizes of unchanged targets
+    use_cache: bool,
+    /// Only include projects on the volume mounted here
+    mount: Option<PathBuf>,
+    /// Include strategies' global locations outside the scanned tree
+    include_global: bool,
+    /// Disabled strategies and target overrides
+    strategies: StrategySettings,
+    /// Per-path risk and auto-selection rules
+    path_rules: PathRules,
+    /// Hidden directories still walked, when hidden directories are skipped
+    walked_hidden: Option<Vec<String>>,
+    /// Reuse cached sizes of targets whose top level is unchanged
+    reuse_sizes: bool,
+    /// Take cached sizes as estimates without checking the targets
+    estimate_sizes: bool,
+    /// Newest versions of each tool in versioned caches that aren't offered
+    kept_versions: usize,
+    /// Only projects whose targets haven't changed for this long
+    older_than: Option<Duration>,
+}
+
+impl ScanSource {
+    /// Runs the scanner on a background thread; `skip_sizing` lets the caller
+    /// abandon sizing of individual projects
+    fn spawn(&self, tx: Sender<ScanEvent>, skip_sizing: SkipSizing) -> JoinHandle<Result<Vec<CleanableProject>>> {
+        let root = self.root.clone();
+        let paths = self.paths.clone();
+        let use_cache = self.use_cache;
+        let mount = self.mount.clone();
+        let include_global = self.include_global;
+        let strategies = self.strategies.clone();
+        let path_rules = self.path_rules.clone();
+        let walked_hidden = self.walked_hidden.clone();
+        let reuse_sizes = self.reuse_sizes;
+        let estimate_sizes = self.estimate_sizes;
+        let kept_versions = self.kept_versions;
+        let older_than = self.older_than;
+
+        thread::spawn(move || {
+            let mut scanner = Scanner::new(configured_strategies(&strategies));
+            if use_cache {
+                scanner = scanner.with_size_cache(SizeCache::load());
+            }
+            if let Some(mount) = mount {
+                scanner = scanner.with_mount_filter(mount);
+            }
+            if include_global {
+                scanner = scanner.with_global_locations();
+            }
+            if let Some(walked) = walked_hidden {
+                scanner = scanner.with_skip_hidden(walked);
+            }
+            if reuse_sizes {
+                scanner = scanner.with_cached_sizes();
+            }
+            if estimate_sizes {
+                scanner = scanner.with_estimated_sizes();
+            }
+            if let Some(age) = older_than {
+                scanner = scanner.with_older_than(age);
+            }
+            scanner = scanner
+                .with_path_rules(path_rules)
+                .with_skip_sizing(skip_sizing)
+                .with_kept_versions(kept_versions);
+            let result = match paths {
+                Some(paths) => scanner.scan_paths(&paths, tx),
+                None => scanner.scan(&root, tx),
+            };
+            match &result {
+                // Estimates may be stale, so they'd skew the regrowth report
+                Ok(_) if estimate_sizes || reuse_sizes => {}
+                // Best effort: feeds the regrowth report
+                Ok(projects) => {
+                    let _ = audit::record_sizes(projects);
+                }
+                Err(_) => {}
+            }
+            result
+        })
+    }
+
+    /// Runs the scanner on a background thread and reports an error or panic
+    /// as a final `ScanEvent::Failed`, so consumers never wait forever
+    fn spawn_watched(&self, skip_sizing: SkipSizing) -> Receiver<ScanEvent> {
+        let (tx, rx) = mpsc::channel();
+        let handle = self.spawn(tx.clone(), skip_sizing);
+
+        thread::spawn(move || {
+            let failure = match handle.join() {
+                Ok(Ok(_)) => return,
+                Ok(Err(e)) => format!("{:#}", e),
+                Err(_) => "Scanner thread panicked".to_string(),
+            };
+            let _ = tx.send(ScanEvent::Failed(failure));
+        });
+
+        rx
+    }
+}
+
+/// Reads a path list from a file or stdin ("-")
+fn read_path_list(source: &Path, format: PathsFormat) -> Result<Vec<PathBuf>> {
+    let content = if source == Path::new("-") {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf).context("Failed to read paths from stdin")?;
+        buf
+    } else {
+        std::fs::read_to_string(source)
+            .with_context(|| format!("Failed to read paths from {}", source.display()))?
+    };
+
+    interop::parse_paths(&content, format)
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    crash::install();
 
     // Show version and exit
     if cli.version {
@@ -48,59 +516,1133 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    match cli.command {
+        Some(Command::InstallSchedule { path, daily, weekly: _, profile }) => {
+            let root = match path {
+                Some(path) => path,
+                None => env::current_dir().context("Failed to get current directory")?,
+            };
+            let root = root.canonicalize().context("Failed to resolve path to clean")?;
+            let frequency = if daily { schedule::Frequency::Daily } else { schedule::Frequency::Weekly };
+            return schedule::install(&root, frequency, profile);
+        }
+        Some(Command::UninstallSchedule) => return schedule::uninstall(),
+        Some(Command::Index { path }) => {
+            let root = match path {
+                Some(path) => path,
+                None => env::current_dir().context("Failed to get current directory")?,
+            };
+            let config = Config::load()?;
+            let settings = config.strategy_settings();
+            settings.validate()?;
+            icons::register_custom(&settings.custom);
+            let rules = PathRules::new(config.path_rules)?;
+            return index::run_indexer(&root, &settings, &rules);
+        }
+        Some(Command::WatchSpace { paths, threshold, notify_only, profile, interval }) => {
+            let mut config = Config::load()?;
+            let defaults = config.space_watch.take().unwrap_or_default();
+            let budgets = defaults
+                .budgets
+                .iter()
+                .map(|budget| {
+                    Ok(space_watch::Budget {
+                        path: retention::expand_home(&budget.path),
+                        max_total: format::parse_size(&budget.max_total)
+                            .with_context(|| format!("Invalid budget for {}", budget.path))?,
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let strategies = config.strategy_settings();
+            strategies.validate()?;
+            icons::register_custom(&strategies.custom);
+            let watch = space_watch::SpaceWatch {
+                paths: if paths.is_empty() { defaults.paths } else { paths },
+                threshold_percent: threshold.or(defaults.threshold_percent).unwrap_or(10.0),
+                notify_only: notify_only || defaults.notify_only,
+                profile: profile.or(defaults.profile).unwrap_or(CleanProfile::Safe),
+                interval: std::time::Duration::from_secs(interval.or(defaults.interval_secs).unwrap_or(300)),
+                units: cli.units.or(config.units).unwrap_or_default(),
+                budgets,
+                low_water_percent: defaults.budget_low_water_percent.unwrap_or(90.0),
+                strategies,
+                path_rules: PathRules::new(config.path_rules.clone())?,
+                native_clean: cli.native_clean || config.native_clean,
+            };
+            return space_watch::run(&watch);
+        }
+        Some(Command::Relocate { scratch_dir, min_cleans, write }) => {
+            let config = Config::load()?;
+            let scratch_dir = scratch_dir
+                .or(config.scratch_dir)
+                .context("No scratch directory: pass --scratch-dir or set scratch_dir in the config file")?;
+            let scratch_dir = std::path::absolute(&scratch_dir)
+                .with_context(|| format!("Failed to resolve {}", scratch_dir.display()))?;
+            let units = cli.units.or(config.units).unwrap_or_default();
+            let out = Output::new(cli.color, units, cli.print0);
+            return run_relocate(&scratch_dir, min_cleans, write, &out);
+        }
+        Some(Command::Doctor) => {
+            doctor::run();
+            return Ok(());
+        }
+        Some(Command::Report { report: Some(ReportCommand::Regrowth { format }), .. }) => {
+            let units = cli.units.or(Config::load()?.units).unwrap_or_default();
+            let out = Output::new(cli.color, units, cli.print0);
+            return print_regrowth_report(format, &out);
+        }
+        Some(Command::Report { report: Some(ReportCommand::Reclaimed { format }), .. }) => {
+            let units = cli.units.or(Config::load()?.units).unwrap_or_default();
+            let out = Output::new(cli.color, units, cli.print0);
+            return print_reclaimed_report(format, &out);
+        }
+        Some(Command::Report { report: None, merge, format }) => {
+            let dir = merge.context("Pass a report (e.g. `spektr report regrowth`) or --merge DIR")?;
+            let units = cli.units.or(Config::load()?.units).unwrap_or_default();
+            let out = Output::new(cli.color, units, cli.print0);
+            return print_fleet_report(&dir, format, &out);
+        }
+        None => {}
+    }
+
+    let config = Config::load()?;
+    crash::set_include_paths(config.crash_dump_paths);
+    if let Some(mode) = cli.mode.to_possible_value() {
+        crash::set_mode(mode.get_name());
+    }
+    let units = cli.units.or(config.units).unwrap_or_default();
+    let archive_dir = cli
+        .archive_dir
+        .or(config.archive_dir.clone())
+        .or_else(archive::default_archive_dir)
+        .context("Failed to determine archive directory")?;
+
     let scan_path = match cli.path {
         Some(path) => path,
         None => env::current_dir().context("Failed to get current directory")?,
     };
+    if cli.confirm_from == Some(ConfirmSource::Stdin) && cli.paths_from.as_deref() == Some(Path::new("-")) {
+        anyhow::bail!("--confirm-from stdin can't be combined with --paths-from -: both read stdin");
+    }
 
+    let mut strategies = config.strategy_settings();
+    strategies.disabled.extend(cli.disable);
+    strategies.enabled.extend(cli.enable);
+    if cli.heuristic {
+        strategies.enabled.push("heuristic".to_string());
+    }
+    strategies.validate()?;
+    icons::register_custom(&strategies.custom);
+
+    let retention = if cli.policy {
+        let policy = RetentionPolicy::new(config.retention.clone(), &strategies)?;
+        if policy.is_empty() {
+            anyhow::bail!("--policy needs [[retention]] rules in the config file");
+        }
+        Some(policy)
+    } else {
+        None
+    };
 
+    let icons = cli.icons.or(config.icons).unwrap_or_default();
+    let theme = cli.theme.or(config.theme).unwrap_or_default();
+    let max_projects = cli
+        .max_projects
+        .or(config.max_projects)
+        .unwrap_or(tui::DEFAULT_MAX_PROJECTS);
+    let native_clean = cli.native_clean || config.native_clean;
+    if let Some(file) = &cli.replay_events {
+        if !matches!(cli.mode, Mode::Tui) || !tui::is_supported() {
+            anyhow::bail!("--replay-events needs the TUI (--mode tui in a terminal that supports it)");
+        }
+        if cli.replay_speed.is_nan() || cli.replay_speed <= 0.0 {
+            anyhow::bail!("--replay-speed must be greater than 0");
+        }
+        let recording = replay::Recording::load(file)?;
+        let options = tui::TuiOptions {
+            units,
+            icons,
+            archive_dir,
+            // The recorded paths belong to another scan, maybe on another machine
+            read_only: true,
+            indexed: Vec::new(),
+            path_rules: PathRules::new(config.path_rules.clone())?,
+            retention,
+            theme,
+            max_projects,
+        };
+        tui::run_tui(|_| recording.play(cli.replay_speed), recording.scan_root.clone(), options)?;
+        return Ok(());
+    }
+    if cli.record_events.is_some() && !matches!(cli.mode, Mode::Tui) {
+        anyhow::bail!("--record-events records the TUI's scan (--mode tui)");
+    }
+    let out = Output::new(cli.color, units, cli.print0)
+        .with_history(CleanHistory::load())
+        .with_icons(icons);
+    let listing = Listing { top: cli.top, sort: cli.sort };
+    let source = ScanSource {
+        root: scan_path,
+        paths: cli
+            .paths_from
+            .as_deref()
+            .map(|source| read_path_list(source, cli.paths_format))
+            .transpose()?,
+        use_cache: !cli.no_cache,
+        mount: cli.device.as_deref().map(mounts::mount_point),
+        include_global: cli.global,
+        strategies,
+        path_rules: PathRules::new(config.path_rules.clone())?,
+        walked_hidden: (cli.skip_hidden || config.skip_hidden).then(|| config.walk_hidden.clone()),
+        reuse_sizes: !cli.no_cache && (cli.cached_sizes || config.cached_sizes),
+        estimate_sizes: cli.summary_only && matches!(cli.mode, Mode::Scan),
+        kept_versions: cli
+            .keep_latest
+            .or(cli.keep_latest_binaries.then_some(1))
+            .or(config.keep_latest_versions)
+            .or(config.keep_latest_binaries.then_some(1))
+            .unwrap_or(0),
+        older_than: cli
+            .older_than
+            .or(config.older_than_days)
+            .map(|days| Duration::from_secs(days * 24 * 60 * 60)),
+    };
 
     match cli.mode {
-        Mode::Scan => run_scan_mode(&scan_path),
-        Mode::Tui => run_tui_mode(&scan_path, cli.dry_run),
+        Mode::Scan => match cli.export {
+            Some(format) => run_export(&source, listing, format),
+            None => run_scan_mode(
+                &source,
+                &out,
+                ScanModeOptions {
+                    listing,
+                    breakdown_depth: cli.breakdown_depth,
+                    progress: cli.progress,
+                    list_clean: cli.list_clean,
+                    summary_only: cli.summary_only,
+                    retention,
+                    json_report: cli.json_report,
+                },
+            ),
+        },
+        Mode::Clean => run_clean_mode(
+            &source,
+            &out,
+            &CleanOptions {
+                listing,
+                progress: cli.progress,
+                dry_run: cli.dry_run,
+                profile: cli.profile,
+                native_clean,
+                confirm_from: cli.confirm_from,
+                report_path: cli.report.as_deref(),
+                report_email: cli.report_email.as_deref(),
+                retention: retention.as_ref(),
+            },
+            &config,
+        ),
+        Mode::Pick => run_pick_mode(&source, &out, native_clean),
+        // Dumb terminals and CI logs get the prompt-based flow instead
+        Mode::Tui if !tui::is_supported() => {
+            eprintln!(
+                "ℹ️  This terminal can't run the TUI; using {} instead",
+                if cli.dry_run { "scan mode" } else { "pick mode" }
+            );
+            if cli.dry_run {
+                run_scan_mode(
+                    &source,
+                    &out,
+                    ScanModeOptions {
+                        listing,
+                        breakdown_depth: cli.breakdown_depth,
+                        progress: cli.progress,
+                        list_clean: cli.list_clean,
+                        summary_only: false,
+                        retention,
+                        json_report: cli.json_report,
+                    },
+                )
+            } else {
+                run_pick_mode(&source, &out, native_clean)
+            }
+        }
+        Mode::Tui => run_tui_mode(
+            &source,
+            cli.record_events.as_deref(),
+            native_clean,
+            tui::TuiOptions {
+                units,
+                icons,
+                archive_dir,
+                read_only: cli.dry_run,
+                indexed: Vec::new(),
+                path_rules: source.path_rules.clone(),
+                retention,
+                theme,
+                max_projects,
+            },
+        ),
     }
 }
 
-fn run_scan_mode(scan_path: &std::path::Path) -> Result<()> {
-    println!("🔍 SPEKTR - Scanning: {}", scan_path.display());
-    println!();
+/// Scan mode settings from the command line
+struct ScanModeOptions {
+    listing: Listing,
+    breakdown_depth: usize,
+    progress: ProgressFormat,
+    /// List projects with nothing to clean after the summary
+    list_clean: bool,
+    /// Skip the project list and the detailed breakdowns
+    summary_only: bool,
+    /// List what the retention rules would clean (`--policy`)
+    retention: Option<RetentionPolicy>,
+    /// Write the results as a fleet scan report (`--json-report`)
+    json_report: Option<PathBuf>,
+}
 
-    let (tx, rx) = mpsc::channel();
-    let tx_clone = tx.clone();
-    let scan_path_clone = scan_path.to_path_buf();
+fn run_scan_mode(source: &ScanSource, out: &Output, options: ScanModeOptions) -> Result<()> {
+    let ScanModeOptions { listing, breakdown_depth, progress, list_clean, summary_only, retention, json_report } =
+        options;
+    let scan_path = source.root.as_path();
+    out.heading("🔍", format!("SPEKTR - Scanning: {}", scan_path.display()));
+    out.blank();
+
+    let mut progress = ProgressReporter::new(progress);
+    let projects = scan_and_print(source, out, (!summary_only).then_some(listing), &mut progress)?;
+    let total_size: u64 = projects.iter().map(|p| p.total_size).sum();
+    if let Some(path) = &json_report {
+        fleet::ScanReport::new(scan_path, &projects).write(path)?;
+    }
+
+    if !summary_only {
+        out.blank();
+    }
+    out.heading("✅", "Scan Complete!");
+    out.line(format!("   Projects Found: {}", projects.len()));
+    let already_clean = progress.already_clean();
+    if !already_clean.is_empty() {
+        out.line(format!(
+            "   Already Clean: {} of {} projects",
+            already_clean.len(),
+            projects.len() + already_clean.len()
+        ));
+    }
+    out.line(format!("   Total Reclaimable: {}", out.size(total_size)));
+    if let Some(path) = &json_report {
+        out.line(format!("   Report written to {}", path.display()));
+    }
+    let estimated = projects.iter().filter(|p| p.size_estimated).count();
+    if estimated > 0 {
+        out.line(format!(
+            "   Estimated: {} of {} projects sized by an earlier scan (drop --summary-only and --cached-sizes for exact sizes)",
+            estimated,
+            projects.len()
+        ));
+    }
+
+    if let Some(stats) = progress.discovery().filter(|s| s.hidden_directories > 0) {
+        out.line(format!(
+            "   Hidden Directories: {} of {} checked, {}{}",
+            format_count(stats.hidden_directories),
+            format_count(stats.directories),
+            format_elapsed(stats.hidden_time),
+            if source.walked_hidden.is_none() { " (--skip-hidden skips them)" } else { "" }
+        ));
+    }
 
-    let handle = thread::spawn(move || {
-        let scanner = Scanner::new(default_strategies());
-        scanner.scan(&scan_path_clone, tx_clone)
+    if let Some(stats) = progress.discovery().filter(|s| !s.skipped_mounts.is_empty()) {
+        out.blank();
+        out.heading("⏭️", "Skipped Locations:");
+        for (path, reason) in &stats.skipped_mounts {
+            out.line(format!("   {} ({})", path.display(), reason));
+        }
+    }
+
+    print_strategy_table(&projects, out);
+    if summary_only {
+        return Ok(());
+    }
+    print_largest_projects(&projects, out);
+    print_slowest_sizing(&projects, out);
+
+    let by_mount = report::summarize_by_mount(&projects);
+    if total_size > 0 && !by_mount.is_empty() {
+        out.blank();
+        out.heading("💽", "Reclaimable by Mount Point:");
+        for summary in by_mount {
+            out.line(format!(
+                "   {:>10}  {} ({} projects)",
+                out.size(summary.total_size),
+                summary.mount_point.display(),
+                summary.project_count
+            ));
+        }
+    }
+
+    let breakdown = report::summarize_by_directory(&projects, scan_path, breakdown_depth);
+    if total_size > 0 && !breakdown.is_empty() {
+        out.blank();
+        out.heading("📂", "Reclaimable by Directory:");
+        for summary in breakdown {
+            let label = if summary.path.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                summary.path.display().to_string()
+            };
+            out.line(format!(
+                "   {:>5.1}%  {:>10}  {} ({} projects)",
+                summary.total_size as f64 * 100.0 / total_size as f64,
+                out.size(summary.total_size),
+                label,
+                summary.project_count
+            ));
+        }
+    }
+
+    if list_clean && !already_clean.is_empty() {
+        out.blank();
+        out.heading("✨", "Already Clean:");
+        for root in already_clean {
+            out.line(format!("   {}", root.display()));
+        }
+    }
+
+    if let Some(policy) = retention {
+        print_retention_plan(&policy, &projects, out);
+    }
+
+    Ok(())
+}
+
+/// Prints the projects the retention rules would clean, with their reasons
+fn print_retention_plan(policy: &RetentionPolicy, projects: &[CleanableProject], out: &Output) {
+    let plan = policy.plan(projects);
+    out.blank();
+    if plan.is_empty() {
+        out.heading("📋", "Retention Plan: nothing to clean, every rule holds");
+        return;
+    }
+
+    let total: u64 = plan.iter().map(|p| p.project.total_size).sum();
+    out.heading(
+        "📋",
+        format!("Retention Plan: {} projects, {}", plan.len(), out.size(total)),
+    );
+    for planned in &plan {
+        out.line(format!(
+            "   {:>10}  {}",
+            out.size(planned.project.total_size),
+            planned.project.root_path.display()
+        ));
+        out.line(format!("               {}", planned.reason));
+    }
+    out.line("   Run with --mode clean --policy to delete these.");
+}
+
+/// Scans silently and prints the results in another tool's format
+fn run_export(source: &ScanSource, listing: Listing, format: InteropFormat) -> Result<()> {
+    let projects = scan_and_collect(source)?;
+    let projects = report::select_projects(projects, listing.top, listing.sort);
+    print!("{}", interop::export(&projects, format)?);
+    Ok(())
+}
+
+/// Number of projects listed in the scan summary's "largest" table
+const TOP_PROJECTS: usize = 10;
+
+/// Prints count, total size, and largest project for each strategy
+fn print_strategy_table(projects: &[CleanableProject], out: &Output) {
+    let summaries = report::summarize_by_strategy(projects);
+    if summaries.is_empty() {
+        return;
+    }
+
+    let rows: Vec<[String; 4]> = summaries
+        .iter()
+        .map(|s| {
+            let largest = s
+                .largest
+                .as_ref()
+                .map(|(path, size)| format!("{} ({})", path.display(), out.size(*size)))
+                .unwrap_or_default();
+            [
+                s.name.clone(),
+                s.project_count.to_string(),
+                out.size(s.total_size),
+                largest,
+            ]
+        })
+        .collect();
+    let header = ["Strategy", "Projects", "Total", "Largest"].map(String::from);
+
+    let name_width = rows.iter().chain([&header]).map(|r| r[0].len()).max().unwrap_or(0);
+    let count_width = rows.iter().chain([&header]).map(|r| r[1].len()).max().unwrap_or(0);
+    let size_width = rows.iter().chain([&header]).map(|r| r[2].len()).max().unwrap_or(0);
+
+    out.blank();
+    out.heading("📊", "By Strategy:");
+    for row in std::iter::once(&header).chain(&rows) {
+        out.line(format!(
+            "   {:<name_width$}  {:>count_width$}  {:>size_width$}  {}",
+            row[0], row[1], row[2], row[3]
+        ));
+    }
+}
+
+/// Prints the largest projects with aligned sizes
+fn print_largest_projects(projects: &[CleanableProject], out: &Output) {
+    let largest = report::largest_projects(projects, TOP_PROJECTS);
+    if largest.is_empty() {
+        return;
+    }
+
+    let sizes: Vec<String> = largest.iter().map(|p| out.size(p.total_size)).collect();
+    let size_width = sizes.iter().map(String::len).max().unwrap_or(0);
+    let name_width = largest.iter().map(|p| p.strategy_name.len()).max().unwrap_or(0);
+
+    out.blank();
+    out.heading("🏆", format!("Top {} Largest:", largest.len()));
+    for (rank, (project, size)) in largest.iter().zip(&sizes).enumerate() {
+        let members = match project.members.len() {
+            0 => String::new(),
+            n => format!(" (workspace, {} members)", n),
+        };
+        out.line(format!(
+            "   {:>2}. {:>size_width$}  {:<name_width$}  {}{}",
+            rank + 1,
+            size,
+            project.strategy_name,
+            project.root_path.display(),
+            members
+        ));
+    }
+}
+
+/// Cleans after which clean mode suggests `spektr relocate`
+const RELOCATE_MIN_CLEANS: usize = 3;
+
+/// Prints (and optionally writes) relocation settings for repeatedly cleaned projects
+fn run_relocate(scratch_dir: &Path, min_cleans: usize, write: Option<EnvTool>, out: &Output) -> Result<()> {
+    let suggestions = relocate::suggestions(min_cleans, scratch_dir);
+    if suggestions.is_empty() {
+        out.line(format!(
+            "No projects with relocatable build output were cleaned {} or more times yet.",
+            min_cleans
+        ));
+        return Ok(());
+    }
+
+    out.heading("🚚", format!("Relocation Suggestions (scratch directory: {})", scratch_dir.display()));
+    let mut updated = false;
+    for suggestion in &suggestions {
+        let clean = &suggestion.clean;
+        out.blank();
+        out.line(format!(
+            "   {}  {}, cleaned {} times, {} freed in total",
+            clean.project.display(),
+            clean.strategy,
+            clean.cleans,
+            out.size(clean.freed)
+        ));
+
+        let Some(tool) = write else {
+            for tool in [EnvTool::Direnv, EnvTool::Mise] {
+                let file = relocate::config_file(tool, &clean.project);
+                let name = file.file_name().unwrap_or_default().to_string_lossy();
+                for line in relocate::snippet(tool, &suggestion.vars).lines() {
+                    out.line(format!("      {:<10}  {}", format!("{}:", name), line));
+                }
+            }
+            continue;
+        };
+        match relocate::write(tool, suggestion)? {
+            WriteOutcome::Written(path) => {
+                updated = true;
+                out.line(format!("      Updated {}", path.display()));
+            }
+            WriteOutcome::AlreadySet(path) => out.line(format!("      Already set in {}", path.display())),
+            WriteOutcome::Skipped(path, reason) => out.line(format!(
+                "      Skipped {}: {}; add these by hand:\n{}",
+                path.display(),
+                reason,
+                relocate::snippet(tool, &suggestion.vars)
+                    .lines()
+                    .map(|line| format!("         {}", line))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            )),
+        }
+    }
+
+    match write {
+        Some(_) if !updated => {}
+        Some(EnvTool::Direnv) => {
+            out.blank();
+            out.line("Run `direnv allow` in each updated project to load the new settings.");
+        }
+        Some(EnvTool::Mise) => {
+            out.blank();
+            out.line("Run `mise trust` in each updated project to load the new settings.");
+        }
+        None => {
+            out.blank();
+            out.line("Run with --write direnv or --write mise to add these settings to each project.");
+        }
+    }
+    Ok(())
+}
+
+/// Prints the totals of a directory of fleet scan reports
+fn print_fleet_report(dir: &Path, format: ReportFormat, out: &Output) -> Result<()> {
+    let summary = fleet::merge(dir)?;
+
+    if format == ReportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    for (path, reason) in &summary.skipped {
+        eprintln!("⚠️  Skipped {}: {}", path.display(), reason);
+    }
+    if summary.reports == 0 {
+        out.line(format!("No scan reports in {} (write them with --json-report)", dir.display()));
+        return Ok(());
+    }
+
+    out.heading(
+        "🌐",
+        format!(
+            "Fleet Report: {} in {} projects from {} reports",
+            out.size(summary.total_size),
+            summary.projects,
+            summary.reports
+        ),
+    );
+    for (title, groups) in [("Host", &summary.hosts), ("User", &summary.users), ("Ecosystem", &summary.ecosystems)] {
+        let rows: Vec<[String; 4]> = groups
+            .iter()
+            .map(|g| [g.name.clone(), g.reports.to_string(), g.projects.to_string(), out.size(g.total_size)])
+            .collect();
+        let header = [title, "Reports", "Projects", "Total"].map(String::from);
+        let widths: Vec<usize> = (0..4)
+            .map(|i| rows.iter().chain([&header]).map(|r| r[i].chars().count()).max().unwrap_or(0))
+            .collect();
+
+        out.blank();
+        for row in std::iter::once(&header).chain(&rows) {
+            out.line(format!(
+                "   {:<w0$}  {:>w1$}  {:>w2$}  {:>w3$}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Prints cleaned projects ranked by how fast their artifacts regrew
+fn print_regrowth_report(format: ReportFormat, out: &Output) -> Result<()> {
+    let report = audit::regrowth_report();
+
+    if format == ReportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.is_empty() {
+        out.line("No regrowth recorded yet: clean some projects, then scan again later.");
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+    let rows: Vec<[String; 5]> = report
+        .iter()
+        .map(|r| {
+            let cleaned = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(r.last_cleaned);
+            [
+                format!("{}/day", out.size(r.bytes_per_day as u64)),
+                out.size(r.current_size),
+                format!("{} ago", format::format_age(now.duration_since(cleaned).unwrap_or_default())),
+                r.cleans.to_string(),
+                format!("{}  {}", r.strategy, r.project.display()),
+            ]
+        })
+        .collect();
+    let header = ["Regrowth", "Size", "Last Cleaned", "Cleans", "Project"].map(String::from);
+
+    let widths: Vec<usize> = (0..4)
+        .map(|i| rows.iter().chain([&header]).map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    out.heading("📈", "Biggest Regrowers:");
+    for row in std::iter::once(&header).chain(&rows) {
+        out.line(format!(
+            "   {:>w0$}  {:>w1$}  {:<w2$}  {:>w3$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        ));
+    }
+
+    Ok(())
+}
+
+/// Prints projects whose sizing was unusually slow, with their file counts
+fn print_slowest_sizing(projects: &[CleanableProject], out: &Output) {
+    let slow = report::slowest_to_size(projects, TOP_PROJECTS);
+    if slow.is_empty() {
+        return;
+    }
+
+    out.blank();
+    out.heading("🐢", "Slowest to Size:");
+    for project in slow {
+        out.line(format!(
+            "   {:>8}  {:>6} files  {}",
+            format::format_elapsed(project.sizing_time),
+            format::format_count(project.file_count),
+            project.root_path.display()
+        ));
+    }
+}
+
+/// Settings for an unattended clean session
+struct CleanOptions<'a> {
+    listing: Listing,
+    progress: ProgressFormat,
+    dry_run: bool,
+    profile: CleanProfile,
+    /// Prefer the toolchains' clean commands (`--native-clean`)
+    native_clean: bool,
+    confirm_from: Option<ConfirmSource>,
+    report_path: Option<&'a Path>,
+    report_email: Option<&'a str>,
+    /// Delete only what the retention rules select (`--policy`)
+    retention: Option<&'a RetentionPolicy>,
+}
+
+/// Prints the deletion plan and waits for its confirmation token on stdin,
+/// so wrappers (e.g., chatops bots) can add their own approval step
+fn confirm_plan_from_stdin(plan: &[CleanableProject], out: &Output) -> Result<()> {
+    let total: u64 = plan.iter().map(|p| p.total_size).sum();
+    let token = format!("delete-{}", plan_fingerprint(plan));
+
+    out.blank();
+    out.heading("📋", format!("Deletion plan: {} projects, {}", plan.len(), out.size(total)));
+    for project in plan {
+        out.line(format!(
+            "   {}  {}  {}",
+            out.size(project.total_size),
+            project.strategy_name,
+            project.root_path.display()
+        ));
+    }
+    out.line(format!("Enter `{}` on stdin to proceed:", token));
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer).context("Failed to read confirmation from stdin")?;
+    if answer.trim() != token {
+        anyhow::bail!("Confirmation token did not match; nothing was deleted");
+    }
+    Ok(())
+}
+
+/// Short hash of the planned targets, so a token only confirms this exact plan
+fn plan_fingerprint(plan: &[CleanableProject]) -> String {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    for target in plan.iter().flat_map(|p| &p.targets) {
+        target.path.hash(&mut hasher);
+        target.size.hash(&mut hasher);
+    }
+    format!("{:08x}", hasher.finish() as u32)
+}
+
+fn run_clean_mode(
+    source: &ScanSource,
+    out: &Output,
+    options: &CleanOptions,
+    config: &Config,
+) -> Result<()> {
+    let CleanOptions {
+        listing,
+        progress,
+        dry_run,
+        profile,
+        native_clean,
+        confirm_from,
+        report_path,
+        report_email,
+        retention,
+    } = *options;
+    let units = out.units;
+    let scan_path = source.root.as_path();
+    let started_at = SystemTime::now();
+
+    // Keep the lock for the whole session so no one else deletes the same targets
+    let _lock = if dry_run {
+        None
+    } else {
+        match RootLock::try_acquire(scan_path, "clean")? {
+            LockStatus::Acquired(lock) => Some(lock),
+            LockStatus::Held(holder) => anyhow::bail!(
+                "{} is locked by {}",
+                scan_path.display(),
+                LockHolder::describe(holder.as_ref())
+            ),
+        }
+    };
+
+    out.heading("🧹", format!("SPEKTR - Cleaning: {}", scan_path.display()));
+    out.blank();
+
+    let mut progress = ProgressReporter::new(progress);
+    let projects = scan_and_print(source, out, Some(listing), &mut progress)?;
+
+    let protected = ProtectedPaths::new(scan_path);
+    let mut cleaned = Vec::new();
+    let mut failures = Vec::new();
+    let mut native_cleans = Vec::new();
+
+    out.blank();
+    // Reasons of the projects the retention rules clean, keyed by (root, strategy)
+    let retained: Option<HashMap<(PathBuf, String), String>> = retention.map(|policy| {
+        policy
+            .plan(&projects)
+            .into_iter()
+            .map(|planned| {
+                let key = (planned.project.root_path.clone(), planned.project.strategy_name.clone());
+                (key, planned.reason)
+            })
+            .collect()
     });
+    let mut plan = Vec::new();
+    for project in projects {
+        if source.path_rules.auto_select(&project.root_path) == Some(false) {
+            out.line(format!("   Skipping (path rule): {}", project.root_path.display()));
+            continue;
+        }
+        if project.manual_only {
+            out.line(format!(
+                "   Skipping ({} guess, pick it in the TUI): {}",
+                project.strategy_name,
+                project.root_path.display()
+            ));
+            continue;
+        }
+        if let Some(retained) = &retained {
+            match retained.get(&(project.root_path.clone(), project.strategy_name.clone())) {
+                Some(reason) => out.line(format!("   Planned ({}): {}", reason, project.root_path.display())),
+                None => {
+                    out.line(format!("   Keeping (retention policy): {}", project.root_path.display()));
+                    continue;
+                }
+            }
+        }
+
+        // Gate each target on the profile's risk limit
+        match project.restricted_to(profile.max_risk()) {
+            Some(project) => plan.push(project),
+            None => out.line(format!("   Skipping ({} profile): {}", profile.label(), project.root_path.display())),
+        }
+    }
+
+    if dry_run {
+        for project in plan {
+            match protected.check(&project) {
+                Ok(()) => {
+                    out.line(format!("   Would delete: {}", project.root_path.display()));
+                    cleaned.push(project);
+                }
+                Err(e) => {
+                    eprintln!("   ❌ {:#}", e);
+                    failures.push((project.root_path.clone(), format!("{:#}", e)));
+                }
+            }
+        }
+        plan = Vec::new();
+    } else if confirm_from == Some(ConfirmSource::Stdin) && !plan.is_empty() {
+        confirm_plan_from_stdin(&plan, out)?;
+    }
+
+    let plan_count = plan.len();
+    let space_before = (plan_count > 0).then(|| SpaceSnapshot::take(&plan));
+    for (index, project) in plan.into_iter().enumerate() {
+        out.line(format!("   Deleting: {}", project.root_path.display()));
+        progress.cleaning(&project, index + 1, plan_count);
+        match cleaner::delete_project(&project, &protected, native_clean) {
+            Ok(deletion) => {
+                for target in &deletion.drifted {
+                    eprintln!("   ⚠️  Skipped {}: {}", target.path.display(), target.reason);
+                }
+                if let Some(native) = deletion.native {
+                    if native.success {
+                        out.line(format!("     Ran `{}`", native.command));
+                    } else {
+                        print_failed_clean_command(&native);
+                    }
+                    native_cleans.push((project.root_path.clone(), native));
+                }
+                cleaned.push(cleaner::without_drifted(&project, &deletion.drifted));
+            }
+            Err(e) => {
+                let error = format!("{:#}", e);
+                eprintln!("   ❌ {}", error);
+                progress.clean_failed(&project.root_path, &error);
+                failures.push((project.root_path.clone(), error));
+            }
+        }
+    }
+    if let Some(before) = space_before {
+        // Best effort: the history is informational
+        let _ = audit::record_space("delete", &before, &cleaned);
+    }
+
+    let report = CleanReport {
+        scan_root: scan_path.to_path_buf(),
+        started_at,
+        finished_at: SystemTime::now(),
+        dry_run,
+        cleaned,
+        failures,
+        native_cleans,
+    };
+
+    progress.clean_complete(report.cleaned.len(), report.failures.len(), report.freed_size());
+
+    out.blank();
+    out.heading("✅", "Cleanup complete!");
+    out.line(format!("   Projects Cleaned: {}", report.cleaned.len()));
+    out.line(format!("   Space Freed: {}", out.size(report.freed_size())));
+    if !report.failures.is_empty() {
+        out.line(format!("   Failures: {}", report.failures.len()));
+    }
+    if !dry_run {
+        let repeated = audit::repeated_cleans(RELOCATE_MIN_CLEANS)
+            .into_iter()
+            .filter(|clean| {
+                report
+                    .cleaned
+                    .iter()
+                    .any(|p| p.root_path == clean.project && p.strategy_name == clean.strategy)
+            })
+            .count();
+        if repeated > 0 {
+            out.blank();
+            out.heading(
+                "💡",
+                format!(
+                    "{} of these projects keep needing cleans; `spektr relocate` can move their build output to a scratch disk",
+                    repeated
+                ),
+            );
+        }
+    }
+
+    if let Some(path) = report_path {
+        std::fs::write(path, report.render_text(units))
+            .with_context(|| format!("Failed to write report to {}", path.display()))?;
+        out.line(format!("   Report written to {}", path.display()));
+    }
+
+    if let Some(address) = report_email {
+        let smtp = config
+            .smtp
+            .as_ref()
+            .context("--report-email requires an [smtp] section in the config file")?;
+        email::send_email(smtp, address, &report.subject(), report.render_text(units))?;
+        out.line(format!("   Report emailed to {}", address));
+    }
+
+    Ok(())
+}
 
-    let mut total_size = 0u64;
-    let mut project_count = 0;
+fn run_pick_mode(source: &ScanSource, out: &Output, native_clean: bool) -> Result<()> {
+    let scan_path = source.root.as_path();
+    let _lock = match RootLock::try_acquire(scan_path, "pick")? {
+        LockStatus::Acquired(lock) => lock,
+        LockStatus::Held(holder) => anyhow::bail!(
+            "{} is locked by {}",
+            scan_path.display(),
+            LockHolder::describe(holder.as_ref())
+        ),
+    };
+
+    eprintln!("🔍 Scanning {}...", scan_path.display());
+    let mut projects = scan_and_collect(source)?;
+    if projects.is_empty() {
+        println!("No cleanable projects found.");
+        return Ok(());
+    }
+    projects.sort_by_key(|p| std::cmp::Reverse(p.total_size));
+
+    let width = projects.len().to_string().len();
+    for (idx, project) in projects.iter().enumerate() {
+        println!(
+            "{:>width$}) {:>10}  {:<8}  {}",
+            idx + 1,
+            out.size(project.total_size),
+            project.strategy_name,
+            project.root_path.display()
+        );
+    }
+    println!();
+
+    print!("Select projects to delete (e.g. 1 3 5-7, 'all'; empty to cancel): ");
+    io::stdout().flush()?;
+    let mut input = String::new();
+    io::stdin().lock().read_line(&mut input)?;
+    if input.trim().is_empty() {
+        println!("👋 Exited without making changes.");
+        return Ok(());
+    }
+
+    let chosen: Vec<&CleanableProject> = picker::parse_selection(&input, projects.len())?
+        .into_iter()
+        .map(|idx| &projects[idx])
+        .collect();
+    let total: u64 = chosen.iter().map(|p| p.total_size).sum();
+
+    let question = format!("Delete {} projects totaling {}? [y/N] ", chosen.len(), out.size(total));
+    if !prompt_yes_no(&question)? {
+        println!("👋 Exited without making changes.");
+        return Ok(());
+    }
+
+    let protected = ProtectedPaths::new(scan_path);
+    let chosen: Vec<CleanableProject> = chosen.into_iter().cloned().collect();
+    let space_before = SpaceSnapshot::take(&chosen);
+    let mut cleaned = Vec::new();
+    let mut failures = Vec::new();
+    for project in &chosen {
+        println!("   Deleting: {}", project.root_path.display());
+        match cleaner::delete_project(project, &protected, native_clean) {
+            Ok(deletion) => {
+                print_deletion(&deletion);
+                cleaned.push(cleaner::without_drifted(project, &deletion.drifted));
+            }
+            Err(e) => failures.push(print_failure(project, e)),
+        }
+    }
+    let _ = audit::record_space("delete", &space_before, &cleaned);
+    println!("✅ Cleanup complete!");
+    print_failures(&failures);
 
+    Ok(())
+}
+
+/// Prints why a project of a batch failed, returning it for the summary;
+/// the rest of the batch goes ahead
+fn print_failure(project: &CleanableProject, error: anyhow::Error) -> (PathBuf, String) {
+    let error = format!("{:#}", error);
+    eprintln!("   ❌ {}", error);
+    (project.root_path.clone(), error)
+}
+
+/// Projects of a batch left alone because they failed, after the others
+fn print_failures(failures: &[(PathBuf, String)]) {
+    if failures.is_empty() {
+        return;
+    }
+    eprintln!("⚠️  {} projects failed and were left alone:", failures.len());
+    for (path, error) in failures {
+        eprintln!("   {}: {}", path.display(), error);
+    }
+}
+
+/// Targets left in place and the clean command's outcome, after "Deleting: ..."
+fn print_deletion(deletion: &cleaner::Deletion) {
+    for target in &deletion.drifted {
+        eprintln!("   ⚠️  Skipped {}: {}", target.path.display(), target.reason);
+    }
+    match &deletion.native {
+        Some(native) if native.success => println!("     Ran `{}`", native.command),
+        Some(native) => print_failed_clean_command(native),
+        None => {}
+    }
+}
+
+/// A clean command that failed, with its output
+fn print_failed_clean_command(native: &cleaner::NativeClean) {
+    eprintln!("   ⚠️  `{}` failed; deleted the targets directly", native.command);
+    for line in native.output.lines() {
+        eprintln!("       {}", line);
+    }
+}
+
+/// Runs a scan silently and returns all projects
+fn scan_and_collect(source: &ScanSource) -> Result<Vec<CleanableProject>> {
+    let (tx, rx) = mpsc::channel();
+    let handle = source.spawn(tx, SkipSizing::default());
+
+    let mut projects = Vec::new();
     for event in rx {
+        crash::record_event(&event);
         match event {
-            ScanEvent::ProjectFound(project) => {
-                project_count += 1;
-                total_size += project.total_size;
+            ScanEvent::ProjectFound(project) => projects.push(project),
+            ScanEvent::Phase(_)
+            | ScanEvent::Scanning(_)
+            | ScanEvent::Discovered(_)
+            | ScanEvent::AlreadyClean(_)
+            | ScanEvent::Analyzing(_)
+            | ScanEvent::ProjectPending(_) => {}
+            ScanEvent::Complete | ScanEvent::Failed(_) => break,
+        }
+    }
 
-                let emoji = match project.strategy_name.as_str() {
-                    "Rust" => "🦀",
-                    "Node.js" => "📦",
-                    "Flutter" => "💙",
-                    "Android" => "🤖",
-                    _ => "📁",
-                };
+    handle.join()
+        .map_err(|_| anyhow::anyhow!("Scanner thread panicked"))?
+        .context("Scanning failed")?;
 
-                println!(
-                    "{} {} | {} | {}",
-                    emoji,
-                    project.strategy_name,
-                    project.root_path.display(),
-                    format_size(project.total_size)
-                );
+    Ok(projects)
+}
+
+/// Scans and prints projects: as they're found, or once the scan completes
+/// when `listing` selects or reorders them. Nothing is printed without a `listing`.
+fn scan_and_print(
+    source: &ScanSource,
+    out: &Output,
+    listing: Option<Listing>,
+    progress: &mut ProgressReporter,
+) -> Result<Vec<CleanableProject>> {
+    let (tx, rx) = mpsc::channel();
+    let handle = source.spawn(tx, SkipSizing::default());
+
+    let mut projects = Vec::new();
+
+    for event in rx {
+        crash::record_event(&event);
+        progress.scan_event(&event);
+
+        match event {
+            ScanEvent::ProjectFound(project) => {
+                if listing.is_some_and(|l| l.is_streaming()) {
+                    out.project(&project);
+                }
+                projects.push(project);
             }
-            ScanEvent::Scanning(_) => {} // Ignore progress in simple scan mode
-            ScanEvent::Complete => break,
+            ScanEvent::Phase(_)
+            | ScanEvent::Scanning(_)
+            | ScanEvent::Discovered(_)
+            | ScanEvent::AlreadyClean(_)
+            | ScanEvent::Analyzing(_)
+            | ScanEvent::ProjectPending(_) => {} // Ignore progress in simple scan mode
+            ScanEvent::Complete | ScanEvent::Failed(_) => break,
         }
     }
 
@@ -109,42 +1651,127 @@ fn run_scan_mode(scan_path: &std::path::Path) -> Result<()> {
         .map_err(|_| anyhow::anyhow!("Scanner thread panicked"))?
         .context("Scanning failed")?;
 
-    println!();
-    println!("✅ Scan Complete!");
-    println!("   Projects Found: {}", project_count);
-    println!("   Total Reclaimable: {}", format_size(total_size));
+    let Some(listing) = listing.filter(|l| !l.is_streaming()) else {
+        return Ok(projects);
+    };
 
-    Ok(())
+    let found = projects.len();
+    let projects = report::select_projects(projects, listing.top, listing.sort);
+    for project in &projects {
+        out.project(project);
+    }
+    if projects.len() < found {
+        out.line(format!("   (showing the {} largest of {} projects)", projects.len(), found));
+    }
+
+    Ok(projects)
 }
 
-fn run_tui_mode(scan_path: &std::path::Path, _dry_run: bool) -> Result<()> {
-    let (tx, rx) = mpsc::channel();
-    let scan_path_clone = scan_path.to_path_buf();
+/// Runs the TUI on `source`, saving the scan's events to `record_events` if given
+fn run_tui_mode(
+    source: &ScanSource,
+    record_events: Option<&Path>,
+    native_clean: bool,
+    mut options: tui::TuiOptions,
+) -> Result<()> {
+    let scan_path = source.root.as_path();
+    let _lock = if options.read_only {
+        None
+    } else {
+        match RootLock::try_acquire(scan_path, "tui")? {
+            LockStatus::Acquired(lock) => Some(lock),
+            LockStatus::Held(holder) => {
+                println!(
+                    "🔒 {} is locked by {}",
+                    scan_path.display(),
+                    LockHolder::describe(holder.as_ref())
+                );
+                if !prompt_yes_no("   Continue in read-only mode? [y/N] ")? {
+                    return Ok(());
+                }
+                options.read_only = true;
+                None
+            }
+        }
+    };
 
-    // Spawn scanner in background thread
-    thread::spawn(move || {
-        let scanner = Scanner::new(default_strategies());
-        let _ = scanner.scan(&scan_path_clone, tx);
-    });
+    // Show indexed results right away; the scan then verifies them
+    if source.paths.is_none() && source.mount.is_none() && !source.include_global && source.use_cache {
+        if let Some(index) = ArtifactIndex::load(scan_path) {
+            let active: Vec<String> = configured_strategies(&source.strategies)
+                .iter()
+                .map(|strategy| strategy.name().to_string())
+                .collect();
+            options.indexed = index
+                .projects
+                .into_iter()
+                .filter(|p| active.contains(&p.strategy_name))
+                .collect();
+        }
+    }
 
-    // Run TUI (blocks until user quits)
-    let final_state = tui::run_tui(rx, scan_path.to_path_buf())?;
+    // Run TUI (blocks until user quits); it (re)starts the background scan
+    // Fail before the TUI takes over the terminal if the recording can't be written
+    if let Some(path) = record_events {
+        replay::EventRecorder::create(path, scan_path)?;
+    }
+    let start_scan = |skip_sizing| {
+        let rx = source.spawn_watched(skip_sizing);
+        // A retried scan replaces the recording; if that fails, it just isn't recorded
+        match record_events.map(|path| replay::EventRecorder::create(path, scan_path)) {
+            Some(Ok(recorder)) => recorder.tee(rx),
+            _ => rx,
+        }
+    };
+    let final_state = tui::run_tui(start_scan, scan_path.to_path_buf(), options)?;
 
     // Handle deletion if user confirmed
     if final_state.deletion_confirmed {
+        let protected = ProtectedPaths::new(scan_path);
         let selected = final_state.get_selected_projects();
-        println!("\n🗑️  Deleting {} projects...", selected.len());
+        let space_before = SpaceSnapshot::take(&selected);
+        let mut cleaned = Vec::new();
+        let mut failures = Vec::new();
+
+        let action = match final_state.pending_action {
+            tui::CleanAction::Delete => {
+                println!("\n🗑️  Deleting {} projects...", selected.len());
 
-        for project in selected {
-            println!("   Deleting: {}", project.root_path.display());
-            for target in &project.targets {
-                if target.exists() {
-                    std::fs::remove_dir_all(target)?;
+                for project in &selected {
+                    println!("   Deleting: {}", project.root_path.display());
+                    match cleaner::delete_project(project, &protected, native_clean) {
+                        Ok(deletion) => {
+                            print_deletion(&deletion);
+                            cleaned.push(cleaner::without_drifted(project, &deletion.drifted));
+                        }
+                        Err(e) => failures.push(print_failure(project, e)),
+                    }
                 }
+                "delete"
             }
-        }
+            tui::CleanAction::Archive => {
+                println!("\n📦 Archiving {} projects...", selected.len());
+
+                for project in &selected {
+                    println!("   Archiving: {}", project.root_path.display());
+                    match archive::archive_project(project, &final_state.archive_dir, &protected) {
+                        Ok((archive_path, drifted)) => {
+                            for target in &drifted {
+                                eprintln!("   ⚠️  Skipped {}: {}", target.path.display(), target.reason);
+                            }
+                            println!("     → {}", archive_path.display());
+                            cleaned.push(cleaner::without_drifted(project, &drifted));
+                        }
+                        Err(e) => failures.push(print_failure(project, e)),
+                    }
+                }
+                "archive"
+            }
+        };
+        let _ = audit::record_space(action, &space_before, &cleaned);
 
         println!("✅ Cleanup complete!");
+        print_failures(&failures);
     } else {
         println!("\n👋 Exited without making changes.");
     }
@@ -152,18 +1779,66 @@ fn run_tui_mode(scan_path: &std::path::Path, _dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
+/// Prints estimated versus measured reclaimed space of past cleans
+fn print_reclaimed_report(format: ReportFormat, out: &Output) -> Result<()> {
+    let report = audit::reclaimed_report();
 
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+    if format == ReportFormat::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
     }
+
+    if report.is_empty() {
+        out.line("No cleans with free-space measurements recorded yet.");
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+    let rows: Vec<[String; 5]> = report
+        .iter()
+        .map(|r| {
+            let cleaned = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(r.timestamp);
+            let reclaimed = out.size(r.reclaimed.unsigned_abs());
+            [
+                format!("{} ago", format::format_age(now.duration_since(cleaned).unwrap_or_default())),
+                r.action.clone(),
+                out.size(r.estimated),
+                if r.reclaimed < 0 { format!("-{}", reclaimed) } else { reclaimed },
+                r.mount.display().to_string(),
+            ]
+        })
+        .collect();
+    let header = ["When", "Action", "Estimated", "Reclaimed", "Volume"].map(String::from);
+
+    let widths: Vec<usize> = (0..4)
+        .map(|i| rows.iter().chain([&header]).map(|r| r[i].chars().count()).max().unwrap_or(0))
+        .collect();
+
+    out.heading("💽", "Reclaimed Space:");
+    for row in std::iter::once(&header).chain(&rows) {
+        out.line(format!(
+            "   {:<w0$}  {:<w1$}  {:>w2$}  {:>w3$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        ));
+    }
+
+    Ok(())
+}
+
+/// Asks a yes/no question on stdin, defaulting to "no"
+fn prompt_yes_no(question: &str) -> Result<bool> {
+    print!("{}", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
 }
@@ -0,0 +1,97 @@
+use std::path::{Path, PathBuf};
+
+/// Mount point (Unix) or volume root (Windows) that `path` lives on
+pub fn mount_point(path: &Path) -> PathBuf {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    platform_mount_point(&path)
+}
+
+/// Walks up until the parent directory is on a different device
+#[cfg(unix)]
+fn platform_mount_point(path: &Path) -> PathBuf {
+    use std::os::unix::fs::MetadataExt;
+
+    let Ok(device) = path.metadata().map(|m| m.dev()) else {
+        return path.to_path_buf();
+    };
+
+    let mut mount = path;
+    while let Some(parent) = mount.parent() {
+        match parent.metadata() {
+            Ok(metadata) if metadata.dev() == device => mount = parent,
+            _ => break,
+        }
+    }
+    mount.to_path_buf()
+}
+
+/// Drive or UNC share prefix plus the root directory
+#[cfg(not(unix))]
+fn platform_mount_point(path: &Path) -> PathBuf {
+    use std::path::Component;
+
+    path.components()
+        .take_while(|c| matches!(c, Component::Prefix(_) | Component::RootDir))
+        .collect()
+}
+
+/// Whether `path` is on the volume mounted at `mount`
+pub fn is_on_mount(path: &Path, mount: &Path) -> bool {
+    mount_point(path) == mount
+}
+
+
+/// Pseudo-filesystems that never hold projects and can be slow or unsafe to walk
+#[cfg(target_os = "linux")]
+const PSEUDO_FILESYSTEMS: [&str; 20] = [
+    "proc", "sysfs", "devtmpfs", "devpts", "cgroup", "cgroup2", "securityfs", "debugfs", "tracefs", "pstore",
+    "bpf", "configfs", "fusectl", "mqueue", "hugetlbfs", "autofs", "binfmt_misc", "efivarfs", "nsfs", "selinuxfs",
+];
+
+/// Mount points of special filesystems that discovery skips, with what they
+/// are: pseudo-filesystems (procfs, sysfs, ...), GVFS, and read-only FUSE mounts
+#[cfg(target_os = "linux")]
+pub fn special_mounts() -> Vec<(PathBuf, String)> {
+    let Ok(table) = std::fs::read_to_string("/proc/self/mounts") else {
+        return Vec::new();
+    };
+    table
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let (_device, mount, fs_type, options) = (fields.next()?, fields.next()?, fields.next()?, fields.next()?);
+            let read_only = options.split(',').any(|option| option == "ro");
+            let reason = if PSEUDO_FILESYSTEMS.contains(&fs_type) {
+                format!("{} pseudo-filesystem", fs_type)
+            } else if fs_type == "fuse.gvfsd-fuse" {
+                "GVFS network mount".to_string()
+            } else if fs_type.starts_with("fuse") && read_only {
+                format!("read-only FUSE mount ({})", fs_type)
+            } else {
+                return None;
+            };
+            Some((PathBuf::from(unescape_mount_path(mount)), reason))
+        })
+        .collect()
+}
+
+/// Mount points of special filesystems that discovery skips, with what they are
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn special_mounts() -> Vec<(PathBuf, String)> {
+    vec![(PathBuf::from("/dev"), "devfs pseudo-filesystem".to_string())]
+}
+
+/// Mount points of special filesystems that discovery skips, with what they are
+#[cfg(not(unix))]
+pub fn special_mounts() -> Vec<(PathBuf, String)> {
+    Vec::new()
+}
+
+/// The mount table escapes spaces, tabs, newlines, and backslashes as octal
+#[cfg(target_os = "linux")]
+fn unescape_mount_path(path: &str) -> String {
+    path.replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
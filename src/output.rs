@@ -0,0 +1,126 @@
+use crate::audit::CleanHistory;
+use crate::format::{format_size, SizeUnits};
+use crate::icons::{strategy_icon, IconSet};
+use crate::scanner::CleanableProject;
+use clap::ValueEnum;
+use crossterm::style::Stylize;
+use std::fmt::Display;
+use std::io::IsTerminal;
+
+/// When to use colors and emoji in headless output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ColorChoice {
+    /// Rich output when stdout is a terminal (and NO_COLOR is unset)
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Writer for scan/clean mode output.
+///
+/// Rich output uses emoji and colors. Plain output (non-TTY or `--color never`)
+/// prints one tab-separated row per project on stdout (`strategy`, `bytes`, `path`)
+/// and sends headings and summaries to stderr, so stdout stays clean data.
+/// With `print0`, rows are just NUL-terminated project paths (for `fzf --read0`).
+pub struct Output {
+    rich: bool,
+    print0: bool,
+    pub units: SizeUnits,
+    /// Previous cleans, noted next to projects in rich output
+    history: CleanHistory,
+    /// Icons next to projects in rich output
+    icons: IconSet,
+}
+
+impl Output {
+    pub fn new(choice: ColorChoice, units: SizeUnits, print0: bool) -> Self {
+        let rich = !print0
+            && match choice {
+                ColorChoice::Always => true,
+                ColorChoice::Never => false,
+                ColorChoice::Auto => {
+                    std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+                }
+            };
+        Self {
+            rich,
+            print0,
+            units,
+            history: CleanHistory::default(),
+            icons: IconSet::default(),
+        }
+    }
+
+    /// Notes previous cleans of each project (rich output only)
+    pub fn with_history(mut self, history: CleanHistory) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Icons shown next to projects (rich output only)
+    pub fn with_icons(mut self, icons: IconSet) -> Self {
+        self.icons = icons;
+        self
+    }
+
+    pub fn size(&self, bytes: u64) -> String {
+        format_size(bytes, self.units)
+    }
+
+    /// Heading line, prefixed with `icon` in rich mode
+    pub fn heading(&self, icon: &str, text: impl Display) {
+        if self.rich {
+            println!("{} {}", icon, text.to_string().bold());
+        } else {
+            eprintln!("{}", text);
+        }
+    }
+
+    /// Informational line (summaries, status)
+    pub fn line(&self, text: impl Display) {
+        if self.rich {
+            println!("{}", text);
+        } else {
+            eprintln!("{}", text);
+        }
+    }
+
+    pub fn blank(&self) {
+        self.line("");
+    }
+
+    /// Data row for a discovered project
+    pub fn project(&self, project: &CleanableProject) {
+        if self.print0 {
+            print!("{}\0", project.root_path.display());
+        } else if self.rich {
+            let note = self
+                .history
+                .describe(project, self.units)
+                .map(|note| format!(" ({})", note).dark_grey().to_string())
+                .unwrap_or_default();
+            let rebuild = project
+                .rebuild
+                .as_ref()
+                .map(|estimate| format!(" | rebuild: {}", estimate.label()).dark_grey().to_string())
+                .unwrap_or_default();
+            println!(
+                "{} {} | {} | {}{}{}",
+                strategy_icon(&project.strategy_name, self.icons),
+                project.strategy_name,
+                project.root_path.display(),
+                self.size(project.total_size).yellow(),
+                note,
+                rebuild
+            );
+        } else {
+            println!(
+                "{}\t{}\t{}",
+                project.strategy_name,
+                project.total_size,
+                project.root_path.display()
+            );
+        }
+    }
+}
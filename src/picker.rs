@@ -0,0 +1,36 @@
+use anyhow::{bail, Context, Result};
+
+/// Parses a picker selection such as `1 3 5-7` or `all` into zero-based indices.
+/// Numbers are one-based and must be within `1..=count`.
+pub fn parse_selection(input: &str, count: usize) -> Result<Vec<usize>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+
+    let mut selected = Vec::new();
+    for token in input.split([' ', ',']).filter(|t| !t.is_empty()) {
+        let (start, end) = match token.split_once('-') {
+            Some((start, end)) => (parse_number(start)?, parse_number(end)?),
+            None => {
+                let n = parse_number(token)?;
+                (n, n)
+            }
+        };
+
+        if start == 0 || end > count || start > end {
+            bail!("Selection '{}' is out of range (1-{})", token, count);
+        }
+        selected.extend(start - 1..end);
+    }
+
+    selected.sort_unstable();
+    selected.dedup();
+    Ok(selected)
+}
+
+fn parse_number(s: &str) -> Result<usize> {
+    s.trim()
+        .parse()
+        .with_context(|| format!("'{}' is not a number", s))
+}
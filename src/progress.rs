@@ -0,0 +1,161 @@
+use crate::scanner::{CleanableProject, DiscoveryStats, ScanEvent, ScanPhase};
+use clap::ValueEnum;
+use serde_json::json;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Minimum interval between discovery progress lines
+const DISCOVERY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Machine-readable progress output on stderr
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ProgressFormat {
+    /// No progress output
+    #[default]
+    None,
+    /// One JSON object per line (ndjson)
+    Json,
+}
+
+/// Emits progress events for scan and clean modes
+pub struct ProgressReporter {
+    format: ProgressFormat,
+    last_discovery: Option<Instant>,
+    directories: usize,
+    projects: usize,
+    total_size: u64,
+    discovery: Option<DiscoveryStats>,
+    already_clean: Vec<PathBuf>,
+}
+
+impl ProgressReporter {
+    pub fn new(format: ProgressFormat) -> Self {
+        Self {
+            format,
+            last_discovery: None,
+            directories: 0,
+            projects: 0,
+            total_size: 0,
+            discovery: None,
+            already_clean: Vec::new(),
+        }
+    }
+
+    /// Discovery statistics of the last full tree walk
+    pub fn discovery(&self) -> Option<&DiscoveryStats> {
+        self.discovery.as_ref()
+    }
+
+    /// Projects and repositories the scan found nothing to clean in
+    pub fn already_clean(&self) -> &[PathBuf] {
+        &self.already_clean
+    }
+
+    fn emit(&self, event: serde_json::Value) {
+        if self.format == ProgressFormat::Json {
+            let _ = writeln!(std::io::stderr().lock(), "{}", event);
+        }
+    }
+
+    /// Reports a scanner event (discovery lines are throttled)
+    pub fn scan_event(&mut self, event: &ScanEvent) {
+        match event {
+            ScanEvent::Scanning(path) => {
+                self.directories += 1;
+                let due = self
+                    .last_discovery
+                    .is_none_or(|last| last.elapsed() >= DISCOVERY_INTERVAL);
+                if due {
+                    self.last_discovery = Some(Instant::now());
+                    self.emit(json!({
+                        "phase": "discovery",
+                        "path": path,
+                        "directories": self.directories,
+                    }));
+                }
+            }
+            ScanEvent::Phase(phase) => self.emit(match phase {
+                ScanPhase::Discovering => json!({ "phase": "stage", "stage": "discovering" }),
+                ScanPhase::Deduplicating => json!({ "phase": "stage", "stage": "deduplicating" }),
+                ScanPhase::Sizing { done, total } => json!({
+                    "phase": "stage",
+                    "stage": "sizing",
+                    "done": done,
+                    "total": total,
+                }),
+            }),
+            ScanEvent::Analyzing(path) => self.emit(json!({
+                "phase": "sizing",
+                "path": path,
+            })),
+            ScanEvent::Discovered(stats) => {
+                self.discovery = Some(stats.clone());
+                self.emit(json!({
+                    "phase": "discovery_complete",
+                    "directories": stats.directories,
+                    "hidden_directories": stats.hidden_directories,
+                    "hidden_ms": stats.hidden_time.as_millis() as u64,
+                    "skipped_mounts": stats.skipped_mounts.len(),
+                }));
+            }
+            ScanEvent::AlreadyClean(roots) => {
+                self.already_clean = roots.clone();
+                self.emit(json!({ "phase": "already_clean", "count": roots.len() }));
+            }
+            ScanEvent::ProjectPending(_) => {}
+            ScanEvent::ProjectFound(project) => {
+                self.projects += 1;
+                self.total_size += project.total_size;
+                self.emit(json!({
+                    "phase": "project",
+                    "path": project.root_path,
+                    "strategy": project.strategy_name,
+                    "size": project.total_size,
+                    "projects": self.projects,
+                    "total_size": self.total_size,
+                }));
+            }
+            ScanEvent::Complete => self.emit(json!({
+                "phase": "scan_complete",
+                "directories": self.directories,
+                "projects": self.projects,
+                "total_size": self.total_size,
+            })),
+            ScanEvent::Failed(error) => self.emit(json!({
+                "phase": "scan_failed",
+                "error": error,
+            })),
+        }
+    }
+
+    /// Reports the start of cleaning one project (`index` is 1-based)
+    pub fn cleaning(&self, project: &CleanableProject, index: usize, total: usize) {
+        self.emit(json!({
+            "phase": "cleaning",
+            "path": project.root_path,
+            "size": project.total_size,
+            "index": index,
+            "total": total,
+        }));
+    }
+
+    /// Reports a project that failed to clean
+    pub fn clean_failed(&self, path: &Path, error: &str) {
+        self.emit(json!({
+            "phase": "clean_failed",
+            "path": path,
+            "error": error,
+        }));
+    }
+
+    /// Reports the end of a clean session
+    pub fn clean_complete(&self, cleaned: usize, failed: usize, freed: u64) {
+        self.emit(json!({
+            "phase": "clean_complete",
+            "cleaned": cleaned,
+            "failed": failed,
+            "freed": freed,
+        }));
+    }
+}
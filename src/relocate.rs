@@ -0,0 +1,164 @@
+use crate::audit::{self, RepeatedClean};
+use crate::format::shell_quote;
+use crate::scanner::strategy::default_strategies;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+
+/// Per-project environment tools that `spektr relocate --write` can configure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum EnvTool {
+    /// Append `export` lines to the project's `.envrc`
+    Direnv,
+    /// Add an `[env]` table to the project's `mise.toml`
+    Mise,
+}
+
+/// Environment variables that would move a repeatedly cleaned project's
+/// build output to the scratch directory
+#[derive(Debug, Clone)]
+pub struct Suggestion {
+    pub clean: RepeatedClean,
+    /// Variable name and the directory it should point to
+    pub vars: Vec<(String, PathBuf)>,
+}
+
+/// What writing a suggestion did
+pub enum WriteOutcome {
+    Written(PathBuf),
+    /// Every variable was already set in the file
+    AlreadySet(PathBuf),
+    /// The file exists in a form that can't be extended safely
+    Skipped(PathBuf, &'static str),
+}
+
+/// Relocation variables of the built-in strategies, keyed by strategy name
+fn relocation_vars() -> HashMap<String, Vec<(String, String)>> {
+    default_strategies()
+        .iter()
+        .map(|strategy| {
+            let vars = strategy
+                .relocation_vars()
+                .into_iter()
+                .map(|(var, subdir)| (var.to_string(), subdir.to_string()))
+                .collect();
+            (strategy.name().to_string(), vars)
+        })
+        .collect()
+}
+
+/// Suggestions for the projects cleaned at least `min_cleans` times whose
+/// strategy supports relocation and that still exist
+pub fn suggestions(min_cleans: usize, scratch_dir: &Path) -> Vec<Suggestion> {
+    let known = relocation_vars();
+    audit::repeated_cleans(min_cleans)
+        .into_iter()
+        .filter(|clean| clean.project.is_dir())
+        .filter_map(|clean| {
+            let vars = known.get(&clean.strategy).filter(|vars| !vars.is_empty())?;
+            let project_dir = scratch_dir.join(scratch_name(&clean.project));
+            let vars = vars
+                .iter()
+                .map(|(var, subdir)| (var.clone(), project_dir.join(subdir)))
+                .collect();
+            Some(Suggestion { clean, vars })
+        })
+        .collect()
+}
+
+/// Directory name for a project on the scratch disk, derived from its whole
+/// path so same-named projects don't collide (`/home/me/app` → `home-me-app`)
+fn scratch_name(project: &Path) -> String {
+    project
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().replace(['-', ' '], "_")),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// File that `tool` reads in a project
+pub fn config_file(tool: EnvTool, project: &Path) -> PathBuf {
+    match tool {
+        EnvTool::Direnv => project.join(".envrc"),
+        EnvTool::Mise => project.join("mise.toml"),
+    }
+}
+
+/// The lines that set the suggested variables for `tool`
+pub fn snippet(tool: EnvTool, vars: &[(String, PathBuf)]) -> String {
+    let mut lines = Vec::new();
+    match tool {
+        EnvTool::Direnv => {
+            for (var, dir) in vars {
+                lines.push(format!("export {}={}", var, shell_quote(dir)));
+            }
+        }
+        EnvTool::Mise => {
+            lines.push("[env]".to_string());
+            for (var, dir) in vars {
+                lines.push(format!("{} = {}", var, toml_quote(dir)));
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Path as a TOML basic string
+fn toml_quote(path: &Path) -> String {
+    format!("\"{}\"", path.display().to_string().replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Adds the suggested variables to the project's `tool` config file
+pub fn write(tool: EnvTool, suggestion: &Suggestion) -> Result<WriteOutcome> {
+    let path = config_file(tool, &suggestion.clean.project);
+    let existing = if path.exists() {
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?
+    } else {
+        String::new()
+    };
+
+    let missing: Vec<(String, PathBuf)> = suggestion
+        .vars
+        .iter()
+        .filter(|(var, _)| !existing.contains(var.as_str()))
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return Ok(WriteOutcome::AlreadySet(path));
+    }
+    // A second [env] table would make the file invalid TOML
+    if tool == EnvTool::Mise && existing.lines().any(|line| line.trim() == "[env]") {
+        return Ok(WriteOutcome::Skipped(path, "it already has an [env] table"));
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    let separator = if existing.is_empty() || existing.ends_with("\n\n") {
+        ""
+    } else if existing.ends_with('\n') {
+        "\n"
+    } else {
+        "\n\n"
+    };
+    writeln!(
+        file,
+        "{}# Build output on the scratch disk (added by spektr relocate)\n{}",
+        separator,
+        snippet(tool, &missing)
+    )
+    .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    for (_, dir) in &missing {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    Ok(WriteOutcome::Written(path))
+}
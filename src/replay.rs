@@ -0,0 +1,136 @@
+use crate::scanner::ScanEvent;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Format version of recordings
+const RECORDING_VERSION: u32 = 1;
+
+/// First line of a recording
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordingHeader {
+    version: u32,
+    scan_root: PathBuf,
+}
+
+/// Every following line: an event and when the scan sent it
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordedEvent {
+    /// Milliseconds since the scan started
+    at_ms: u64,
+    event: ScanEvent,
+}
+
+/// Writes a scan's events to a file (JSON lines), for `--replay-events`
+pub struct EventRecorder {
+    writer: BufWriter<File>,
+}
+
+impl EventRecorder {
+    /// Creates (or truncates) the recording at `path` and writes its header
+    pub fn create(path: &Path, scan_root: &Path) -> Result<Self> {
+        let file = File::create(path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut writer = BufWriter::new(file);
+        let header = RecordingHeader {
+            version: RECORDING_VERSION,
+            scan_root: scan_root.to_path_buf(),
+        };
+        writeln!(writer, "{}", serde_json::to_string(&header)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(Self { writer })
+    }
+
+    /// Passes the scan's events on while recording them. Writing is best
+    /// effort: a failed write ends the recording, never the scan.
+    pub fn tee(self, rx: Receiver<ScanEvent>) -> Receiver<ScanEvent> {
+        let mut writer = self.writer;
+        let (tx, forwarded) = mpsc::channel();
+        thread::spawn(move || {
+            let started = Instant::now();
+            let mut recording = true;
+            for event in rx {
+                if recording {
+                    let recorded = RecordedEvent {
+                        at_ms: started.elapsed().as_millis() as u64,
+                        event: event.clone(),
+                    };
+                    recording = serde_json::to_string(&recorded)
+                        .ok()
+                        .is_some_and(|line| writeln!(writer, "{}", line).is_ok());
+                }
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+            let _ = writer.flush();
+        });
+        forwarded
+    }
+}
+
+/// Scan events saved by `--record-events`
+pub struct Recording {
+    pub scan_root: PathBuf,
+    events: Vec<RecordedEvent>,
+}
+
+impl Recording {
+    pub fn load(path: &Path) -> Result<Self> {
+        let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut lines = BufReader::new(file).lines();
+        let header: RecordingHeader = match lines.next() {
+            Some(line) => serde_json::from_str(&line?).context("Not an event recording")?,
+            None => bail!("{} is empty", path.display()),
+        };
+        if header.version != RECORDING_VERSION {
+            bail!("Unsupported event recording version {}", header.version);
+        }
+
+        let mut events = Vec::new();
+        for (number, line) in lines.enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            // Line 1 is the header
+            let event = serde_json::from_str(&line).with_context(|| format!("Bad event on line {}", number + 2))?;
+            events.push(event);
+        }
+        Ok(Self {
+            scan_root: header.scan_root,
+            events,
+        })
+    }
+
+    /// Sends the events again on a background thread, `speed` times as fast as
+    /// they were recorded
+    pub fn play(&self, speed: f64) -> Receiver<ScanEvent> {
+        let timeline: Vec<(Duration, ScanEvent)> = self
+            .events
+            .iter()
+            .map(|recorded| {
+                let at = Duration::from_millis(recorded.at_ms).div_f64(speed);
+                (at, recorded.event.clone())
+            })
+            .collect();
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let started = Instant::now();
+            for (at, event) in timeline {
+                if let Some(wait) = at.checked_sub(started.elapsed()) {
+                    thread::sleep(wait);
+                }
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        rx
+    }
+}
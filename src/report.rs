@@ -0,0 +1,318 @@
+use crate::cleaner::NativeClean;
+use crate::format::{format_size, SizeUnits};
+use crate::mounts::mount_point;
+use crate::scanner::CleanableProject;
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Reclaimable space grouped under a directory of the scan root
+#[derive(Debug, Clone)]
+pub struct DirectorySummary {
+    pub path: PathBuf,
+    pub total_size: u64,
+    pub project_count: usize,
+}
+
+/// Groups projects by their ancestor directory `depth` levels below the scan root,
+/// largest first. Projects shallower than `depth` are grouped under their own root.
+pub fn summarize_by_directory(
+    projects: &[CleanableProject],
+    scan_root: &Path,
+    depth: usize,
+) -> Vec<DirectorySummary> {
+    let mut groups: HashMap<PathBuf, DirectorySummary> = HashMap::new();
+
+    for project in projects {
+        let key = match project.root_path.strip_prefix(scan_root) {
+            Ok(relative) => relative.components().take(depth.max(1)).collect(),
+            Err(_) => project.root_path.clone(),
+        };
+
+        let summary = groups.entry(key.clone()).or_insert(DirectorySummary {
+            path: key,
+            total_size: 0,
+            project_count: 0,
+        });
+        summary.total_size += project.total_size;
+        summary.project_count += 1;
+    }
+
+    let mut summaries: Vec<DirectorySummary> = groups.into_values().collect();
+    summaries.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.path.cmp(&b.path)));
+    summaries
+}
+
+/// Reclaimable space on one mounted volume
+#[derive(Debug, Clone)]
+pub struct MountSummary {
+    pub mount_point: PathBuf,
+    pub total_size: u64,
+    pub project_count: usize,
+}
+
+/// Groups projects by the mount point they live on, largest first
+pub fn summarize_by_mount(projects: &[CleanableProject]) -> Vec<MountSummary> {
+    let mut groups: HashMap<PathBuf, MountSummary> = HashMap::new();
+
+    for project in projects {
+        let mount = mount_point(&project.root_path);
+        let summary = groups.entry(mount.clone()).or_insert(MountSummary {
+            mount_point: mount,
+            total_size: 0,
+            project_count: 0,
+        });
+        summary.total_size += project.total_size;
+        summary.project_count += 1;
+    }
+
+    let mut summaries: Vec<MountSummary> = groups.into_values().collect();
+    summaries.sort_by(|a, b| {
+        b.total_size
+            .cmp(&a.total_size)
+            .then_with(|| a.mount_point.cmp(&b.mount_point))
+    });
+    summaries
+}
+
+/// Upper bounds (in days) and labels of the age buckets; the last is open-ended
+const AGE_BUCKETS: [(Option<u64>, &str); 4] = [
+    (Some(7), "0–7d"),
+    (Some(30), "7–30d"),
+    (Some(90), "30–90d"),
+    (None, ">90d"),
+];
+
+/// Reclaimable space of projects last modified within an age range
+#[derive(Debug, Clone)]
+pub struct AgeSummary {
+    pub label: &'static str,
+    pub total_size: u64,
+    pub project_count: usize,
+}
+
+/// Buckets projects by the age of their last modification (0–7d, 7–30d,
+/// 30–90d, >90d). Projects with an unknown age count as the newest bucket.
+pub fn summarize_by_age(projects: &[CleanableProject], now: SystemTime) -> Vec<AgeSummary> {
+    let mut summaries: Vec<AgeSummary> = AGE_BUCKETS
+        .iter()
+        .map(|(_, label)| AgeSummary {
+            label,
+            total_size: 0,
+            project_count: 0,
+        })
+        .collect();
+
+    for project in projects {
+        let days = project
+            .last_modified
+            .and_then(|modified| now.duration_since(modified).ok())
+            .map_or(0, |age| age.as_secs() / 86_400);
+
+        let bucket = AGE_BUCKETS
+            .iter()
+            .position(|(max_days, _)| max_days.is_none_or(|max| days < max))
+            .unwrap_or(AGE_BUCKETS.len() - 1);
+
+        summaries[bucket].total_size += project.total_size;
+        summaries[bucket].project_count += 1;
+    }
+
+    summaries
+}
+
+/// Totals for all projects detected by one strategy
+#[derive(Debug, Clone)]
+pub struct StrategySummary {
+    pub name: String,
+    pub project_count: usize,
+    pub total_size: u64,
+    /// Largest project of this type (path, size)
+    pub largest: Option<(PathBuf, u64)>,
+}
+
+/// Groups projects by strategy, largest total first
+pub fn summarize_by_strategy(projects: &[CleanableProject]) -> Vec<StrategySummary> {
+    let mut groups: HashMap<&str, StrategySummary> = HashMap::new();
+
+    for project in projects {
+        let summary = groups
+            .entry(project.strategy_name.as_str())
+            .or_insert_with(|| StrategySummary {
+                name: project.strategy_name.clone(),
+                project_count: 0,
+                total_size: 0,
+                largest: None,
+            });
+        summary.project_count += 1;
+        summary.total_size += project.total_size;
+
+        let is_largest = summary
+            .largest
+            .as_ref()
+            .is_none_or(|(_, size)| project.total_size > *size);
+        if is_largest {
+            summary.largest = Some((project.root_path.clone(), project.total_size));
+        }
+    }
+
+    let mut summaries: Vec<StrategySummary> = groups.into_values().collect();
+    summaries.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.name.cmp(&b.name)));
+    summaries
+}
+
+/// The `count` largest projects, largest first
+pub fn largest_projects(projects: &[CleanableProject], count: usize) -> Vec<&CleanableProject> {
+    let mut sorted: Vec<&CleanableProject> = projects.iter().collect();
+    sorted.sort_by_key(|p| std::cmp::Reverse(p.total_size));
+    sorted.truncate(count);
+    sorted
+}
+
+/// Sizing slower than this marks a project as an outlier worth a look
+pub const SLOW_SIZING: Duration = Duration::from_secs(5);
+
+/// Up to `count` projects whose sizing took at least `SLOW_SIZING`, slowest first
+pub fn slowest_to_size(projects: &[CleanableProject], count: usize) -> Vec<&CleanableProject> {
+    let mut slow: Vec<&CleanableProject> = projects
+        .iter()
+        .filter(|p| p.sizing_time >= SLOW_SIZING)
+        .collect();
+    slow.sort_by_key(|p| std::cmp::Reverse(p.sizing_time));
+    slow.truncate(count);
+    slow
+}
+
+/// Output format of `spektr report` subcommands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned table for reading in a terminal
+    Table,
+    /// JSON array for scripts and dashboards
+    Json,
+}
+
+/// Output order of projects in scan and clean modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ProjectSort {
+    /// Largest first
+    Size,
+    /// Least recently modified first
+    Age,
+    /// By path
+    Name,
+}
+
+/// Keeps the `top` largest projects (all if `None`), ordered by `sort`
+/// (largest first if `None`)
+pub fn select_projects(
+    mut projects: Vec<CleanableProject>,
+    top: Option<usize>,
+    sort: Option<ProjectSort>,
+) -> Vec<CleanableProject> {
+    if let Some(top) = top {
+        projects.sort_by_key(|p| std::cmp::Reverse(p.total_size));
+        projects.truncate(top);
+    }
+
+    match sort.unwrap_or(ProjectSort::Size) {
+        ProjectSort::Size => projects.sort_by_key(|p| std::cmp::Reverse(p.total_size)),
+        // Unknown ages (None) sort first, alongside the oldest
+        ProjectSort::Age => projects.sort_by_key(|p| p.last_modified),
+        ProjectSort::Name => projects.sort_by(|a, b| a.root_path.cmp(&b.root_path)),
+    }
+    projects
+}
+
+/// Outcome of an unattended clean session, for writing to a file or emailing
+#[derive(Debug)]
+pub struct CleanReport {
+    pub scan_root: PathBuf,
+    pub started_at: SystemTime,
+    pub finished_at: SystemTime,
+    pub dry_run: bool,
+    pub cleaned: Vec<CleanableProject>,
+    /// Projects that failed to clean, with the error message
+    pub failures: Vec<(PathBuf, String)>,
+    /// Clean commands run by `--native-clean`, by project root
+    pub native_cleans: Vec<(PathBuf, NativeClean)>,
+}
+
+impl CleanReport {
+    pub fn freed_size(&self) -> u64 {
+        self.cleaned.iter().map(|p| p.total_size).sum()
+    }
+
+    /// One-line summary, used as the email subject
+    pub fn subject(&self) -> String {
+        let status = if self.failures.is_empty() { "OK" } else { "FAILURES" };
+        format!(
+            "spektr clean [{}]: {} on {}",
+            status,
+            format_size(self.freed_size(), SizeUnits::Auto),
+            self.scan_root.display()
+        )
+    }
+
+    /// Plain-text report listing cleaned projects and failures
+    pub fn render_text(&self, units: SizeUnits) -> String {
+        let mut out = String::new();
+        let duration = self
+            .finished_at
+            .duration_since(self.started_at)
+            .unwrap_or_default();
+
+        let _ = writeln!(out, "SPEKTR clean report");
+        let _ = writeln!(out, "Root:     {}", self.scan_root.display());
+        let _ = writeln!(out, "Started:  {}", humantime::format_rfc3339_seconds(self.started_at));
+        let _ = writeln!(out, "Duration: {}s", duration.as_secs());
+        if self.dry_run {
+            let _ = writeln!(out, "Mode:     dry run (nothing was deleted)");
+        }
+        let _ = writeln!(
+            out,
+            "Cleaned:  {} projects, {}",
+            self.cleaned.len(),
+            format_size(self.freed_size(), units)
+        );
+        let _ = writeln!(out, "Failures: {}", self.failures.len());
+
+        if !self.failures.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "Needs attention:");
+            for (path, error) in &self.failures {
+                let _ = writeln!(out, "  {}: {}", path.display(), error);
+            }
+        }
+
+        if !self.cleaned.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "Cleaned projects:");
+            for project in &self.cleaned {
+                let _ = writeln!(
+                    out,
+                    "  {:>10}  {} ({})",
+                    format_size(project.total_size, units),
+                    project.root_path.display(),
+                    project.strategy_name
+                );
+            }
+        }
+
+        if !self.native_cleans.is_empty() {
+            let _ = writeln!(out);
+            let _ = writeln!(out, "Clean commands:");
+            for (path, native) in &self.native_cleans {
+                let status = if native.success { "ok" } else { "failed, targets deleted directly" };
+                let _ = writeln!(out, "  {} in {} ({})", native.command, path.display(), status);
+                for line in native.output.lines() {
+                    let _ = writeln!(out, "    {}", line);
+                }
+            }
+        }
+
+        out
+    }
+}
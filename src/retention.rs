@@ -0,0 +1,215 @@
+use crate::format::{format_size, parse_size, SizeUnits};
+use crate::scanner::strategy::{matches_strategy, StrategySettings};
+use crate::scanner::CleanableProject;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Config rule deciding which projects a retention plan cleans, e.g.
+/// `{ strategy = "rust", keep_recent = 3 }` or `{ path = "~/work", max_total = "100 GB" }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct RetentionRule {
+    /// Only projects of this strategy
+    pub strategy: Option<String>,
+    /// Only projects under this directory; a leading `~` is the home directory
+    pub path: Option<String>,
+    /// Keep this many of the most recently built projects in each directory,
+    /// and clean the rest
+    pub keep_recent: Option<usize>,
+    /// Keep the matching projects' reclaimable total under this size (e.g.,
+    /// "100 GB"), cleaning the least recently built first
+    pub max_total: Option<String>,
+}
+
+/// A rule with its path expanded and its size limit parsed
+#[derive(Debug, Clone)]
+struct CompiledRule {
+    strategy: Option<String>,
+    path: Option<PathBuf>,
+    keep_recent: Option<usize>,
+    max_total: Option<u64>,
+}
+
+impl CompiledRule {
+    fn matches(&self, project: &CleanableProject) -> bool {
+        !project.manual_only
+            && self
+                .strategy
+                .as_ref()
+                .is_none_or(|name| matches_strategy(&project.strategy_name, name))
+            && self.path.as_ref().is_none_or(|path| project.root_path.starts_with(path))
+    }
+
+    /// "Rust projects under ~/work", for plan reasons
+    fn scope(&self) -> String {
+        let kind = match &self.strategy {
+            Some(name) => format!("{} projects", name),
+            None => "projects".to_string(),
+        };
+        match &self.path {
+            Some(path) => format!("{} under {}", kind, path.display()),
+            None => kind,
+        }
+    }
+}
+
+/// A project the retention rules propose cleaning, and the rule's reason
+#[derive(Debug, Clone)]
+pub struct PlannedClean<'a> {
+    pub project: &'a CleanableProject,
+    pub reason: String,
+}
+
+/// Compiled `[[retention]]` rules. Each rule proposes projects on its own;
+/// a project proposed by several rules is listed once, with the first reason.
+#[derive(Debug, Clone, Default)]
+pub struct RetentionPolicy {
+    rules: Vec<CompiledRule>,
+}
+
+impl RetentionPolicy {
+    /// Compiles `rules`; strategy names must be among `settings`' built-in
+    /// and custom strategies
+    pub fn new(rules: Vec<RetentionRule>, settings: &StrategySettings) -> Result<Self> {
+        let strategies = settings.all_strategies();
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                if rule.keep_recent.is_none() && rule.max_total.is_none() {
+                    bail!("Retention rules need keep_recent or max_total");
+                }
+                if let Some(name) = &rule.strategy {
+                    if !strategies.iter().any(|s| matches_strategy(s.name(), name)) {
+                        bail!("Unknown strategy '{}' in a retention rule", name);
+                    }
+                }
+                let path = rule.path.as_deref().map(expand_home);
+                let max_total = rule
+                    .max_total
+                    .as_deref()
+                    .map(parse_size)
+                    .transpose()
+                    .context("Invalid max_total in a retention rule")?;
+                Ok(CompiledRule {
+                    strategy: rule.strategy,
+                    path,
+                    keep_recent: rule.keep_recent,
+                    max_total,
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The projects to clean so every rule holds, in the order of `projects`
+    pub fn plan<'a>(&self, projects: &'a [CleanableProject]) -> Vec<PlannedClean<'a>> {
+        let mut reasons: HashMap<usize, String> = HashMap::new();
+        for rule in &self.rules {
+            let matching: Vec<usize> = (0..projects.len()).filter(|&i| rule.matches(&projects[i])).collect();
+            if let Some(keep) = rule.keep_recent {
+                for (i, reason) in keep_recent(projects, &matching, keep) {
+                    reasons.entry(i).or_insert(reason);
+                }
+            }
+            if let Some(limit) = rule.max_total {
+                for i in cap_total(projects, &matching, limit, &reasons) {
+                    reasons.entry(i).or_insert_with(|| {
+                        format!("keeps {} within {}", rule.scope(), format_size(limit, SizeUnits::Auto))
+                    });
+                }
+            }
+        }
+
+        let mut planned: Vec<usize> = reasons.keys().copied().collect();
+        planned.sort_unstable();
+        planned
+            .into_iter()
+            .map(|i| PlannedClean {
+                project: &projects[i],
+                reason: reasons.remove(&i).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// The path with a leading `~` replaced by the home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix('~'), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest.trim_start_matches(['/', '\\'])),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Projects without a modification time count as the oldest
+pub fn built_at(project: &CleanableProject) -> SystemTime {
+    project.last_modified.unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// All but the `keep` most recently built of `matching` in each parent directory
+fn keep_recent(projects: &[CleanableProject], matching: &[usize], keep: usize) -> Vec<(usize, String)> {
+    let mut by_parent: BTreeMap<&Path, Vec<usize>> = BTreeMap::new();
+    for &i in matching {
+        let parent = projects[i].root_path.parent().unwrap_or(&projects[i].root_path);
+        by_parent.entry(parent).or_default().push(i);
+    }
+
+    let mut older = Vec::new();
+    for (parent, mut group) in by_parent {
+        group.sort_by_key(|&i| std::cmp::Reverse(built_at(&projects[i])));
+        for (rank, &i) in group.iter().enumerate().skip(keep) {
+            older.push((
+                i,
+                format!(
+                    "built {} most recently of {} {} projects in {} (keeps {})",
+                    ordinal(rank + 1),
+                    group.len(),
+                    projects[i].strategy_name,
+                    parent.display(),
+                    keep
+                ),
+            ));
+        }
+    }
+    older
+}
+
+/// Least recently built of `matching` until the rest fit under `limit`.
+/// Projects an earlier rule already cleans don't count toward the total.
+fn cap_total(
+    projects: &[CleanableProject],
+    matching: &[usize],
+    limit: u64,
+    planned: &HashMap<usize, String>,
+) -> Vec<usize> {
+    let mut remaining: Vec<usize> = matching.iter().copied().filter(|i| !planned.contains_key(i)).collect();
+    let mut total: u64 = remaining.iter().map(|&i| projects[i].total_size).sum();
+    remaining.sort_by_key(|&i| built_at(&projects[i]));
+
+    let mut cleaned = Vec::new();
+    for i in remaining {
+        if total <= limit {
+            break;
+        }
+        total -= projects[i].total_size;
+        cleaned.push(i);
+    }
+    cleaned
+}
+
+/// "1st", "2nd", "3rd", "4th", ...
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
+}
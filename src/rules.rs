@@ -0,0 +1,76 @@
+use crate::scanner::{CleanableProject, RiskLevel};
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Config rule for projects under a path glob, e.g.
+/// `{ path = "~/experiments/**", risk = "low", auto_select = true }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PathRule {
+    /// Glob matched against project roots; a leading `~` is the home directory
+    pub path: String,
+    /// Risk level applied to every target of matching projects
+    pub risk: Option<RiskLevel>,
+    /// `true` preselects matching projects in the TUI; `false` keeps them
+    /// out of unattended cleans
+    pub auto_select: Option<bool>,
+}
+
+/// Compiled path rules. Rules are checked in order; for each setting, the
+/// first matching rule that sets it wins.
+#[derive(Debug, Clone, Default)]
+pub struct PathRules {
+    rules: Vec<(GlobSet, PathRule)>,
+}
+
+impl PathRules {
+    pub fn new(rules: Vec<PathRule>) -> Result<Self> {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                let pattern = match (rule.path.strip_prefix('~'), dirs::home_dir()) {
+                    (Some(rest), Some(home)) => format!("{}{}", home.display(), rest),
+                    _ => rule.path.clone(),
+                };
+                let mut builder = GlobSetBuilder::new();
+                builder.add(Glob::new(&pattern).with_context(|| format!("Invalid path rule glob '{}'", rule.path))?);
+                // "dir/**" also covers a project at "dir" itself
+                if let Some(dir) = pattern.strip_suffix("/**") {
+                    builder.add(Glob::new(dir)?);
+                }
+                let matcher = builder.build()?;
+                Ok((matcher, rule))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { rules })
+    }
+
+    fn matching<'a>(&'a self, path: &'a Path) -> impl Iterator<Item = &'a PathRule> {
+        self.rules
+            .iter()
+            .filter(move |(matcher, _)| matcher.is_match(path))
+            .map(|(_, rule)| rule)
+    }
+
+    /// Risk level forced on projects at `path`, if any rule sets one
+    pub fn risk_for(&self, path: &Path) -> Option<RiskLevel> {
+        self.matching(path).find_map(|rule| rule.risk)
+    }
+
+    /// Whether projects at `path` are auto-selected (`Some(true)`) or never
+    /// auto-selected (`Some(false)`); `None` when no rule decides
+    pub fn auto_select(&self, path: &Path) -> Option<bool> {
+        self.matching(path).find_map(|rule| rule.auto_select)
+    }
+
+    /// Applies a forced risk level to every target of `project`
+    pub fn apply(&self, project: &mut CleanableProject) {
+        if let Some(risk) = self.risk_for(&project.root_path) {
+            for target in &mut project.targets {
+                target.risk_level = risk;
+            }
+            project.risk_level = risk;
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::path::{Path, PathBuf};
+
+/// Files that identify a `coverage/` directory as a coverage report rather
+/// than source code that happens to share the name
+const COVERAGE_DIR_MARKERS: [&str; 6] = [
+    "lcov.info",
+    "lcov-report",
+    "coverage-final.json",
+    "coverage-summary.json",
+    "clover.xml",
+    "cobertura-coverage.xml",
+];
+
+/// Files that identify an `htmlcov/` directory as a coverage.py report
+const HTMLCOV_MARKERS: [&str; 2] = ["status.json", "coverage_html.js"];
+
+/// Coverage reports at the top of a project root, whatever its type:
+/// Istanbul/nyc/Jest/Vitest `coverage/` and `.nyc_output/`, coverage.py
+/// `htmlcov/`, cargo-tarpaulin `tarpaulin-report.*`, and stray `lcov.info` files.
+/// Returns each report with what deleting it means.
+pub fn find(root: &Path) -> Vec<(PathBuf, &'static str)> {
+    let mut found = Vec::new();
+
+    let coverage = root.join("coverage");
+    if COVERAGE_DIR_MARKERS.iter().any(|marker| coverage.join(marker).exists()) {
+        found.push((coverage, "Test coverage report; regenerated by the next test run with coverage"));
+    }
+
+    let nyc_output = root.join(".nyc_output");
+    if nyc_output.is_dir() {
+        found.push((nyc_output, "Raw nyc coverage data; regenerated by the next `nyc` run"));
+    }
+
+    let htmlcov = root.join("htmlcov");
+    if HTMLCOV_MARKERS.iter().any(|marker| htmlcov.join(marker).exists()) {
+        found.push((htmlcov, "coverage.py HTML report; regenerated by `coverage html`"));
+    }
+
+    let lcov = root.join("lcov.info");
+    if lcov.is_file() {
+        found.push((lcov, "LCOV coverage data (a file); regenerated by the next test run with coverage"));
+    }
+
+    if let Ok(entries) = std::fs::read_dir(root) {
+        let mut reports: Vec<PathBuf> = entries
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("tarpaulin-report"))
+            .map(|entry| entry.path())
+            .collect();
+        reports.sort();
+        found.extend(
+            reports
+                .into_iter()
+                .map(|report| (report, "cargo-tarpaulin report; regenerated by the next `cargo tarpaulin`")),
+        );
+    }
+
+    found
+}
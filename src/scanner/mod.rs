@@ -1,80 +1,608 @@
+pub mod coverage;
+pub mod rebuild;
+pub mod size_cache;
+pub mod snapshot;
 pub mod strategy;
 
 use rayon::prelude::*;
+pub use rebuild::RebuildEstimate;
+pub use size_cache::SizeCache;
+pub use snapshot::TargetSnapshot;
 pub use strategy::{CleaningStrategy, RiskLevel};
+use strategy::expand_target;
+use crate::mounts;
+use crate::rules::PathRules;
 use anyhow::Result;
+use globset::{Glob, GlobMatcher};
 use jwalk::WalkDir;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Marker file that protects a directory, and everything under it, from all strategies
+pub const KEEP_MARKER: &str = ".spektr-keep";
+
+/// Hidden directories walked even when hidden directories are skipped:
+/// tools keep `CACHEDIR.TAG`-tagged caches under `.cache`
+const WALKED_HIDDEN_DIRS: &[&str] = &[".cache"];
+
+/// Whether `path` or one of its ancestors holds a keep marker
+fn is_kept(path: &Path) -> bool {
+    path.ancestors().any(|dir| dir.join(KEEP_MARKER).exists())
+}
+
+/// Whether a keep marker sits anywhere inside a target, which then isn't
+/// cleaned: deleting it would take the kept directory along
+pub fn holds_keep_marker(target: &Path) -> bool {
+    WalkDir::new(target)
+        .skip_hidden(false)
+        .parallelism(jwalk::Parallelism::Serial)
+        .into_iter()
+        .flatten()
+        .any(|entry| entry.file_name() == KEEP_MARKER)
+}
+
+/// Keeps a walk out of `.git` directories among `children`
+pub(crate) fn skip_git(children: &mut [jwalk::Result<jwalk::DirEntry<((), ())>>]) {
+    for child in children.iter_mut().flatten() {
+        if child.file_name() == ".git" {
+            child.read_children_path = None;
+        }
+    }
+}
+
+/// Drops targets that resolve to, or into, another target, so a workspace
+/// member's symlink to the hoisted `node_modules` or the shared `target`
+/// isn't sized twice. Real directories are kept over symlinks to them, since
+/// deleting a symlink only removes the link; otherwise the first path wins.
+/// `found` is sorted by path, and stays so.
+fn drop_aliased_targets(found: &mut Vec<FoundTarget>) {
+    if found.len() < 2 {
+        return;
+    }
+    found.sort_by_key(|target| target.path.is_symlink());
+    let mut kept: Vec<PathBuf> = Vec::new();
+    found.retain(|target| {
+        let resolved = target.path.canonicalize().unwrap_or_else(|_| target.path.clone());
+        if kept.iter().any(|earlier| resolved.starts_with(earlier)) {
+            return false;
+        }
+        kept.push(resolved);
+        true
+    });
+    found.sort_by(|a, b| a.path.cmp(&b.path));
+}
+
+/// A single artifact directory within a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanTarget {
+    pub path: PathBuf,
+    pub size: u64,
+    pub risk_level: RiskLevel,
+    /// What deleting this target means, from the strategy
+    #[serde(default)]
+    pub explanation: String,
+    /// State when the target was sized; not kept in saved results, whose
+    /// targets may have changed legitimately since
+    #[serde(skip)]
+    pub snapshot: Option<TargetSnapshot>,
+}
 
 /// Represents a discovered project that can be cleaned
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CleanableProject {
     pub root_path: PathBuf,
     pub strategy_name: String,
-    pub targets: Vec<PathBuf>,
+    pub targets: Vec<CleanTarget>,
     pub total_size: u64,
-    #[allow(dead_code)]
+    /// Highest risk level among the targets
     pub risk_level: RiskLevel,
+    /// Most recent modification of the project root or its targets
+    #[serde(default)]
+    pub last_modified: Option<SystemTime>,
+    /// When a toolchain was last run, for strategies that can tell
+    #[serde(default)]
+    pub last_used: Option<SystemTime>,
+    /// Newest modification of anything inside the targets (a file written,
+    /// added, or removed); unknown when a target's sizing was skipped
+    #[serde(default)]
+    pub artifacts_modified: Option<SystemTime>,
+    /// How long the targets take to come back once deleted; unknown until sized
+    #[serde(default)]
+    pub rebuild: Option<RebuildEstimate>,
+    /// Time spent sizing the targets (near zero when sizes were cached)
+    #[serde(default)]
+    pub sizing_time: Duration,
+    /// Number of files in the targets
+    #[serde(default)]
+    pub file_count: u64,
+    /// Sizing was skipped: sizes are zero but the targets are still cleanable
+    #[serde(default)]
+    pub size_unknown: bool,
+    /// Some target sizes were taken from an earlier scan instead of walking
+    /// the targets, so changes inside them may be missed
+    #[serde(default)]
+    pub size_estimated: bool,
+    /// Only cleaned when the user picks it: never selected by path rules or
+    /// retention plans, nor cleaned unattended
+    #[serde(default)]
+    pub manual_only: bool,
+    /// Member packages folded into this workspace root, which their targets
+    /// are counted under
+    #[serde(default)]
+    pub members: Vec<PathBuf>,
+}
+
+impl CleanableProject {
+    /// Returns a copy limited to targets at or below `max_risk`,
+    /// or `None` if no target qualifies
+    pub fn restricted_to(&self, max_risk: RiskLevel) -> Option<CleanableProject> {
+        let targets: Vec<CleanTarget> = self
+            .targets
+            .iter()
+            .filter(|t| t.risk_level <= max_risk)
+            .cloned()
+            .collect();
+
+        if targets.is_empty() {
+            return None;
+        }
+
+        Some(CleanableProject {
+            root_path: self.root_path.clone(),
+            strategy_name: self.strategy_name.clone(),
+            total_size: targets.iter().map(|t| t.size).sum(),
+            risk_level: targets.iter().map(|t| t.risk_level).max().unwrap_or(RiskLevel::Low),
+            last_modified: self.last_modified,
+            last_used: self.last_used,
+            artifacts_modified: self.artifacts_modified,
+            rebuild: self.rebuild.clone(),
+            sizing_time: self.sizing_time,
+            file_count: self.file_count,
+            size_unknown: self.size_unknown,
+            size_estimated: self.size_estimated,
+            manual_only: self.manual_only,
+            members: self.members.clone(),
+            targets,
+        })
+    }
+
+    /// Each workspace member with the total size of its targets
+    pub fn member_sizes(&self) -> Vec<(&Path, u64)> {
+        self.members
+            .iter()
+            .map(|member| {
+                let size = self
+                    .targets
+                    .iter()
+                    .filter(|target| target.path.starts_with(member))
+                    .map(|target| target.size)
+                    .sum();
+                (member.as_path(), size)
+            })
+            .collect()
+    }
+}
+
+/// Project roots whose sizing should be abandoned, shared with a running scan
+#[derive(Debug, Clone, Default)]
+pub struct SkipSizing(Arc<Mutex<HashSet<PathBuf>>>);
+
+impl SkipSizing {
+    /// Stops sizing the project at `root`; it is reported with an unknown size
+    pub fn skip(&self, root: &Path) {
+        if let Ok(mut roots) = self.0.lock() {
+            roots.insert(root.to_path_buf());
+        }
+    }
+
+    fn is_skipped(&self, root: &Path) -> bool {
+        self.0.lock().is_ok_and(|roots| roots.contains(root))
+    }
+}
+
+/// An existing target directory awaiting sizing
+#[derive(Clone)]
+struct FoundTarget {
+    path: PathBuf,
+    risk_level: RiskLevel,
+    explanation: String,
+}
+
+/// A detected project root awaiting deduplication and sizing
+struct Candidate {
+    root: PathBuf,
+    strategy_idx: usize,
+    /// Nested module roots folded into this project (multi-module workspaces)
+    modules: Vec<PathBuf>,
 }
 
 /// Scanner that uses multiple cleaning strategies to find cleanable artifacts
 pub struct Scanner {
     strategies: Vec<Box<dyn CleaningStrategy>>,
+    size_cache: Option<SizeCache>,
+    /// Only report projects on the volume mounted here
+    mount: Option<PathBuf>,
+    /// Also offer strategies' global locations outside the scanned tree
+    include_global: bool,
+    /// Per-path risk overrides from the config
+    path_rules: PathRules,
+    /// Projects whose sizing the user gave up on
+    skip_sizing: SkipSizing,
+    /// When set, discovery doesn't descend into hidden directories except these
+    walked_hidden: Option<Vec<String>>,
+    /// Reuse cached sizes of targets whose top level is unchanged
+    reuse_cached: bool,
+    /// Trust cached sizes even for targets that changed since
+    estimate_sizes: bool,
+    /// Newest versions of each tool in versioned caches left out
+    kept_versions: usize,
+    /// Only report projects whose targets haven't changed for this long
+    older_than: Option<Duration>,
 }
 
 impl Scanner {
     pub fn new(strategies: Vec<Box<dyn CleaningStrategy>>) -> Self {
-        Self { strategies }
+        Self {
+            strategies,
+            size_cache: None,
+            mount: None,
+            include_global: false,
+            path_rules: PathRules::default(),
+            skip_sizing: SkipSizing::default(),
+            walked_hidden: None,
+            reuse_cached: false,
+            estimate_sizes: false,
+            kept_versions: 0,
+            older_than: None,
+        }
+    }
+
+    /// Records target sizes for later scans, which reuse them with
+    /// `with_cached_sizes` or `with_estimated_sizes`
+    pub fn with_size_cache(mut self, cache: SizeCache) -> Self {
+        self.size_cache = Some(cache);
+        self
+    }
+
+    /// Reuses the cached size of a target whose mtime and entry count are
+    /// unchanged instead of walking it (needs a size cache). Changes deeper
+    /// inside the target go unnoticed, so these sizes count as estimates.
+    pub fn with_cached_sizes(mut self) -> Self {
+        self.reuse_cached = true;
+        self
+    }
+
+    /// Takes any cached size as an estimate instead of re-checking the
+    /// target, so only never-seen targets are walked (needs a size cache)
+    pub fn with_estimated_sizes(mut self) -> Self {
+        self.estimate_sizes = true;
+        self
+    }
+
+    /// Skips projects that aren't on the volume mounted at `mount`
+    pub fn with_mount_filter(mut self, mount: PathBuf) -> Self {
+        self.mount = Some(mount);
+        self
+    }
+
+    /// Also reports tool-wide locations outside the scanned tree
+    /// (e.g., Xcode DerivedData)
+    pub fn with_global_locations(mut self) -> Self {
+        self.include_global = true;
+        self
+    }
+
+    /// Applies the risk levels of matching path rules to found projects
+    pub fn with_path_rules(mut self, rules: PathRules) -> Self {
+        self.path_rules = rules;
+        self
+    }
+
+    /// Doesn't descend into hidden directories during discovery, except
+    /// `WALKED_HIDDEN_DIRS` and `also_walked`. Hidden directories are still
+    /// checked for project markers themselves.
+    pub fn with_skip_hidden(mut self, also_walked: Vec<String>) -> Self {
+        let mut walked: Vec<String> = WALKED_HIDDEN_DIRS.iter().map(|name| name.to_string()).collect();
+        walked.extend(also_walked);
+        self.walked_hidden = Some(walked);
+        self
+    }
+
+    /// Lets the caller abandon sizing of individual projects mid-scan
+    pub fn with_skip_sizing(mut self, skip: SkipSizing) -> Self {
+        self.skip_sizing = skip;
+        self
+    }
+
+    /// Leaves the newest `count` versions of each tool in versioned caches
+    /// (see `CleaningStrategy::target_version`) out of the results
+    pub fn with_kept_versions(mut self, count: usize) -> Self {
+        self.kept_versions = count;
+        self
+    }
+
+    /// Leaves out projects with anything inside their targets modified
+    /// within `age`, so only stale build output is offered. Targets are
+    /// walked even when cached, since the cache doesn't notice changes deep
+    /// inside them; projects whose sizing was skipped are left out, since
+    /// their targets' age is unknown.
+    pub fn with_older_than(mut self, age: Duration) -> Self {
+        self.older_than = Some(age);
+        self
     }
 
-    /// Scans a directory tree for cleanable projects
-    /// Sends updates via the provided channel
     /// Scans a directory tree for cleanable projects
     /// Sends updates via the provided channel
     pub fn scan(&self, root: &Path, tx: Sender<ScanEvent>) -> Result<Vec<CleanableProject>> {
-        struct Candidate {
-            root: PathBuf,
-            strategy_idx: usize,
-        }
-
         let mut candidates = Vec::new();
+        let _ = tx.send(ScanEvent::Phase(ScanPhase::Discovering));
 
         // 1. Discovery Phase: specific project detection
         // Use jwalk for parallel directory traversal
         let tx_progress = tx.clone();
+        let walked_hidden = self.walked_hidden.clone();
+        let mut stats = DiscoveryStats::default();
+        let mut repos = Vec::new();
+
+        // Nothing under a kept directory is reported, even when the scan starts below it
+        if is_kept(root) {
+            let _ = tx.send(ScanEvent::Discovered(stats));
+            return self.process_candidates(Vec::new(), Vec::new(), tx);
+        }
+
+        // Special filesystems under the root are skipped, and noted for the summary
+        let special: Vec<(PathBuf, String)> = mounts::special_mounts()
+            .into_iter()
+            .filter(|(mount, _)| mount.starts_with(root))
+            .collect();
+        if let Some((mount, reason)) = special.iter().find(|(mount, _)| mount == root) {
+            stats.skipped_mounts.push((mount.clone(), reason.clone()));
+            let _ = tx.send(ScanEvent::Discovered(stats));
+            return self.process_candidates(Vec::new(), Vec::new(), tx);
+        }
+        let skipped_mounts: Arc<Mutex<Vec<(PathBuf, String)>>> = Arc::default();
+        let skipped = Arc::clone(&skipped_mounts);
+
         for entry in WalkDir::new(root)
             .skip_hidden(false)
-            .process_read_dir(move |_depth, path, _read_dir_state, _children| {
+            .process_read_dir(move |_depth, path, _read_dir_state, children| {
                 // Emit scanning event (best effort)
                 let _ = tx_progress.send(ScanEvent::Scanning(path.display().to_string()));
+
+                // Nothing inside a kept directory is reported
+                let kept = children.iter().any(|child| {
+                    child
+                        .as_ref()
+                        .is_ok_and(|entry| entry.file_name() == KEEP_MARKER)
+                });
+                if kept {
+                    children.clear();
+                }
+
+                // Git internals never hold projects
+                for child in children.iter_mut().flatten() {
+                    let name = child.file_name().to_string_lossy();
+                    let skipped = name == ".git"
+                        || walked_hidden
+                            .as_ref()
+                            .is_some_and(|walked| name.starts_with('.') && !walked.iter().any(|w| *w == name));
+                    if skipped {
+                        child.read_children_path = None;
+                    }
+                }
+
+                if !special.is_empty() {
+                    for child in children.iter_mut().flatten() {
+                        if child.read_children_path.is_none() {
+                            continue;
+                        }
+                        let path = child.path();
+                        if let Some((_, reason)) = special.iter().find(|(mount, _)| *mount == path) {
+                            child.read_children_path = None;
+                            if let Ok(mut skipped) = skipped.lock() {
+                                skipped.push((path, reason.clone()));
+                            }
+                        }
+                    }
+                }
             })
             .parallelism(jwalk::Parallelism::RayonNewPool(num_cpus::get()))
         {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_dir() {
-                for (idx, strategy) in self.strategies.iter().enumerate() {
-                    if strategy.detect(&path) {
-                        candidates.push(Candidate {
-                            root: path.clone(),
-                            strategy_idx: idx,
-                        });
-                        // Once a strategy matches, stop checking others for this dir
-                        // (Assuming one dir isn't multiple project types simultaneously, or if so, first wins)
-                        break; 
-                    }
+            // `.git` may also be a file (worktrees, submodules)
+            if entry.file_name() == ".git" {
+                repos.extend(path.parent().map(Path::to_path_buf));
+            }
+            if !path.is_dir() {
+                continue;
+            }
+            let started = Instant::now();
+            if !path.join(KEEP_MARKER).exists() {
+                candidates.extend(self.detect(&path));
+            }
+
+            stats.directories += 1;
+            let hidden = path.strip_prefix(root).is_ok_and(|relative| {
+                relative
+                    .components()
+                    .any(|c| c.as_os_str().to_string_lossy().starts_with('.'))
+            });
+            if hidden {
+                stats.hidden_directories += 1;
+                stats.hidden_time += started.elapsed();
+            }
+        }
+        if let Ok(mut skipped) = skipped_mounts.lock() {
+            skipped.sort();
+            stats.skipped_mounts = std::mem::take(&mut *skipped);
+        }
+        let _ = tx.send(ScanEvent::Discovered(stats));
+
+        self.process_candidates(candidates, repos, tx)
+    }
+
+    /// Checks an externally supplied list of project roots, skipping discovery.
+    /// Paths that don't match any strategy are ignored.
+    pub fn scan_paths(&self, paths: &[PathBuf], tx: Sender<ScanEvent>) -> Result<Vec<CleanableProject>> {
+        let _ = tx.send(ScanEvent::Phase(ScanPhase::Discovering));
+        let candidates = paths
+            .iter()
+            .filter(|path| path.is_dir() && !is_kept(path))
+            .flat_map(|path| {
+                let _ = tx.send(ScanEvent::Scanning(path.display().to_string()));
+                self.detect(path)
+            })
+            .collect();
+
+        self.process_candidates(candidates, Vec::new(), tx)
+    }
+
+    /// Returns a candidate for every strategy that matches `path`.
+    /// A directory can be several project types at once (e.g., Maven + Node.js);
+    /// targets they share are resolved in `process_candidates`. Fallback
+    /// strategies are only tried when no other strategy matches.
+    fn detect(&self, path: &Path) -> Vec<Candidate> {
+        let matching = |fallback: bool| -> Vec<Candidate> {
+            self.strategies
+                .iter()
+                .enumerate()
+                .filter(|(_, strategy)| strategy.fallback() == fallback && strategy.detect(path))
+                .map(|(idx, _)| Candidate {
+                    root: path.to_path_buf(),
+                    strategy_idx: idx,
+                    modules: Vec::new(),
+                })
+                .collect()
+        };
+        let candidates = matching(false);
+        if candidates.is_empty() {
+            matching(true)
+        } else {
+            candidates
+        }
+    }
+
+    /// Candidates for strategies' global roots that exist on this machine
+    fn global_candidates(&self) -> Vec<Candidate> {
+        self.strategies
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, strategy)| {
+                let root = strategy.global_root()?;
+                root.is_dir().then(|| Candidate {
+                    root,
+                    strategy_idx: idx,
+                    modules: Vec::new(),
+                })
+            })
+            .collect()
+    }
+
+    /// Gives each target path claimed by several projects to a single owner:
+    /// the first claimant whose strategy recognizes the target's contents,
+    /// falling back to the first claimant. A target outside the claimants'
+    /// roots (e.g., a Cargo target-dir set for all projects) holds the build
+    /// output of all of them, so it becomes an entry of its own instead, at
+    /// medium risk or higher. That goes for a single project's target too,
+    /// since projects outside the scan may build into it, unless it belongs
+    /// to a global cache (e.g., the Android SDK's emulator devices). Targets inside another claimed target (e.g., a
+    /// web cache's `node_modules/.vite` under Node's `node_modules`) are
+    /// dropped, since deleting the outer one covers them.
+    fn resolve_shared_targets(&self, projects: &mut Vec<(Candidate, Vec<FoundTarget>)>) {
+        let mut claimants: HashMap<PathBuf, Vec<usize>> = HashMap::new();
+        for (idx, (_, targets)) in projects.iter().enumerate() {
+            for target in targets {
+                claimants.entry(target.path.clone()).or_default().push(idx);
+            }
+        }
+
+        for (path, owners) in claimants {
+            let outside = owners.iter().all(|&idx| !path.starts_with(&projects[idx].0.root));
+            let shared = owners.len() > 1
+                || (outside && self.strategies[projects[owners[0]].0.strategy_idx].global_root().is_none());
+            if !shared {
+                continue;
+            }
+
+            let winner = owners
+                .iter()
+                .copied()
+                .find(|&idx| self.strategies[projects[idx].0.strategy_idx].owns_target(&path))
+                .unwrap_or(owners[0]);
+
+            if outside {
+                let Some(mut shared) = projects[winner].1.iter().find(|t| t.path == path).cloned() else {
+                    continue;
+                };
+                shared.risk_level = shared.risk_level.max(RiskLevel::Medium);
+                shared.explanation = if owners.len() > 1 {
+                    format!("Shared by {} projects, which all rebuild once it's gone. {}", owners.len(), shared.explanation)
+                } else {
+                    format!(
+                        "Shared by every project built into it, which all rebuild once it's gone. {}",
+                        shared.explanation
+                    )
+                };
+                for &idx in &owners {
+                    projects[idx].1.retain(|t| t.path != path);
                 }
+                let entry = Candidate {
+                    root: path,
+                    strategy_idx: projects[winner].0.strategy_idx,
+                    modules: Vec::new(),
+                };
+                projects.push((entry, vec![shared]));
+                continue;
             }
+
+            for idx in owners.into_iter().filter(|&idx| idx != winner) {
+                projects[idx].1.retain(|t| t.path != path);
+            }
+        }
+
+        let claimed: HashSet<PathBuf> = projects
+            .iter()
+            .flat_map(|(_, targets)| targets.iter().map(|t| t.path.clone()))
+            .collect();
+        for (_, targets) in projects.iter_mut() {
+            targets.retain(|t| !t.path.ancestors().skip(1).any(|dir| claimed.contains(dir)));
+        }
+    }
+
+    /// Deduplicates candidates, sizes their targets, and reports each project.
+    /// Projects without targets, and `repos` without any project, are
+    /// reported as already clean instead.
+    fn process_candidates(
+        &self,
+        mut candidates: Vec<Candidate>,
+        repos: Vec<PathBuf>,
+        tx: Sender<ScanEvent>,
+    ) -> Result<Vec<CleanableProject>> {
+        if self.include_global {
+            candidates.extend(self.global_candidates());
+        }
+
+        if let Some(mount) = &self.mount {
+            candidates.retain(|c| mounts::is_on_mount(&c.root, mount));
         }
 
+        let _ = tx.send(ScanEvent::Phase(ScanPhase::Deduplicating));
+
         // 2. Deduplication Phase: Filter out nested projects
         // Sort by path length (shortest first) to ensure parents are processed before children
-        candidates.sort_by(|a, b| a.root.components().count().cmp(&b.root.components().count()));
+        candidates.sort_by_key(|c| c.root.components().count());
 
-        let mut valid_projects = Vec::new();
+        let mut valid_projects: Vec<Candidate> = Vec::new();
         let mut ignored_prefixes = Vec::new();
+        // Workspace roots: (root path, strategy index, index into valid_projects)
+        let mut workspaces: Vec<(PathBuf, usize, usize)> = Vec::new();
 
         for candidate in candidates {
             // Check if this project is inside a directory marked for deletion
@@ -91,83 +619,522 @@ impl Scanner {
             // It's a valid project
             let strategy = &self.strategies[candidate.strategy_idx];
             
-            // Mark its targets as ignored zones for future candidates
-            for target_name in strategy.targets() {
-                ignored_prefixes.push(candidate.root.join(target_name));
+            // Mark its targets and member directories as ignored zones for future candidates
+            for name in strategy.project_targets(&candidate.root) {
+                ignored_prefixes.extend(expand_target(&candidate.root, name));
+            }
+            for name in strategy.member_dirs() {
+                ignored_prefixes.push(candidate.root.join(name));
+            }
+
+            // Fold modules of a multi-module workspace into the workspace root
+            let owner = workspaces
+                .iter()
+                .find(|(root, idx, _)| *idx == candidate.strategy_idx && candidate.root.starts_with(root))
+                .map(|(_, _, owner)| *owner);
+
+            if let Some(owner) = owner {
+                valid_projects[owner].modules.push(candidate.root);
+                continue;
+            }
+
+            if strategy.is_workspace_root(&candidate.root) {
+                workspaces.push((candidate.root.clone(), candidate.strategy_idx, valid_projects.len()));
             }
 
             valid_projects.push(candidate);
         }
+        for project in &mut valid_projects {
+            project.modules.sort();
+        }
 
-        // 3. Calculation Phase: Compute sizes and notify
-        let projects: Vec<CleanableProject> = valid_projects
-            .into_par_iter()
+        // 3. Target Phase: Locate existing targets, one owner per target path
+        let mut with_targets: Vec<(Candidate, Vec<FoundTarget>)> = valid_projects
+            .into_iter()
             .map(|candidate| {
+                let strategy = self.strategies[candidate.strategy_idx].as_ref();
+                let mut found = self.find_targets(&candidate.root, strategy);
+                for module in &candidate.modules {
+                    found.extend(self.find_targets(module, strategy));
+                }
+                found.extend(self.find_nested_targets(&candidate.root, strategy));
+                found.extend(self.find_discovered_targets(&candidate.root, strategy));
+                found.sort_by(|a, b| a.path.cmp(&b.path));
+                found.dedup_by(|a, b| a.path == b.path);
+                drop_aliased_targets(&mut found);
+                self.drop_kept_versions(&mut found, strategy);
+                (candidate, found)
+            })
+            .collect();
+        self.resolve_shared_targets(&mut with_targets);
+
+        // A root is clean when none of the strategies detected there found a target
+        let mut dirty: HashSet<&Path> = HashSet::new();
+        for (candidate, found) in &with_targets {
+            if !found.is_empty() {
+                dirty.insert(&candidate.root);
+            }
+        }
+        let mut clean: Vec<PathBuf> = with_targets
+            .iter()
+            .map(|(candidate, _)| candidate.root.as_path())
+            .filter(|root| !dirty.contains(root))
+            .map(Path::to_path_buf)
+            .collect();
+        clean.extend(repos.into_iter().filter(|repo| {
+            !with_targets
+                .iter()
+                .any(|(candidate, _)| candidate.root.starts_with(repo))
+        }));
+        clean.sort();
+        clean.dedup();
+        let _ = tx.send(ScanEvent::AlreadyClean(clean));
+        with_targets.retain(|(_, found)| !found.is_empty());
+        let with_targets = self.split_versioned(with_targets);
+
+        // 4. Calculation Phase: Compute sizes and notify
+        let total = with_targets.len();
+        let sized = AtomicUsize::new(0);
+        let _ = tx.send(ScanEvent::Phase(ScanPhase::Sizing { done: 0, total }));
+        let projects: Vec<CleanableProject> = with_targets
+            .into_par_iter()
+            .map(|(candidate, found)| {
                 let strategy = &self.strategies[candidate.strategy_idx];
                 
                 // Emit scanning event for this project
                 // Clone tx for this thread
-                let _ = tx.send(ScanEvent::Scanning(format!("Analyzing: {}", candidate.root.display())));
+                let _ = tx.send(ScanEvent::Analyzing(candidate.root.clone()));
+                // Only announced when it's sure to be reported: with an age
+                // filter, the project may be left out once it's sized
+                if self.older_than.is_none() {
+                    let _ = tx.send(ScanEvent::ProjectPending(CleanableProject {
+                        root_path: candidate.root.clone(),
+                        strategy_name: strategy.name().to_string(),
+                        targets: found
+                            .iter()
+                            .map(|target| CleanTarget {
+                                path: target.path.clone(),
+                                size: 0,
+                                risk_level: target.risk_level,
+                                explanation: target.explanation.clone(),
+                                snapshot: None,
+                            })
+                            .collect(),
+                        total_size: 0,
+                        risk_level: found
+                            .iter()
+                            .map(|t| t.risk_level)
+                            .max()
+                            .unwrap_or_else(|| strategy.risk_level()),
+                        last_modified: None,
+                        last_used: None,
+                        artifacts_modified: None,
+                        rebuild: None,
+                        sizing_time: Duration::ZERO,
+                        file_count: 0,
+                        size_unknown: true,
+                        size_estimated: false,
+                        manual_only: !strategy.auto_selectable(),
+                        members: candidate.modules.clone(),
+                    }));
+                }
 
-                let targets = self.find_targets(&candidate.root, strategy.as_ref());
-                
-                // Calculate size (using jwalk internally for parallelism)
-                let total_size = self.calculate_size(&targets).unwrap_or(0);
+                // Calculate sizes (projects are already sized in parallel)
+                let started = Instant::now();
+                let mut file_count = 0;
+                let mut size_unknown = false;
+                let mut size_estimated = false;
+                let mut newest = Vec::new();
+                let targets: Vec<CleanTarget> = found
+                    .into_iter()
+                    .map(|target| {
+                        let estimate = self
+                            .estimated_size(&target.path)
+                            .or_else(|| self.cached_size(&target.path));
+                        size_estimated |= estimate.is_some();
+                        let (size, files, modified) = estimate
+                            .or_else(|| self.target_size(&target.path, &candidate.root))
+                            .unwrap_or_else(|| {
+                                size_unknown = true;
+                                (0, 0, None)
+                            });
+                        file_count += files;
+                        newest.push(modified);
+                        CleanTarget {
+                            snapshot: TargetSnapshot::take(&target.path),
+                            size,
+                            path: target.path,
+                            risk_level: target.risk_level,
+                            explanation: target.explanation,
+                        }
+                    })
+                    .collect();
+                let sizing_time = started.elapsed();
+                let total_size = targets.iter().map(|t| t.size).sum();
+                let risk_level = targets
+                    .iter()
+                    .map(|t| t.risk_level)
+                    .max()
+                    .unwrap_or_else(|| strategy.risk_level());
 
-                let project = CleanableProject {
+                let last_modified = std::iter::once(candidate.root.as_path())
+                    .chain(targets.iter().map(|t| t.path.as_path()))
+                    .filter_map(|path| path.metadata().and_then(|m| m.modified()).ok())
+                    .max();
+
+                let last_used = targets.iter().filter_map(|t| strategy.last_used(&t.path)).max();
+                let artifacts_modified = newest.into_iter().collect::<Option<Vec<_>>>().and_then(|t| t.into_iter().max());
+                let rebuild = RebuildEstimate::new(strategy.as_ref(), &candidate.root, total_size);
+
+                let mut project = CleanableProject {
+                    last_modified,
+                    last_used,
+                    artifacts_modified,
+                    rebuild: Some(rebuild),
+                    sizing_time,
+                    file_count,
+                    size_unknown,
+                    size_estimated,
+                    manual_only: !strategy.auto_selectable(),
+                    members: candidate.modules,
                     root_path: candidate.root,
                     strategy_name: strategy.name().to_string(),
                     targets,
                     total_size,
-                    risk_level: strategy.risk_level(),
+                    risk_level,
                 };
+                self.path_rules.apply(&mut project);
+
+                let done = sized.fetch_add(1, Ordering::Relaxed) + 1;
+                if !self.is_old_enough(&project) {
+                    let _ = tx.send(ScanEvent::Phase(ScanPhase::Sizing { done, total }));
+                    return None;
+                }
 
                 // Send progress update
                 let _ = tx.send(ScanEvent::ProjectFound(project.clone()));
+                let _ = tx.send(ScanEvent::Phase(ScanPhase::Sizing { done, total }));
 
-                project
+                Some(project)
             })
+            .flatten()
             .collect();
 
+        if let Some(cache) = &self.size_cache {
+            // Best effort: a failed write only costs a re-walk next time
+            let _ = cache.save();
+        }
+
         tx.send(ScanEvent::Complete)?;
         Ok(projects)
     }
 
-    /// Finds all target directories within a project
-    fn find_targets(&self, root: &Path, strategy: &dyn CleaningStrategy) -> Vec<PathBuf> {
+    /// Whether nothing in the project's targets changed within `older_than`
+    fn is_old_enough(&self, project: &CleanableProject) -> bool {
+        let Some(age) = self.older_than else {
+            return true;
+        };
+        let Some(modified) = project.artifacts_modified else {
+            return false;
+        };
+        SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|elapsed| elapsed >= age)
+    }
+
+    /// Removes the newest `kept_versions` versioned targets of each tool
+    fn drop_kept_versions(&self, found: &mut Vec<FoundTarget>, strategy: &dyn CleaningStrategy) {
+        if self.kept_versions == 0 {
+            return;
+        }
+        let mut by_tool: HashMap<String, Vec<(Vec<u64>, PathBuf)>> = HashMap::new();
+        for target in found.iter() {
+            if let Some((tool, version)) = strategy.target_version(&target.path) {
+                by_tool.entry(tool).or_default().push((version, target.path.clone()));
+            }
+        }
+        let kept: HashSet<PathBuf> = by_tool
+            .into_values()
+            .flat_map(|mut versions| {
+                versions.sort_by(|a, b| b.cmp(a));
+                versions.into_iter().take(self.kept_versions).map(|(_, path)| path)
+            })
+            .collect();
+        found.retain(|target| !kept.contains(&target.path));
+    }
+
+    /// Turns each versioned target into a project of its own, rooted at the
+    /// target, so versions are listed and picked one by one
+    fn split_versioned(&self, projects: Vec<(Candidate, Vec<FoundTarget>)>) -> Vec<(Candidate, Vec<FoundTarget>)> {
+        let mut split = Vec::with_capacity(projects.len());
+        for (candidate, found) in projects {
+            let strategy = self.strategies[candidate.strategy_idx].as_ref();
+            let (versioned, rest): (Vec<FoundTarget>, Vec<FoundTarget>) = found
+                .into_iter()
+                .partition(|target| strategy.target_version(&target.path).is_some());
+            for target in versioned {
+                let entry = Candidate {
+                    root: target.path.clone(),
+                    strategy_idx: candidate.strategy_idx,
+                    modules: Vec::new(),
+                };
+                split.push((entry, vec![target]));
+            }
+            if !rest.is_empty() {
+                split.push((candidate, rest));
+            }
+        }
+        split
+    }
+
+    /// Size, file count, and newest modification of a target, walking it
+    /// and recording the result in the cache. `None` if sizing of the
+    /// project at `root` was skipped.
+    fn target_size(&self, target: &Path, root: &Path) -> Option<(u64, u64, Option<SystemTime>)> {
+        let (size, files, newest) = match self.calculate_size(target, root) {
+            Ok(sized) => sized?,
+            Err(_) => (0, 0, None),
+        };
+        if let Some(cache) = &self.size_cache {
+            cache.insert(target, size, files, newest);
+        }
+        Some((size, files, newest))
+    }
+
+    /// Cached size of a target whose top level is unchanged, when cached
+    /// sizes are reused. An age filter needs the newest modification as it
+    /// is now, so it always walks.
+    fn cached_size(&self, target: &Path) -> Option<(u64, u64, Option<SystemTime>)> {
+        if !self.reuse_cached || self.older_than.is_some() {
+            return None;
+        }
+        self.size_cache.as_ref()?.get(target)
+    }
+
+    /// Last recorded size of a target, when sizes are being estimated
+    fn estimated_size(&self, target: &Path) -> Option<(u64, u64, Option<SystemTime>)> {
+        if !self.estimate_sizes || self.older_than.is_some() {
+            return None;
+        }
+        self.size_cache.as_ref()?.estimate(target)
+    }
+
+    /// Finds all target directories within a project, with their risk levels
+    fn find_targets(&self, root: &Path, strategy: &dyn CleaningStrategy) -> Vec<FoundTarget> {
         let mut targets = Vec::new();
+        let target_names = strategy.project_targets(root);
+
+        // Coverage reports ride along with whatever project they're in, except
+        // in self-contained caches, whose root target already includes them
+        if !target_names.contains(&".") {
+            targets.extend(
+                coverage::find(root)
+                    .into_iter()
+                    .filter(|(path, _)| !path.join(KEEP_MARKER).exists())
+                    .map(|(path, explanation)| FoundTarget {
+                        path,
+                        risk_level: RiskLevel::Low,
+                        explanation: explanation.to_string(),
+                    }),
+            );
+        }
 
-        for target_name in strategy.targets() {
-            let target_path = root.join(target_name);
-            if target_path.exists() {
-                targets.push(target_path);
+        for target_name in target_names {
+            // "." makes the project root itself the target (self-contained cache dirs)
+            for target_path in expand_target(root, target_name) {
+                if target_path.exists() && !target_path.join(KEEP_MARKER).exists() {
+                    targets.push(FoundTarget {
+                        path: target_path,
+                        risk_level: strategy.target_risk(target_name),
+                        explanation: strategy.target_explanation(target_name).to_string(),
+                    });
+                }
             }
         }
 
         targets
     }
 
-    /// Calculates the total size of all targets
-    fn calculate_size(&self, targets: &[PathBuf]) -> Result<u64> {
+    /// Finds directories named like the strategy's nested targets, or
+    /// matching its `**/` target globs, anywhere under `root`, without
+    /// descending into them, into other hidden directories, or into kept directories
+    fn find_nested_targets(&self, root: &Path, strategy: &dyn CleaningStrategy) -> Vec<FoundTarget> {
+        let project_targets = strategy.project_targets(root);
+        // (target as the strategy names it, matcher of directory names)
+        let patterns: Vec<(String, GlobMatcher)> = strategy
+            .nested_targets()
+            .into_iter()
+            .map(|name| (name, name))
+            .chain(
+                project_targets
+                    .iter()
+                    .filter_map(|target| Some((*target, target.strip_prefix("**/")?))),
+            )
+            .filter_map(|(target, name)| Some((target.to_string(), Glob::new(name).ok()?.compile_matcher())))
+            .collect();
+        if patterns.is_empty() {
+            return Vec::new();
+        }
+        // Top-level targets (e.g., a virtualenv) are cleaned whole
+        let top_level: Vec<PathBuf> = project_targets
+            .iter()
+            .flat_map(|target| expand_target(root, target))
+            .collect();
+        let matchers: Vec<GlobMatcher> = patterns.iter().map(|(_, matcher)| matcher.clone()).collect();
+
+        WalkDir::new(root)
+            .skip_hidden(false)
+            .parallelism(jwalk::Parallelism::Serial)
+            .process_read_dir(move |_depth, _path, _state, children| {
+                let kept = children
+                    .iter()
+                    .any(|child| child.as_ref().is_ok_and(|entry| entry.file_name() == KEEP_MARKER));
+                if kept {
+                    children.clear();
+                    return;
+                }
+
+                for child in children.iter_mut().flatten() {
+                    let name = child.file_name().to_string_lossy();
+                    if name.starts_with('.')
+                        || matchers.iter().any(|matcher| matcher.is_match(name.as_ref()))
+                        || top_level.contains(&child.path())
+                    {
+                        child.read_children_path = None;
+                    }
+                }
+            })
+            .into_iter()
+            .flatten()
+            .filter(|entry| entry.file_type().is_dir())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                let (target_name, _) = patterns.iter().find(|(_, matcher)| matcher.is_match(&name))?;
+                let path = entry.path();
+                if path.join(KEEP_MARKER).exists() {
+                    return None;
+                }
+                Some(FoundTarget {
+                    path,
+                    risk_level: strategy.target_risk(target_name),
+                    explanation: strategy.target_explanation(target_name).to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Targets the strategy found by walking the project, except those at or
+    /// under a keep marker inside the project
+    fn find_discovered_targets(&self, root: &Path, strategy: &dyn CleaningStrategy) -> Vec<FoundTarget> {
+        strategy
+            .discover_targets(root)
+            .into_iter()
+            .filter(|path| {
+                !path
+                    .ancestors()
+                    .take_while(|dir| *dir != root)
+                    .any(|dir| dir.join(KEEP_MARKER).exists())
+            })
+            .map(|path| {
+                let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+                FoundTarget {
+                    risk_level: strategy.target_risk(&name),
+                    explanation: strategy.target_explanation(&name).to_string(),
+                    path,
+                }
+            })
+            .collect()
+    }
+
+    /// Calculates the total size and file count of a target directory,
+    /// or `None` once sizing of the project at `root` is skipped
+    fn calculate_size(&self, target: &Path, root: &Path) -> Result<Option<(u64, u64, Option<SystemTime>)>> {
         let mut total = 0u64;
+        let mut files = 0u64;
+        // Directories count too: removing a file changes its directory's mtime
+        let mut newest: Option<SystemTime> = None;
 
-        for target in targets {
-            for entry in WalkDir::new(target).skip_hidden(false) {
-                let entry = entry?;
-                if entry.file_type().is_file() {
-                    total += entry.metadata()?.len();
+        // Walk serially: this runs inside the rayon pool, where a parallel
+        // jwalk would fail with a busy-pool error. Git internals (e.g., of a
+        // vendored checkout) aren't build output, so they aren't counted.
+        for (i, entry) in WalkDir::new(target)
+            .skip_hidden(false)
+            .parallelism(jwalk::Parallelism::Serial)
+            .process_read_dir(|_depth, _path, _state, children| skip_git(children))
+            .into_iter()
+            .enumerate()
+        {
+            if i % 1024 == 0 && self.skip_sizing.is_skipped(root) {
+                return Ok(None);
+            }
+            let entry = entry?;
+            let file_type = entry.file_type();
+            if file_type.is_file() || file_type.is_dir() {
+                let metadata = entry.metadata()?;
+                if file_type.is_file() {
+                    total += metadata.len();
+                    files += 1;
+                }
+                if let Ok(modified) = metadata.modified() {
+                    newest = newest.max(Some(modified));
                 }
             }
         }
 
-        Ok(total)
+        Ok(Some((total, files, newest)))
+    }
+}
+
+/// Stage of a scan, reported as it starts (and as sizing progresses)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScanPhase {
+    /// Walking the tree for project markers
+    Discovering,
+    /// Folding nested projects and workspaces, locating targets
+    Deduplicating,
+    /// Measuring targets; `done` of `total` projects are sized
+    Sizing { done: usize, total: usize },
+}
+
+impl ScanPhase {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Discovering => "Discovering".to_string(),
+            Self::Deduplicating => "Deduplicating".to_string(),
+            Self::Sizing { done, total } => format!("Sizing {}/{} projects", done, total),
+        }
     }
 }
 
+/// Directories checked during discovery, and how many of them (and how much
+/// of the marker checking time) were inside hidden directories
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DiscoveryStats {
+    pub directories: u64,
+    pub hidden_directories: u64,
+    pub hidden_time: Duration,
+    /// Special filesystems (procfs, GVFS, read-only FUSE, ...) left unwalked,
+    /// with what they are
+    pub skipped_mounts: Vec<(PathBuf, String)>,
+}
+
 /// Events sent during scanning
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ScanEvent {
+    /// The scan entered a new phase
+    Phase(ScanPhase),
     Scanning(String), // New variant for progress updates
+    /// Discovery finished walking the tree
+    Discovered(DiscoveryStats),
+    /// Sizing the targets of a detected project
+    Analyzing(PathBuf),
+    /// Projects (and repositories without a detected project) that have
+    /// nothing to clean, sent once before sizing starts
+    AlreadyClean(Vec<PathBuf>),
+    /// A detected project about to be sized (all sizes zero)
+    ProjectPending(CleanableProject),
     ProjectFound(CleanableProject),
     Complete,
+    /// The scan stopped with an error (or the scanner thread panicked)
+    Failed(String),
 }
@@ -0,0 +1,129 @@
+use super::CleaningStrategy;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Lockfile and manifest bytes per dependency, for guessing the dependency
+/// count of projects whose strategy can't count them
+const BYTES_PER_DEPENDENCY: u64 = 400;
+
+/// The estimate is a single figure; the range shown spans these multiples of it
+const LOW_FACTOR: f64 = 0.6;
+const HIGH_FACTOR: f64 = 1.6;
+
+/// How a strategy's targets come back once deleted, as inputs to
+/// [`RebuildEstimate::new`]
+#[derive(Debug, Clone, Copy)]
+pub enum RebuildModel {
+    /// Nothing to redo (the targets regenerate on their own, or nothing
+    /// needs them); why
+    Free(&'static str),
+    /// The project doesn't tell how long it takes; how they come back
+    Varies(&'static str),
+    /// `command` rebuilds them in a fixed time plus time per dependency and
+    /// per MB of deleted artifacts
+    Build {
+        command: &'static str,
+        base_secs: f64,
+        secs_per_dependency: f64,
+        secs_per_mb: f64,
+        /// Lockfiles or manifests, relative to the project root, whose size
+        /// stands in for the dependency count when the strategy can't count
+        manifests: &'static [&'static str],
+    },
+}
+
+/// How long a project's targets take to come back once deleted, worked out
+/// from their size, the project's dependencies, and the strategy's model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RebuildEstimate {
+    /// Nothing to redo; why
+    Free(String),
+    /// Can't be told from the project; how they come back
+    Varies(String),
+    /// About `low_secs` to `high_secs` of `command`
+    Build {
+        low_secs: u64,
+        high_secs: u64,
+        command: String,
+        /// Dependencies counted in the project's lockfile, if the strategy
+        /// could count them
+        dependencies: Option<usize>,
+    },
+}
+
+impl RebuildEstimate {
+    /// Estimates the rebuild of `artifact_size` bytes of targets of the
+    /// project at `root`
+    pub fn new(strategy: &dyn CleaningStrategy, root: &Path, artifact_size: u64) -> Self {
+        let (command, base_secs, secs_per_dependency, secs_per_mb, manifests) = match strategy.rebuild_model() {
+            RebuildModel::Free(why) => return Self::Free(why.to_string()),
+            RebuildModel::Varies(how) => return Self::Varies(how.to_string()),
+            RebuildModel::Build {
+                command,
+                base_secs,
+                secs_per_dependency,
+                secs_per_mb,
+                manifests,
+            } => (command, base_secs, secs_per_dependency, secs_per_mb, manifests),
+        };
+
+        let counted = strategy.dependency_count(root);
+        let dependencies = counted.unwrap_or_else(|| {
+            let bytes: u64 = manifests
+                .iter()
+                .filter_map(|manifest| root.join(manifest).metadata().ok())
+                .map(|metadata| metadata.len())
+                .sum();
+            (bytes / BYTES_PER_DEPENDENCY) as usize
+        });
+        let megabytes = artifact_size as f64 / (1024.0 * 1024.0);
+        let secs = base_secs + secs_per_dependency * dependencies as f64 + secs_per_mb * megabytes;
+
+        Self::Build {
+            low_secs: (secs * LOW_FACTOR) as u64,
+            high_secs: (secs * HIGH_FACTOR).ceil() as u64,
+            command: command.to_string(),
+            dependencies: counted,
+        }
+    }
+
+    /// "~2-5 mins (cargo build, 212 dependencies)", "None (recreated on save)"
+    pub fn label(&self) -> String {
+        match self {
+            Self::Free(why) => format!("None ({})", why),
+            Self::Varies(how) => format!("Varies ({})", how),
+            Self::Build {
+                low_secs,
+                high_secs,
+                command,
+                dependencies,
+            } => {
+                let how = match dependencies {
+                    Some(1) => format!("{}, 1 dependency", command),
+                    Some(n) => format!("{}, {} dependencies", command, n),
+                    None => command.clone(),
+                };
+                format!("{} ({})", duration_range(*low_secs, *high_secs), how)
+            }
+        }
+    }
+}
+
+/// "<1 min", "~3 mins", "~2-5 mins", "~1-2 hours"
+fn duration_range(low_secs: u64, high_secs: u64) -> String {
+    if high_secs < 60 {
+        return "<1 min".to_string();
+    }
+    let (low, high, unit) = if low_secs >= 2 * 60 * 60 {
+        (low_secs / 3600, high_secs.div_ceil(3600), "hours")
+    } else {
+        ((low_secs / 60).max(1), high_secs.div_ceil(60), "mins")
+    };
+    if high == 1 {
+        "~1 min".to_string()
+    } else if low >= high {
+        format!("~{} {}", high, unit)
+    } else {
+        format!("~{}-{} {}", low, high, unit)
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Identifies the state of a directory: modification time and number of entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct Stamp {
+    mtime_nanos: u128,
+    entry_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    stamp: Stamp,
+    size: u64,
+    #[serde(default)]
+    files: u64,
+    /// Newest modification of anything in the target; missing in entries
+    /// written before it was recorded
+    #[serde(default)]
+    newest: Option<SystemTime>,
+}
+
+/// Persistent cache of target sizes, keyed by path and invalidated when the
+/// target's mtime or entry count changes.
+///
+/// Stored at `<cache dir>/spektr/sizes.json`.
+#[derive(Debug, Default)]
+pub struct SizeCache {
+    entries: Mutex<HashMap<PathBuf, CacheEntry>>,
+}
+
+impl SizeCache {
+    pub fn path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("spektr").join("sizes.json"))
+    }
+
+    /// Loads the cache from disk (empty if missing or unreadable)
+    pub fn load() -> Self {
+        let entries = Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+
+        Self {
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Writes the cache to disk, dropping entries whose targets no longer exist
+    pub fn save(&self) -> anyhow::Result<()> {
+        let Some(path) = Self::path() else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut entries = self.entries.lock().map_err(|_| anyhow::anyhow!("Size cache poisoned"))?;
+        entries.retain(|target, _| target.exists());
+        std::fs::write(path, serde_json::to_string(&*entries)?)?;
+        Ok(())
+    }
+
+    /// Returns the cached size, file count, and newest modification if the
+    /// target is unchanged since it was recorded. Entries without the newest
+    /// modification are missed, so the target is walked again.
+    pub fn get(&self, target: &Path) -> Option<(u64, u64, Option<SystemTime>)> {
+        let stamp = stamp(target)?;
+        let entries = self.entries.lock().ok()?;
+        entries
+            .get(target)
+            .filter(|entry| entry.stamp == stamp && entry.newest.is_some())
+            .map(|entry| (entry.size, entry.files, entry.newest))
+    }
+
+    /// Returns the last recorded size, file count, and newest modification
+    /// without checking that the target is unchanged, for quick estimates
+    pub fn estimate(&self, target: &Path) -> Option<(u64, u64, Option<SystemTime>)> {
+        let entries = self.entries.lock().ok()?;
+        entries.get(target).map(|entry| (entry.size, entry.files, entry.newest))
+    }
+
+    /// Records the size, file count, and newest modification of a target
+    /// along with its current stamp
+    pub fn insert(&self, target: &Path, size: u64, files: u64, newest: Option<SystemTime>) {
+        let Some(stamp) = stamp(target) else {
+            return;
+        };
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(
+                target.to_path_buf(),
+                CacheEntry {
+                    stamp,
+                    size,
+                    files,
+                    newest,
+                },
+            );
+        }
+    }
+}
+
+fn stamp(target: &Path) -> Option<Stamp> {
+    let mtime = std::fs::metadata(target).ok()?.modified().ok()?;
+    let mtime_nanos = mtime.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+    let entry_count = std::fs::read_dir(target).ok()?.count();
+
+    Some(Stamp {
+        mtime_nanos,
+        entry_count,
+    })
+}
@@ -0,0 +1,90 @@
+use crate::format::{format_size, SizeUnits};
+use jwalk::WalkDir;
+use std::path::Path;
+use std::time::SystemTime;
+
+/// Size changes up to this fraction of the scanned size are expected drift
+/// (a build finishing, a cache warming up)
+const DRIFT_RATIO: f64 = 0.5;
+
+/// Size changes below this many bytes never count as drift
+const DRIFT_FLOOR: u64 = 16 * 1024 * 1024;
+
+/// Identity and shape of a target when it was sized, checked again right
+/// before deletion in case it changed since the scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetSnapshot {
+    symlink: bool,
+    /// Device and inode (Unix only)
+    file_id: Option<(u64, u64)>,
+    modified: Option<SystemTime>,
+    /// Entries directly inside a directory target
+    entry_count: usize,
+}
+
+impl TargetSnapshot {
+    pub fn take(path: &Path) -> Option<Self> {
+        let metadata = path.symlink_metadata().ok()?;
+        Some(Self {
+            symlink: metadata.file_type().is_symlink(),
+            file_id: file_id(&metadata),
+            modified: metadata.modified().ok(),
+            entry_count: entry_count(path),
+        })
+    }
+
+    /// Why the target at `path`, `size` bytes at scan time, should no longer
+    /// be deleted; `None` if it still matches (or is already gone). The target
+    /// is only walked again when its top level changed.
+    pub fn drift(&self, path: &Path, size: u64) -> Option<String> {
+        let current = Self::take(path)?;
+        if current.symlink && !self.symlink {
+            return Some("it was replaced by a symlink".to_string());
+        }
+        if current.file_id != self.file_id {
+            return Some("it was replaced by a different directory".to_string());
+        }
+        if current.modified == self.modified && current.entry_count == self.entry_count {
+            return None;
+        }
+
+        let now = current_size(path);
+        let allowed = ((size as f64 * DRIFT_RATIO) as u64).max(DRIFT_FLOOR);
+        (now.abs_diff(size) > allowed).then(|| {
+            format!(
+                "its size changed from {} to {} since the scan",
+                format_size(size, SizeUnits::Auto),
+                format_size(now, SizeUnits::Auto)
+            )
+        })
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+fn entry_count(path: &Path) -> usize {
+    std::fs::read_dir(path).map(|entries| entries.count()).unwrap_or(0)
+}
+
+/// Total size of the files in a target, which may be a single file, left
+/// out of `.git` like the scan's sizing
+fn current_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .skip_hidden(false)
+        .process_read_dir(|_depth, _path, _state, children| super::skip_git(children))
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
@@ -1,42 +1,298 @@
-use std::path::Path;
+use super::rebuild::RebuildModel;
+use crate::icons::StrategyIcons;
+use anyhow::{bail, Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-/// Risk level for deletion operations
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Risk level for deletion operations, ordered from least to most risky
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum RiskLevel {
     /// Safe to delete, can be rebuilt easily (e.g., node_modules, target)
+    #[serde(alias = "low")]
     Low,
     /// Cache directories, may slow down next build
-    #[allow(dead_code)]
+    #[serde(alias = "medium")]
     Medium,
     /// Configuration or state files, requires caution
-    #[allow(dead_code)]
+    #[serde(alias = "high")]
     High,
 }
 
+impl RiskLevel {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Low => "Low",
+            Self::Medium => "Medium",
+            Self::High => "High",
+        }
+    }
+
+    /// Short marker shown next to risky projects (empty for low risk)
+    pub fn badge(&self) -> &str {
+        match self {
+            Self::Low => "",
+            Self::Medium => "⚠",
+            Self::High => "⛔",
+        }
+    }
+
+    /// Generic consequence of deleting a target at this risk level
+    pub fn explanation(&self) -> &'static str {
+        match self {
+            Self::Low => "Build output; the next build regenerates it",
+            Self::Medium => "Cache; the next build re-downloads or recomputes it and runs slower",
+            Self::High => "May hold state or configuration that can't be regenerated",
+        }
+    }
+}
+
+/// A toolchain's own clean command, run instead of deleting targets when
+/// native cleaning is on
+#[derive(Debug, Clone)]
+pub struct CleanCommand {
+    /// Program and arguments, run in the project root
+    pub argv: Vec<String>,
+    /// Targets the command removes, relative to the project root (and to
+    /// each workspace member) or absolute. It only runs when every one of them that
+    /// exists is being cleaned, so it never removes more than was chosen.
+    pub removes: Vec<String>,
+    /// How the command is shown when `argv` wraps it (e.g., in a shell that loads nvm)
+    pub label: Option<String>,
+}
+
+impl CleanCommand {
+    fn new(argv: &[&str], removes: &[&str]) -> Self {
+        Self {
+            argv: argv.iter().map(|arg| arg.to_string()).collect(),
+            removes: removes.iter().map(|target| target.to_string()).collect(),
+            label: None,
+        }
+    }
+
+    /// Runs the project's wrapper script (e.g., `./gradlew`) when it has one,
+    /// and `tool` from the PATH otherwise
+    fn wrapped(root: &Path, wrapper: &str, tool: &str, args: &[&str], removes: &[&str]) -> Self {
+        let wrapper = if cfg!(windows) { format!("{}.bat", wrapper) } else { wrapper.to_string() };
+        let program = if root.join(&wrapper).is_file() {
+            root.join(&wrapper).to_string_lossy().into_owned()
+        } else {
+            tool.to_string()
+        };
+        let mut command = Self::new(args, removes);
+        command.argv.insert(0, program);
+        command
+    }
+
+    /// Also counts every directory named `name` anywhere in the project as
+    /// removed, for build tools whose clean reaches into all subprojects
+    /// (including ones spektr doesn't detect, or that hold a keep marker)
+    fn with_nested(mut self, root: &Path, name: &str) -> Self {
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            let Ok(entries) = std::fs::read_dir(&dir) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+                if !is_dir || file_name.starts_with('.') || file_name == "node_modules" {
+                    continue;
+                }
+                if file_name == name {
+                    if let Ok(relative) = entry.path().strip_prefix(root) {
+                        self.removes.push(relative.to_string_lossy().into_owned());
+                    }
+                } else {
+                    pending.push(entry.path());
+                }
+            }
+        }
+        self.removes.sort();
+        self.removes.dedup();
+        self
+    }
+
+    /// The command as it would be typed
+    pub fn display(&self) -> String {
+        self.label.clone().unwrap_or_else(|| self.argv.join(" "))
+    }
+}
+
 /// Trait for cleaning strategies targeting specific project types
 pub trait CleaningStrategy: Send + Sync {
     /// Name of the strategy (e.g., "Node.js", "Rust")
     fn name(&self) -> &str;
 
+    /// Icons shown next to this strategy's projects
+    fn icons(&self) -> StrategyIcons;
+
     /// Detects if a given path represents a project of this type
     /// Usually checks for marker files like package.json, Cargo.toml
     fn detect(&self, path: &Path) -> bool;
 
-    /// Returns the list of target directories to clean
+    /// Returns the list of target directories to clean, relative to the
+    /// project root. Globs match every existing path (e.g., `cmake-build-*`),
+    /// and a leading `**/` matches at any depth (e.g., `**/__pycache__`).
     fn targets(&self) -> Vec<&str>;
 
-    /// Risk level for deleting this project's artifacts
+    /// Directory names cleaned at any depth under the project root, for
+    /// artifacts that sit next to sources in every folder (e.g., `.ipynb_checkpoints`)
+    fn nested_targets(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Targets found by walking the project, for artifacts whose location
+    /// depends on its layout (e.g., `bin/` and `obj/` next to every .NET
+    /// project file). Their risk and explanation come from `target_risk` and
+    /// `target_explanation` with the directory name.
+    fn discover_targets(&self, _root: &Path) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Targets that apply to a specific project root (a subset of `targets()`).
+    /// Override for targets that depend on the project's setup, e.g. framework caches.
+    fn project_targets(&self, _root: &Path) -> Vec<&str> {
+        self.targets()
+    }
+
+    /// Checks whether an existing target directory was produced by this
+    /// strategy's tooling. Used to pick an owner when several strategies
+    /// detected at the same root share a target name (e.g., `target`).
+    fn owns_target(&self, _target_path: &Path) -> bool {
+        true
+    }
+
+    /// Tool-wide location outside any project (e.g., `~/Library/Developer`)
+    /// whose `targets()` are offered when global locations are enabled.
+    /// Strategies with a global root usually don't detect in-tree projects.
+    fn global_root(&self) -> Option<PathBuf> {
+        None
+    }
+
+    /// Subdirectories holding parts of this project that other strategies
+    /// detect as projects of their own (e.g., a Tauri app's `src-tauri` crate).
+    /// Nothing under them is reported separately.
+    fn member_dirs(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    /// Detects if a given path is the root of a multi-module workspace.
+    /// Nested projects of the same type are folded into the root instead of
+    /// being reported separately.
+    fn is_workspace_root(&self, _path: &Path) -> bool {
+        false
+    }
+
+    /// Default risk level for deleting this project's artifacts
     fn risk_level(&self) -> RiskLevel;
 
-    /// Optional: estimate rebuild time as a string
-    #[allow(dead_code)]
-    fn rebuild_estimate(&self) -> &str {
-        "~1-3 mins"
+    /// Risk level for deleting a specific target (one of `targets()`)
+    fn target_risk(&self, _target: &str) -> RiskLevel {
+        self.risk_level()
+    }
+
+    /// Consequence of deleting a specific target, shown in the details pane.
+    /// Defaults to a generic explanation of the target's risk level.
+    fn target_explanation(&self, target: &str) -> &str {
+        self.target_risk(target).explanation()
+    }
+
+    /// Environment variables that move this strategy's build output out of
+    /// the project, each with the subdirectory of a scratch directory it
+    /// should point to. Used by `spektr relocate`.
+    fn relocation_vars(&self) -> Vec<(&str, &str)> {
+        Vec::new()
+    }
+
+    /// Tool and version a target holds, for caches with one directory per
+    /// installed version (e.g., `("ms-playwright/chromium-*", [1091])`). Each
+    /// versioned target is listed as an entry of its own, so versions can be
+    /// picked one by one, and `--keep-latest N` leaves the newest N of each tool.
+    fn target_version(&self, _target: &Path) -> Option<(String, Vec<u64>)> {
+        None
+    }
+
+    /// When a target was last used, for installs whose age matters less
+    /// than whether anything still runs them (e.g., a toolchain's compiler)
+    fn last_used(&self, _target: &Path) -> Option<SystemTime> {
+        None
+    }
+
+    /// The toolchain's clean command for a project (e.g., `cargo clean`),
+    /// preferred over deleting its targets with `--native-clean`
+    fn clean_command(&self, _root: &Path) -> Option<CleanCommand> {
+        None
+    }
+
+    /// Whether the clean command runs even without `--native-clean`, for
+    /// installs their tool keeps track of (e.g., rustup toolchains)
+    fn prefers_clean_command(&self) -> bool {
+        false
+    }
+
+    /// Whether the strategy runs without being listed in `enabled_strategies`.
+    /// Opt-in strategies match directories that aren't build output.
+    fn enabled_by_default(&self) -> bool {
+        true
+    }
+
+    /// Whether the strategy only detects directories no other strategy
+    /// detects, for guesses that must not compete with a recognized project
+    fn fallback(&self) -> bool {
+        false
+    }
+
+    /// Whether this strategy's projects may be selected or cleaned without
+    /// the user picking them: by path rules, retention plans, or unattended cleaning
+    fn auto_selectable(&self) -> bool {
+        true
+    }
+
+    /// How the targets come back once deleted, for rebuild estimates
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "rebuild",
+            base_secs: 60.0,
+            secs_per_dependency: 0.0,
+            secs_per_mb: 0.1,
+            manifests: &[],
+        }
+    }
+
+    /// Number of dependencies the project at `root` pins (e.g., packages in
+    /// its lockfile), for rebuild estimates. Without a count, the size of the
+    /// rebuild model's manifests stands in for it.
+    fn dependency_count(&self, _root: &Path) -> Option<usize> {
+        None
     }
 }
 
 // === Node.js Strategy ===
 
+/// Lines of the file at `path` that `counts` accepts, `None` if it can't be read
+fn count_lines(path: &Path, counts: impl Fn(&str) -> bool) -> Option<usize> {
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(content.lines().filter(|line| counts(line)).count())
+}
+
+/// Installed packages listed in `package-lock.json` (lockfile version 2 and
+/// later) or `yarn.lock`
+fn node_dependency_count(root: &Path) -> Option<usize> {
+    count_lines(&root.join("package-lock.json"), |line| {
+        line.trim_start().starts_with("\"node_modules/")
+    })
+    .filter(|&packages| packages > 0)
+    .or_else(|| {
+        count_lines(&root.join("yarn.lock"), |line| {
+            !line.starts_with([' ', '#']) && line.ends_with(':')
+        })
+    })
+}
+
 pub struct NodeStrategy;
 
 impl CleaningStrategy for NodeStrategy {
@@ -44,20 +300,204 @@ impl CleaningStrategy for NodeStrategy {
         "Node.js"
     }
 
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "📦",
+            ascii: "N",
+            nerd: "\u{e718}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("package.json").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["node_modules", ".next", "dist", "build", ".angular/cache", "tmp"]
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        let mut targets = vec!["node_modules", ".next", "dist", "build"];
+        // Angular CLI workspaces keep a persistent build cache; `dist/` is already covered
+        if root.join("angular.json").exists() {
+            targets.push(".angular/cache");
+        }
+        // Ember CLI stages builds in `tmp/`
+        if root.join("ember-cli-build.js").exists() {
+            targets.push("tmp");
+        }
+        targets
+    }
+
+    fn is_workspace_root(&self, path: &Path) -> bool {
+        if path.join("pnpm-workspace.yaml").exists() || path.join("lerna.json").exists() {
+            return true;
+        }
+
+        // npm/yarn workspaces are declared in package.json
+        std::fs::read_to_string(path.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .is_some_and(|manifest| manifest.get("workspaces").is_some())
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "node_modules" => "Installed packages; the next `npm install` re-downloads them",
+            ".angular/cache" => "Angular CLI build cache; the next `ng build` or `ng serve` starts cold",
+            "tmp" => "Ember CLI build staging; recreated by the next `ember build` or `ember serve`",
+            _ => "Bundler output; the next build regenerates it",
+        }
+    }
+
+    fn relocation_vars(&self) -> Vec<(&str, &str)> {
+        // node_modules can't move, but npm's download cache can
+        vec![("npm_config_cache", "npm-cache")]
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "npm install",
+            base_secs: 5.0,
+            secs_per_dependency: 0.08,
+            secs_per_mb: 0.1,
+            manifests: &["package-lock.json", "yarn.lock", "pnpm-lock.yaml", "bun.lockb"],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        node_dependency_count(root)
+    }
+}
+
+// === Web Cache Strategy ===
+
+/// Cache directories (and files) left by JavaScript build tooling
+const WEB_CACHE_TARGETS: [&str; 5] = [".turbo", ".parcel-cache", "node_modules/.vite", ".cache", ".eslintcache"];
+
+/// Bundler, linter, and task-runner caches in JavaScript projects, offered
+/// separately from Node.js so they can be cleared without touching `node_modules`
+pub struct WebCacheStrategy;
+
+impl CleaningStrategy for WebCacheStrategy {
+    fn name(&self) -> &str {
+        "Web Cache"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "⚡",
+            ascii: "W",
+            nerd: "\u{f0e7}",
+        }
+    }
+
     fn detect(&self, path: &Path) -> bool {
+        // Only report projects that actually have a cache
         path.join("package.json").exists()
+            && WEB_CACHE_TARGETS.iter().any(|target| path.join(target).exists())
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        WEB_CACHE_TARGETS.to_vec()
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".turbo" => "Turborepo task cache; the next run re-executes tasks instead of replaying them",
+            ".parcel-cache" => "Parcel build cache; the next build starts cold",
+            "node_modules/.vite" => "Vite pre-bundled dependencies; re-optimized on the next dev server start",
+            ".eslintcache" => "ESLint results cache (a file); the next lint checks every file",
+            _ => "Generic tool cache (Babel, Gatsby, webpack loaders, ...); rebuilt on demand",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Free("slower first build")
+    }
+}
+
+// === Electron Strategy ===
+
+/// Standalone electron-builder and Electron Forge config files
+const ELECTRON_CONFIGS: [&str; 8] = [
+    "electron-builder.yml",
+    "electron-builder.yaml",
+    "electron-builder.json",
+    "electron-builder.json5",
+    "electron-builder.config.js",
+    "forge.config.js",
+    "forge.config.cjs",
+    "forge.config.ts",
+];
+
+/// Packaged Electron apps: every build leaves installers and unpacked apps
+/// of several hundred MB next to the JavaScript project
+pub struct ElectronStrategy;
+
+impl CleaningStrategy for ElectronStrategy {
+    fn name(&self) -> &str {
+        "Electron"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "⚛",
+            ascii: "L",
+            nerd: "\u{f0768}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        if ELECTRON_CONFIGS.iter().any(|config| path.join(config).exists()) {
+            return true;
+        }
+
+        // Both tools also accept their config inside package.json
+        std::fs::read_to_string(path.join("package.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .is_some_and(|manifest| {
+                ["dependencies", "devDependencies"].iter().any(|section| {
+                    manifest
+                        .get(section)
+                        .is_some_and(|deps| deps.get("electron-builder").is_some() || deps.get("@electron-forge/cli").is_some())
+                })
+            })
     }
 
     fn targets(&self) -> Vec<&str> {
-        vec!["node_modules", ".next", "dist", "build"]
+        vec!["out", "release", "dist_electron"]
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "out" => "Electron Forge packages and installers; rebuilt by `electron-forge make`",
+            "dist_electron" => "Vue CLI Electron builds; rebuilt by `vue-cli-service electron:build`",
+            _ => "electron-builder installers and unpacked apps; rebuilt by the next packaging run",
+        }
     }
 
     fn risk_level(&self) -> RiskLevel {
         RiskLevel::Low
     }
 
-    fn rebuild_estimate(&self) -> &str {
-        "~1-2 mins (npm install)"
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "electron-builder / electron-forge make",
+            base_secs: 90.0,
+            secs_per_dependency: 0.05,
+            secs_per_mb: 0.5,
+            manifests: &["package-lock.json", "yarn.lock", "pnpm-lock.yaml"],
+        }
     }
 }
 
@@ -65,11 +505,72 @@ impl CleaningStrategy for NodeStrategy {
 
 pub struct RustStrategy;
 
+/// `base` joined with `path`, with `.` and `..` resolved without touching
+/// the filesystem
+fn join_lexically(base: &Path, path: impl AsRef<Path>) -> PathBuf {
+    let mut joined = PathBuf::new();
+    for component in base.join(path).components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                joined.pop();
+            }
+            other => joined.push(other),
+        }
+    }
+    joined
+}
+
+impl RustStrategy {
+    /// Build output directory configured for a project, as Cargo resolves it:
+    /// `CARGO_TARGET_DIR` (or `CARGO_BUILD_TARGET_DIR`), then `build.target-dir`
+    /// in the nearest `.cargo/config.toml` of the project or its parents, then
+    /// the one in `CARGO_HOME`
+    fn configured_target_dir(root: &Path) -> Option<PathBuf> {
+        for var in ["CARGO_TARGET_DIR", "CARGO_BUILD_TARGET_DIR"] {
+            if let Some(dir) = std::env::var_os(var).filter(|dir| !dir.is_empty()) {
+                // Relative to the working directory, like cargo
+                return Some(join_lexically(&std::env::current_dir().ok()?, dir));
+            }
+        }
+
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+        let config_dirs = root.ancestors().map(|dir| dir.join(".cargo")).chain(cargo_home);
+        for config_dir in config_dirs {
+            // `config` is the name older Cargo versions read
+            for file in ["config.toml", "config"] {
+                let Ok(content) = std::fs::read_to_string(config_dir.join(file)) else {
+                    continue;
+                };
+                let target_dir = content
+                    .parse::<toml::Table>()
+                    .ok()
+                    .and_then(|config| Some(config.get("build")?.get("target-dir")?.as_str()?.to_string()));
+                if let Some(dir) = target_dir {
+                    // Relative to the directory holding `.cargo`
+                    return Some(join_lexically(config_dir.parent()?, dir));
+                }
+            }
+        }
+        None
+    }
+}
+
 impl CleaningStrategy for RustStrategy {
     fn name(&self) -> &str {
         "Rust"
     }
 
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🦀",
+            ascii: "R",
+            nerd: "\u{e7a8}",
+        }
+    }
+
     fn detect(&self, path: &Path) -> bool {
         path.join("Cargo.toml").exists()
     }
@@ -78,12 +579,64 @@ impl CleaningStrategy for RustStrategy {
         vec!["target"]
     }
 
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        // A `target/` from before the setting is still offered as a target
+        Self::configured_target_dir(root)
+            .filter(|dir| dir.is_dir() && *dir != root.join("target") && !root.starts_with(dir))
+            .into_iter()
+            .collect()
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        // Cargo tags its target directory and records the toolchain it used
+        target_path.join("CACHEDIR.TAG").exists() || target_path.join(".rustc_info.json").exists()
+    }
+
+    fn is_workspace_root(&self, path: &Path) -> bool {
+        // Members of a Cargo workspace share the root's `target`
+        std::fs::read_to_string(path.join("Cargo.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .is_some_and(|manifest| manifest.contains_key("workspace"))
+    }
+
     fn risk_level(&self) -> RiskLevel {
         RiskLevel::Low
     }
 
-    fn rebuild_estimate(&self) -> &str {
-        "~2-5 mins (cargo build)"
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Compiled crates and incremental state; the next `cargo build` recompiles everything"
+    }
+
+    fn relocation_vars(&self) -> Vec<(&str, &str)> {
+        vec![("CARGO_TARGET_DIR", "target")]
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        // Not for a shared target-dir listed on its own
+        if !root.join("Cargo.toml").is_file() {
+            return None;
+        }
+        let mut command = CleanCommand::new(&["cargo", "clean"], &["target"]);
+        // `cargo clean` removes the configured directory, not `target/`
+        if let Some(dir) = Self::configured_target_dir(root) {
+            command.removes.push(dir.to_string_lossy().into_owned());
+        }
+        Some(command)
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "cargo build",
+            base_secs: 10.0,
+            secs_per_dependency: 0.8,
+            secs_per_mb: 0.05,
+            manifests: &["Cargo.lock"],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        count_lines(&root.join("Cargo.lock"), |line| line == "[[package]]")
     }
 }
 
@@ -96,6 +649,14 @@ impl CleaningStrategy for FlutterStrategy {
         "Flutter"
     }
 
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "💙",
+            ascii: "F",
+            nerd: "\u{e798}",
+        }
+    }
+
     fn detect(&self, path: &Path) -> bool {
         path.join("pubspec.yaml").exists()
     }
@@ -108,13 +669,57 @@ impl CleaningStrategy for FlutterStrategy {
         RiskLevel::Low
     }
 
-    fn rebuild_estimate(&self) -> &str {
-        "~1-3 mins (flutter pub get + build)"
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".dart_tool" => "Package config and tool caches; restored by `flutter pub get`",
+            _ => "Build output; the next `flutter build` regenerates it",
+        }
+    }
+
+    fn clean_command(&self, _root: &Path) -> Option<CleanCommand> {
+        Some(CleanCommand::new(&["flutter", "clean"], &["build", ".dart_tool"]))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "flutter pub get + build",
+            base_secs: 30.0,
+            secs_per_dependency: 0.5,
+            secs_per_mb: 0.2,
+            manifests: &["pubspec.lock"],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        count_lines(&root.join("pubspec.lock"), |line| line.trim_start().starts_with("dependency:"))
     }
 }
 
 // === Android Strategy ===
 
+/// Gradle build or settings script in `path`
+fn has_gradle_build(path: &Path) -> bool {
+    ["build.gradle", "build.gradle.kts", "settings.gradle", "settings.gradle.kts"]
+        .iter()
+        .any(|file| path.join(file).exists())
+}
+
+/// Whether a Gradle project builds an Android app or library: it has an
+/// `AndroidManifest.xml` or its build script applies an Android plugin
+fn is_android_project(path: &Path) -> bool {
+    if path.join("src/main/AndroidManifest.xml").exists()
+        || path.join("app/src/main/AndroidManifest.xml").exists()
+    {
+        return true;
+    }
+
+    ["build.gradle", "build.gradle.kts"].iter().any(|file| {
+        std::fs::read_to_string(path.join(file))
+            .map(|script| script.contains("com.android.") || script.contains("plugins.android."))
+            .unwrap_or(false)
+    })
+}
+
 pub struct AndroidStrategy;
 
 impl CleaningStrategy for AndroidStrategy {
@@ -122,29 +727,2875 @@ impl CleaningStrategy for AndroidStrategy {
         "Android"
     }
 
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🤖",
+            ascii: "A",
+            nerd: "\u{e70e}",
+        }
+    }
+
     fn detect(&self, path: &Path) -> bool {
-        path.join("build.gradle").exists() || path.join("build.gradle.kts").exists()
+        has_gradle_build(path) && is_android_project(path)
     }
 
     fn targets(&self) -> Vec<&str> {
         vec!["app/build", "build", ".gradle"]
     }
 
+    fn is_workspace_root(&self, path: &Path) -> bool {
+        path.join("settings.gradle").exists() || path.join("settings.gradle.kts").exists()
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Project-local Gradle cache: next build re-downloads and reconfigures
+            ".gradle" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
     fn risk_level(&self) -> RiskLevel {
         RiskLevel::Low
     }
 
-    fn rebuild_estimate(&self) -> &str {
-        "~3-10 mins (gradle build)"
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".gradle" => "Deleting .gradle clears the project-local Gradle cache; next build re-downloads dependencies",
+            _ => "Compiled classes and packaged outputs; the next Gradle build regenerates them",
+        }
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        // Cleans every module, not just `app`
+        Some(CleanCommand::wrapped(root, "gradlew", "gradle", &["clean"], &[]).with_nested(root, "build"))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "gradle build",
+            base_secs: 120.0,
+            secs_per_dependency: 1.5,
+            secs_per_mb: 0.3,
+            manifests: &["build.gradle", "build.gradle.kts", "app/build.gradle", "app/build.gradle.kts"],
+        }
     }
 }
 
-/// Factory function to create all built-in strategies
-pub fn default_strategies() -> Vec<Box<dyn CleaningStrategy>> {
-    vec![
-        Box::new(NodeStrategy),
-        Box::new(RustStrategy),
-        Box::new(FlutterStrategy),
-        Box::new(AndroidStrategy),
-    ]
+// === Maven Strategy ===
+
+pub struct MavenStrategy;
+
+impl CleaningStrategy for MavenStrategy {
+    fn name(&self) -> &str {
+        "Maven"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "☕",
+            ascii: "M",
+            nerd: "\u{e738}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("pom.xml").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["target"]
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        ["classes", "test-classes", "maven-status", "maven-archiver"]
+            .iter()
+            .any(|marker| target_path.join(marker).exists())
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Compiled classes and packaged artifacts; the next `mvn package` regenerates them"
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        // Cleans every module listed in the POM
+        Some(CleanCommand::wrapped(root, "mvnw", "mvn", &["clean"], &[]).with_nested(root, "target"))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "mvn package",
+            base_secs: 20.0,
+            secs_per_dependency: 1.5,
+            secs_per_mb: 0.3,
+            manifests: &[],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        count_lines(&root.join("pom.xml"), |line| line.trim() == "<dependency>")
+    }
+}
+
+/// Generic JVM Gradle projects (Kotlin/Java libraries, Spring apps), plus
+/// with `--global` the distributions the Gradle wrapper downloaded
+pub struct GradleStrategy;
+
+impl GradleStrategy {
+    /// One directory per distribution (e.g., `gradle-8.5-bin`) under the global root
+    const WRAPPER_DISTS: &'static str = "wrapper/dists";
+}
+
+impl CleaningStrategy for GradleStrategy {
+    fn name(&self) -> &str {
+        "Gradle"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🐘",
+            ascii: "G",
+            nerd: "\u{e660}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        has_gradle_build(path) && !is_android_project(path)
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["build", ".gradle"]
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        if self.global_root().is_some_and(|global| global == root) {
+            // Wrapper distributions are discovered one version at a time
+            Vec::new()
+        } else {
+            self.targets()
+        }
+    }
+
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        if self.global_root().is_none_or(|global| global != root) {
+            return Vec::new();
+        }
+        entries_at_depth(&root.join(Self::WRAPPER_DISTS), 1)
+            .into_iter()
+            .filter(|entry| self.target_version(entry).is_some())
+            .collect()
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        std::env::var_os("GRADLE_USER_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".gradle")))
+    }
+
+    fn is_workspace_root(&self, path: &Path) -> bool {
+        path.join("settings.gradle").exists() || path.join("settings.gradle.kts").exists()
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        ["classes", "libs", "kotlin", "tmp"]
+            .iter()
+            .any(|marker| target_path.join(marker).exists())
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Project-local Gradle cache: next build re-downloads and reconfigures
+            ".gradle" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".gradle" => "Deleting .gradle clears the project-local Gradle cache; next build re-downloads dependencies",
+            name if name.starts_with("gradle-") => {
+                "Gradle distribution downloaded by the wrapper; projects pinned to this version download it again"
+            }
+            _ => "Compiled classes and packaged outputs; the next Gradle build regenerates them",
+        }
+    }
+
+    fn target_version(&self, target: &Path) -> Option<(String, Vec<u64>)> {
+        if !target.parent()?.ends_with(Self::WRAPPER_DISTS) {
+            return None;
+        }
+        split_version(&target.file_name()?.to_string_lossy())
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        // Cleans every subproject
+        Some(CleanCommand::wrapped(root, "gradlew", "gradle", &["clean"], &[]).with_nested(root, "build"))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "gradle build",
+            base_secs: 40.0,
+            secs_per_dependency: 1.5,
+            secs_per_mb: 0.3,
+            manifests: &["build.gradle", "build.gradle.kts", "gradle.lockfile"],
+        }
+    }
+}
+
+pub struct ElixirStrategy;
+
+impl CleaningStrategy for ElixirStrategy {
+    fn name(&self) -> &str {
+        "Elixir"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "💧",
+            ascii: "E",
+            nerd: "\u{e62d}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("mix.exs").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["_build", "deps"]
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Fetched dependency sources: may include local patches, and
+            // re-fetching needs network access
+            "deps" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "deps" => "Fetched dependency sources; `mix deps.get` re-downloads them, losing any local edits",
+            _ => "Compiled BEAM files; the next `mix compile` regenerates them",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn relocation_vars(&self) -> Vec<(&str, &str)> {
+        vec![("MIX_BUILD_ROOT", "_build")]
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "mix deps.get && mix compile",
+            base_secs: 15.0,
+            secs_per_dependency: 3.0,
+            secs_per_mb: 0.2,
+            manifests: &[],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        count_lines(&root.join("mix.lock"), |line| line.starts_with("  \""))
+    }
+}
+
+pub struct RubyStrategy;
+
+impl CleaningStrategy for RubyStrategy {
+    fn name(&self) -> &str {
+        "Ruby"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "💎",
+            ascii: "B",
+            nerd: "\u{e739}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("Gemfile").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["vendor/bundle", ".bundle", "tmp/cache"]
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Holds the project's Bundler config (install path, groups, mirrors)
+            ".bundle" => RiskLevel::High,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "vendor/bundle" => "Installed gems; the next `bundle install` re-downloads them",
+            ".bundle" => "Project-local Bundler config; settings like the install path or excluded groups are lost",
+            _ => "Application cache (assets, bootsnap); rebuilt on the next run",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "bundle install",
+            base_secs: 10.0,
+            secs_per_dependency: 1.0,
+            secs_per_mb: 0.1,
+            manifests: &[],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        // Gems are listed 4 spaces in under `specs:`, their own dependencies 6
+        count_lines(&root.join("Gemfile.lock"), |line| {
+            line.starts_with("    ") && !line.starts_with("     ")
+        })
+    }
+}
+
+/// Files that mark a Python project
+const PYTHON_MARKERS: [&str; 7] = [
+    "pyproject.toml",
+    "setup.py",
+    "setup.cfg",
+    "requirements.txt",
+    "Pipfile",
+    "poetry.lock",
+    "environment.yml",
+];
+
+/// Directory names commonly used for a project's virtualenv or Conda environment
+const PYTHON_ENV_DIRS: [&str; 4] = [".venv", "venv", "env", "conda-env"];
+
+/// Whether `dir` is a virtualenv or Conda environment, not a source folder
+/// that happens to be named `env`
+fn is_python_env(dir: &Path) -> bool {
+    dir.join("pyvenv.cfg").exists()
+        || dir.join("conda-meta").is_dir()
+        || dir.join("bin/activate").exists()
+        || dir.join("Scripts/activate").exists()
+}
+
+/// Python projects: in-project virtualenvs and Conda environments, plus
+/// bytecode caches throughout the source tree
+pub struct PythonStrategy;
+
+impl CleaningStrategy for PythonStrategy {
+    fn name(&self) -> &str {
+        "Python"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🐍",
+            ascii: "Y",
+            nerd: "\u{e73c}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        PYTHON_MARKERS.iter().any(|marker| path.join(marker).exists())
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        PYTHON_ENV_DIRS.to_vec()
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        PYTHON_ENV_DIRS
+            .into_iter()
+            .filter(|dir| is_python_env(&root.join(dir)))
+            .collect()
+    }
+
+    fn nested_targets(&self) -> Vec<&str> {
+        vec!["__pycache__"]
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            "__pycache__" => RiskLevel::Low,
+            // Recreating an environment needs the right interpreter and
+            // network access, and loses packages installed by hand
+            _ => RiskLevel::Medium,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "__pycache__" => "Compiled bytecode; Python regenerates it on the next import",
+            "conda-env" => "Conda environment; `conda env create` reinstalls it, losing packages installed by hand",
+            _ => "Virtual environment; reinstalled from the lock or requirements file, losing packages installed by hand",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "pip install / poetry install",
+            base_secs: 10.0,
+            secs_per_dependency: 1.5,
+            secs_per_mb: 0.05,
+            manifests: &["Pipfile.lock", "pdm.lock"],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        ["poetry.lock", "uv.lock"]
+            .iter()
+            .find_map(|lockfile| count_lines(&root.join(lockfile), |line| line == "[[package]]"))
+            .or_else(|| {
+                count_lines(&root.join("requirements.txt"), |line| {
+                    let line = line.trim();
+                    !line.is_empty() && !line.starts_with('#') && !line.starts_with('-')
+                })
+            })
+    }
+}
+
+pub struct ComposerStrategy;
+
+impl CleaningStrategy for ComposerStrategy {
+    fn name(&self) -> &str {
+        "PHP"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🎼",
+            ascii: "P",
+            nerd: "\u{e73d}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("composer.json").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["vendor", "storage/framework/cache", "var/cache"]
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        let mut targets = vec!["vendor"];
+        // Laravel
+        if root.join("artisan").exists() {
+            targets.push("storage/framework/cache");
+        }
+        // Symfony
+        if root.join("bin/console").exists() || root.join("symfony.lock").exists() {
+            targets.push("var/cache");
+        }
+        targets
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "vendor" => "Installed packages; the next `composer install` re-downloads them",
+            _ => "Framework cache; rebuilt on the next request or cache warmup",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "composer install",
+            base_secs: 5.0,
+            secs_per_dependency: 0.5,
+            secs_per_mb: 0.1,
+            manifests: &["composer.lock"],
+        }
+    }
+}
+
+/// Xcode's per-user build products and simulator caches, which live under
+/// `~/Library/Developer` rather than inside each project
+pub struct XcodeStrategy;
+
+impl CleaningStrategy for XcodeStrategy {
+    fn name(&self) -> &str {
+        "Xcode"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🔨",
+            ascii: "X",
+            nerd: "\u{e711}",
+        }
+    }
+
+    fn detect(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        // Archives are offered by the Mobile strategy
+        vec!["Xcode/DerivedData", "Xcode/iOS DeviceSupport", "CoreSimulator/Caches"]
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join("Library").join("Developer"))
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Symbol files are re-copied from the device on next connect, which is slow
+            "Xcode/iOS DeviceSupport" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "Xcode/DerivedData" => "Build products and indexes of all Xcode projects; each rebuilds on next open",
+            "Xcode/iOS DeviceSupport" => "Device symbol files; Xcode re-copies them when a device next connects",
+            _ => "Simulator caches; rebuilt when simulators next boot",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "xcodebuild",
+            base_secs: 60.0,
+            secs_per_dependency: 0.0,
+            secs_per_mb: 0.3,
+            manifests: &[],
+        }
+    }
+}
+
+/// iOS and macOS dependency caches: CocoaPods, Carthage, and SwiftPM
+/// downloads plus Xcode archives under `~/Library` (with `--global`), and
+/// Carthage's `Carthage/Build` in each project
+pub struct MobileStrategy;
+
+impl MobileStrategy {
+    /// Build output inside a Carthage project
+    const PROJECT_TARGETS: [&'static str; 1] = ["Carthage/Build"];
+
+    /// Per-user caches and archives under `~/Library`
+    const GLOBAL_TARGETS: [&'static str; 4] = [
+        "Caches/CocoaPods",
+        "Caches/org.carthage.CarthageKit",
+        "Caches/org.swift.swiftpm",
+        "Developer/Xcode/Archives",
+    ];
+}
+
+impl CleaningStrategy for MobileStrategy {
+    fn name(&self) -> &str {
+        "Mobile"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "📱",
+            ascii: "m",
+            nerd: "\u{f10b}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("Cartfile").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        let mut targets = Self::PROJECT_TARGETS.to_vec();
+        targets.extend(Self::GLOBAL_TARGETS);
+        targets
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        if self.global_root().is_some_and(|global| global == root) {
+            Self::GLOBAL_TARGETS.to_vec()
+        } else {
+            Self::PROJECT_TARGETS.to_vec()
+        }
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join("Library"))
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            "Carthage/Build" => RiskLevel::Low,
+            // Shipped builds and their dSYMs, needed to symbolicate crash reports
+            "Developer/Xcode/Archives" => RiskLevel::High,
+            _ => RiskLevel::Medium,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "Carthage/Build" => "Prebuilt frameworks; `carthage bootstrap` rebuilds them",
+            "Caches/CocoaPods" => "CocoaPods download cache shared by all projects; pods re-download on the next `pod install`",
+            "Caches/org.carthage.CarthageKit" => "Carthage download cache; dependencies re-download on the next `carthage bootstrap`",
+            "Caches/org.swift.swiftpm" => "Swift Package Manager repository cache; packages re-download on the next resolve",
+            "Developer/Xcode/Archives" => "Archived app builds and dSYMs; crash reports for those releases can no longer be symbolicated",
+            _ => self.risk_level().explanation(),
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "pod install / carthage bootstrap / swift package resolve",
+            base_secs: 20.0,
+            secs_per_dependency: 5.0,
+            secs_per_mb: 0.3,
+            manifests: &["Podfile.lock", "Cartfile.resolved", "Package.resolved"],
+        }
+    }
+}
+
+/// Download caches of browsers and runtimes under the user cache directory,
+/// with how deep their version entries sit (electron-builder keeps one
+/// directory per tool, each holding versions)
+const BINARY_CACHES: [(&str, usize); 4] = [
+    ("ms-playwright", 1),
+    ("Cypress", 1),
+    ("electron", 1),
+    ("electron-builder", 2),
+];
+
+/// Browsers and runtimes downloaded by test and packaging tools (Playwright,
+/// Cypress, Electron, electron-builder), one entry per downloaded version,
+/// with `--global`
+pub struct BinaryCachesStrategy;
+
+/// Name a cache entry is versioned by: its own, or for Electron's
+/// checksum-named directories, the archive inside
+fn version_label(entry: &Path) -> Option<String> {
+    let name = entry.file_name()?.to_string_lossy().into_owned();
+    if name.len() >= 32 && name.chars().all(|c| c.is_ascii_hexdigit()) {
+        let inner = std::fs::read_dir(entry).ok()?.flatten().next()?;
+        return Some(inner.file_name().to_string_lossy().into_owned());
+    }
+    Some(name)
+}
+
+/// Splits a label at its first run of digits and dots: "chromium-1091"
+/// into ("chromium-*", [1091]), "13.6.0" into ("*", [13, 6, 0]). Entries
+/// without a version (lock files, link directories) give `None`.
+fn split_version(label: &str) -> Option<(String, Vec<u64>)> {
+    let start = label.find(|c: char| c.is_ascii_digit())?;
+    let end = label[start..]
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map_or(label.len(), |len| start + len);
+    let version = label[start..end].split('.').filter_map(|part| part.parse().ok()).collect();
+    Some((format!("{}*{}", &label[..start], &label[end..]), version))
+}
+
+/// Non-hidden entries `depth` levels below `dir`
+fn entries_at_depth(dir: &Path, depth: usize) -> Vec<PathBuf> {
+    let mut entries = vec![dir.to_path_buf()];
+    for _ in 0..depth {
+        entries = entries
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|children| children.flatten())
+            .filter(|child| !child.file_name().to_string_lossy().starts_with('.'))
+            .map(|child| child.path())
+            .collect();
+    }
+    entries
+}
+
+impl CleaningStrategy for BinaryCachesStrategy {
+    fn name(&self) -> &str {
+        "Binaries"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "💿",
+            ascii: "i",
+            nerd: "\u{f019}",
+        }
+    }
+
+    fn detect(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        BINARY_CACHES.iter().map(|(cache, _)| *cache).collect()
+    }
+
+    fn project_targets(&self, _root: &Path) -> Vec<&str> {
+        // Each version is a target of its own
+        Vec::new()
+    }
+
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        BINARY_CACHES
+            .iter()
+            .flat_map(|(cache, depth)| entries_at_depth(&root.join(cache), *depth))
+            .filter(|entry| self.target_version(entry).is_some())
+            .collect()
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        dirs::cache_dir()
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Downloaded browser or runtime; the tool downloads it again when a project next needs this version"
+    }
+
+    fn target_version(&self, target: &Path) -> Option<(String, Vec<u64>)> {
+        // Grouped by the directory holding the versions, so electron-builder's
+        // tools (and Playwright's browsers, by name) are kept apart
+        let (tool, version) = split_version(&version_label(target)?)?;
+        let cache = target.parent()?.file_name()?.to_string_lossy();
+        Some((format!("{}/{}", cache, tool), version))
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "re-download",
+            base_secs: 10.0,
+            secs_per_dependency: 0.0,
+            secs_per_mb: 0.1,
+            manifests: &[],
+        }
+    }
+}
+
+/// Rust toolchains installed by rustup and Node.js versions installed by
+/// nvm, one entry per toolchain or version, with `--global`. The default
+/// toolchain and version (and rustup's directory overrides) aren't offered.
+pub struct ToolchainsStrategy;
+
+impl ToolchainsStrategy {
+    fn rustup_home() -> Option<PathBuf> {
+        std::env::var_os("RUSTUP_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".rustup")))
+    }
+
+    fn nvm_dir() -> Option<PathBuf> {
+        std::env::var_os("NVM_DIR")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".nvm")))
+    }
+
+    /// Installed toolchains, except the default and directory overrides
+    /// from rustup's `settings.toml`
+    fn rustup_toolchains() -> Vec<PathBuf> {
+        let Some(home) = Self::rustup_home() else {
+            return Vec::new();
+        };
+        let settings = std::fs::read_to_string(home.join("settings.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .unwrap_or_default();
+        let mut in_use: Vec<String> = settings
+            .get("overrides")
+            .and_then(|overrides| overrides.as_table())
+            .map(|overrides| overrides.values().filter_map(|v| v.as_str().map(str::to_string)).collect())
+            .unwrap_or_default();
+        in_use.extend(settings.get("default_toolchain").and_then(|v| v.as_str()).map(str::to_string));
+
+        entries_at_depth(&home.join("toolchains"), 1)
+            .into_iter()
+            .filter(|toolchain| toolchain.is_dir())
+            .filter(|toolchain| {
+                let name = toolchain.file_name().unwrap_or_default().to_string_lossy();
+                // "stable" also names "stable-x86_64-unknown-linux-gnu"
+                !in_use
+                    .iter()
+                    .any(|used| name == used.as_str() || name.starts_with(&format!("{}-", used)))
+            })
+            .collect()
+    }
+
+    /// Leading version numbers nvm's `default` alias names ("18", "v18.19.0"),
+    /// empty for the newest ("node", or no default), following aliases to
+    /// other aliases (`lts/*` to `lts/iron` to `v20.11.0`). `None` if it
+    /// names an alias that can't be resolved.
+    fn nvm_default(nvm_dir: &Path) -> Option<Vec<u64>> {
+        let Ok(content) = std::fs::read_to_string(nvm_dir.join("alias/default")) else {
+            return Some(Vec::new());
+        };
+        let mut alias = content.trim().to_string();
+        // Aliases can point to each other in a loop
+        for _ in 0..8 {
+            if alias == "node" || alias == "stable" {
+                return Some(Vec::new());
+            }
+            let version = split_version(alias.trim_start_matches('v'))
+                .filter(|(rest, _)| rest == "*")
+                .map(|(_, version)| version);
+            if version.is_some() {
+                return version;
+            }
+            alias = std::fs::read_to_string(nvm_dir.join("alias").join(&alias)).ok()?.trim().to_string();
+        }
+        None
+    }
+
+    /// Installed Node.js versions, except the one nvm's `default` alias uses
+    fn nvm_versions() -> Vec<PathBuf> {
+        let Some(nvm_dir) = Self::nvm_dir() else {
+            return Vec::new();
+        };
+        let mut versions: Vec<(Vec<u64>, PathBuf)> = entries_at_depth(&nvm_dir.join("versions/node"), 1)
+            .into_iter()
+            .filter_map(|dir| Some((split_version(&dir.file_name()?.to_string_lossy())?.1, dir)))
+            .collect();
+        versions.sort_by(|a, b| b.cmp(a));
+
+        // The newest installed version matching the default is in use. When
+        // spektr can't tell which one that is, none are offered.
+        let Some(wanted) = Self::nvm_default(&nvm_dir) else {
+            return Vec::new();
+        };
+        let default = versions.iter().position(|(version, _)| version.starts_with(&wanted));
+        versions
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| Some(*index) != default)
+            .map(|(_, (_, dir))| dir)
+            .collect()
+    }
+
+    fn is_rustup_toolchain(target: &Path) -> bool {
+        Self::rustup_home().is_some_and(|home| target.parent() == Some(home.join("toolchains").as_path()))
+    }
+
+    fn is_nvm_version(target: &Path) -> bool {
+        Self::nvm_dir().is_some_and(|dir| target.parent() == Some(dir.join("versions/node").as_path()))
+    }
+}
+
+impl CleaningStrategy for ToolchainsStrategy {
+    fn name(&self) -> &str {
+        "Toolchains"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🧰",
+            ascii: "u",
+            nerd: "\u{f0ad}",
+        }
+    }
+
+    fn detect(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec![".rustup/toolchains", ".nvm/versions/node"]
+    }
+
+    fn project_targets(&self, _root: &Path) -> Vec<&str> {
+        // Each toolchain is a target of its own
+        Vec::new()
+    }
+
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        if self.global_root().is_none_or(|global| global != root) {
+            return Vec::new();
+        }
+        let mut found = Self::rustup_toolchains();
+        found.extend(Self::nvm_versions());
+        found
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        dirs::home_dir()
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Installed toolchain; projects that pin it install it again on their next build"
+    }
+
+    fn target_version(&self, target: &Path) -> Option<(String, Vec<u64>)> {
+        let name = target.file_name()?.to_string_lossy();
+        if Self::is_nvm_version(target) {
+            return Some(("nvm/node".to_string(), split_version(&name)?.1));
+        }
+        if !Self::is_rustup_toolchain(target) {
+            return None;
+        }
+        // "1.75.0-<host>", "nightly-2024-01-15-<host>", or a channel like "beta-<host>"
+        let (channel, rest) = name.split_once('-').unwrap_or((&name, ""));
+        let version = match channel {
+            "stable" | "beta" | "nightly" => {
+                let date: Vec<u64> = rest.split('-').map_while(|part| part.parse().ok()).take(3).collect();
+                return Some((format!("rustup/{}", channel), date));
+            }
+            _ => split_version(channel)?.1,
+        };
+        Some(("rustup/release".to_string(), version))
+    }
+
+    fn last_used(&self, target: &Path) -> Option<SystemTime> {
+        // The compiler or runtime is read on every use
+        ["bin/rustc", "bin/node"]
+            .iter()
+            .filter_map(|binary| target.join(binary).metadata().ok()?.accessed().ok())
+            .max()
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        let name = root.file_name()?.to_string_lossy().into_owned();
+        if Self::is_rustup_toolchain(root) {
+            let mut command = CleanCommand::new(&["rustup", "toolchain", "uninstall"], &["."]);
+            command.argv.push(name);
+            return Some(command);
+        }
+        if Self::is_nvm_version(root) {
+            // nvm is a shell function, loaded from nvm.sh
+            let nvm_sh = Self::nvm_dir()?.join("nvm.sh").to_string_lossy().into_owned();
+            let mut command = CleanCommand::new(&["bash", "-c", r#". "$1" && nvm uninstall "$2""#, "nvm"], &["."]);
+            command.label = Some(format!("nvm uninstall {}", name));
+            command.argv.extend([nvm_sh, name]);
+            return Some(command);
+        }
+        None
+    }
+
+    fn prefers_clean_command(&self) -> bool {
+        true
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "rustup toolchain install / nvm install",
+            base_secs: 20.0,
+            secs_per_dependency: 0.0,
+            secs_per_mb: 0.1,
+            manifests: &[],
+        }
+    }
+}
+
+/// Android SDK components that pile up across platform releases: emulator
+/// system images, build-tools versions other than the newest, and emulator
+/// devices (AVDs), one entry each, with `--global`
+pub struct AndroidSdkStrategy;
+
+impl AndroidSdkStrategy {
+    /// `ANDROID_HOME`, `ANDROID_SDK_ROOT`, or where Android Studio installs it
+    fn sdk_root() -> Option<PathBuf> {
+        if let Some(dir) = ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+            .iter()
+            .find_map(|var| std::env::var_os(var).filter(|dir| !dir.is_empty()))
+        {
+            return Some(PathBuf::from(dir));
+        }
+        if cfg!(target_os = "macos") {
+            dirs::home_dir().map(|home| home.join("Library/Android/sdk"))
+        } else if cfg!(windows) {
+            dirs::data_local_dir().map(|dir| dir.join("Android/Sdk"))
+        } else {
+            dirs::home_dir().map(|home| home.join("Android/Sdk"))
+        }
+    }
+
+    /// Where the emulator keeps its devices
+    fn avd_dir() -> Option<PathBuf> {
+        let var = |name| std::env::var_os(name).filter(|dir: &std::ffi::OsString| !dir.is_empty()).map(PathBuf::from);
+        var("ANDROID_AVD_HOME")
+            .or_else(|| var("ANDROID_USER_HOME").map(|dir| dir.join("avd")))
+            .or_else(|| var("ANDROID_SDK_HOME").map(|dir| dir.join(".android/avd")))
+            .or_else(|| dirs::home_dir().map(|home| home.join(".android/avd")))
+    }
+
+    /// sdkmanager or avdmanager from the SDK's command-line tools, falling
+    /// back to the PATH
+    fn sdk_tool(tool: &str) -> String {
+        let file = if cfg!(windows) { format!("{}.bat", tool) } else { tool.to_string() };
+        Self::sdk_root()
+            .map(|sdk| sdk.join("cmdline-tools/latest/bin").join(file))
+            .filter(|path| path.is_file())
+            .map_or_else(|| tool.to_string(), |path| path.to_string_lossy().into_owned())
+    }
+
+    /// The component's package path for sdkmanager (e.g.,
+    /// `system-images;android-34;google_apis;x86_64`)
+    fn package_path(target: &Path) -> Option<String> {
+        let relative = target.strip_prefix(Self::sdk_root()?).ok()?;
+        let parts: Vec<String> = relative.iter().map(|part| part.to_string_lossy().into_owned()).collect();
+        let package = match parts.first()?.as_str() {
+            "build-tools" if parts.len() == 2 => parts,
+            "system-images" if parts.len() == 4 => parts,
+            _ => return None,
+        };
+        Some(package.join(";"))
+    }
+
+    /// System images the emulator devices boot from (`image.sysdir.1` in
+    /// each AVD's `config.ini`, relative to the SDK root)
+    fn images_in_use(sdk: &Path, avds: &[PathBuf]) -> Vec<PathBuf> {
+        avds.iter()
+            .filter_map(|avd| std::fs::read_to_string(avd.join("config.ini")).ok())
+            .flat_map(|config| {
+                config
+                    .lines()
+                    .filter_map(|line| {
+                        let (key, value) = line.split_once('=')?;
+                        (key.trim() == "image.sysdir.1").then(|| sdk.join(value.trim().replace('\\', "/")))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn avd_name(target: &Path) -> Option<String> {
+        if target.parent()? != Self::avd_dir()? {
+            return None;
+        }
+        Some(target.file_name()?.to_str()?.strip_suffix(".avd")?.to_string())
+    }
+}
+
+impl CleaningStrategy for AndroidSdkStrategy {
+    fn name(&self) -> &str {
+        "Android SDK"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "📲",
+            ascii: "k",
+            nerd: "\u{f17b}",
+        }
+    }
+
+    fn detect(&self, _path: &Path) -> bool {
+        false
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["system-images", "build-tools"]
+    }
+
+    fn project_targets(&self, _root: &Path) -> Vec<&str> {
+        // Each component is a target of its own
+        Vec::new()
+    }
+
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        if self.global_root().is_none_or(|global| global != root) {
+            return Vec::new();
+        }
+        let avds: Vec<PathBuf> = Self::avd_dir()
+            .map(|avd_dir| {
+                entries_at_depth(&avd_dir, 1)
+                    .into_iter()
+                    .filter(|entry| entry.is_dir() && entry.extension().is_some_and(|ext| ext == "avd"))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // Images a device boots from stay, or the device stops working
+        let in_use = Self::images_in_use(root, &avds);
+        let mut found: Vec<PathBuf> = entries_at_depth(&root.join("system-images"), 3)
+            .into_iter()
+            .filter(|image| !in_use.iter().any(|used| used.components().eq(image.components())))
+            .collect();
+
+        // Projects build with the newest build-tools unless they pin one
+        let mut build_tools: Vec<(Vec<u64>, PathBuf)> = entries_at_depth(&root.join("build-tools"), 1)
+            .into_iter()
+            .filter_map(|dir| Some((split_version(&dir.file_name()?.to_string_lossy())?.1, dir)))
+            .collect();
+        build_tools.sort_by(|a, b| b.cmp(a));
+        found.extend(build_tools.into_iter().skip(1).map(|(_, dir)| dir));
+        found.extend(avds);
+        found.retain(|entry| entry.is_dir());
+        found
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        Self::sdk_root()
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        // A device holds whatever was installed and set up on it
+        if target.ends_with(".avd") {
+            RiskLevel::High
+        } else {
+            RiskLevel::Medium
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        if target.ends_with(".avd") {
+            "Emulator device with its installed apps and data, which are lost; AVD Manager creates a fresh one"
+        } else if target.starts_with(|c: char| c.is_ascii_digit()) {
+            "Older build-tools version; the SDK manager installs it again if a project pins it"
+        } else {
+            "Emulator system image; the SDK manager downloads it again (often several GB)"
+        }
+    }
+
+    fn target_version(&self, target: &Path) -> Option<(String, Vec<u64>)> {
+        if let Some(name) = Self::avd_name(target) {
+            return Some((format!("avd/{}", name), Vec::new()));
+        }
+        let package = Self::package_path(target)?;
+        let parts: Vec<&str> = package.split(';').collect();
+        match parts.as_slice() {
+            ["build-tools", version] => Some(("build-tools".to_string(), split_version(version)?.1)),
+            // Grouped by tag and ABI; the API level ("android-34") is the version
+            ["system-images", api, tag, abi] => Some((
+                format!("system-images/{}/{}", tag, abi),
+                split_version(api).map(|(_, version)| version).unwrap_or_default(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        if let Some(name) = Self::avd_name(root) {
+            let mut command = CleanCommand::new(&["delete", "avd", "-n"], &["."]);
+            command.argv.insert(0, Self::sdk_tool("avdmanager"));
+            command.argv.push(name);
+            return Some(command);
+        }
+        let package = Self::package_path(root)?;
+        let mut command = CleanCommand::new(&["--uninstall"], &["."]);
+        command.argv.insert(0, Self::sdk_tool("sdkmanager"));
+        command.argv.push(format!("--sdk_root={}", Self::sdk_root()?.display()));
+        command.argv.push(package);
+        Some(command)
+    }
+
+    fn prefers_clean_command(&self) -> bool {
+        true
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "sdkmanager download",
+            base_secs: 30.0,
+            secs_per_dependency: 0.0,
+            secs_per_mb: 0.1,
+            manifests: &[],
+        }
+    }
+}
+
+pub struct SbtStrategy;
+
+impl CleaningStrategy for SbtStrategy {
+    fn name(&self) -> &str {
+        "Scala"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🔺",
+            ascii: "S",
+            nerd: "\u{e737}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("build.sbt").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["target", "project/target", ".bloop", ".metals"]
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        target_path.join("streams").exists()
+            || std::fs::read_dir(target_path).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.file_name().to_string_lossy().starts_with("scala-"))
+            })
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Metals' index database: the editor re-imports and re-indexes the build
+            ".metals" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "project/target" => "Compiled build definition; sbt recompiles it on next start",
+            ".bloop" => "Bloop build server config; regenerated by `sbt bloopInstall` or Metals",
+            ".metals" => "Metals index database; the editor re-imports and re-indexes the build",
+            _ => "Compiled classes and packaged outputs; the next `sbt compile` regenerates them",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        // Cleans every subproject, and `project/target`
+        Some(CleanCommand::new(&["sbt", "clean"], &[]).with_nested(root, "target"))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "sbt compile",
+            base_secs: 90.0,
+            secs_per_dependency: 2.0,
+            secs_per_mb: 0.5,
+            manifests: &["build.sbt"],
+        }
+    }
+}
+
+/// MSBuild project files (C#, F#, Visual Basic)
+const DOTNET_PROJECT_EXTENSIONS: [&str; 3] = ["csproj", "fsproj", "vbproj"];
+
+/// Files at the root of a .NET solution or repository
+const DOTNET_SOLUTION_MARKERS: [&str; 3] = ["Directory.Build.props", "Directory.Packages.props", "global.json"];
+
+/// Whether `dir` holds an MSBuild project file
+fn has_dotnet_project(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries.flatten().any(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| DOTNET_PROJECT_EXTENSIONS.contains(&ext))
+        })
+    })
+}
+
+/// .NET solutions and projects: `bin/` and `obj/` next to every project
+/// file, found by walking the solution since projects nest at any depth
+pub struct DotNetStrategy;
+
+impl CleaningStrategy for DotNetStrategy {
+    fn name(&self) -> &str {
+        ".NET"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🔷",
+            ascii: "#",
+            nerd: "\u{e77f}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        // Listing the folder only when it has build output keeps most
+        // directories at two stats
+        DOTNET_SOLUTION_MARKERS.iter().any(|marker| path.join(marker).exists())
+            || ((path.join("bin").is_dir() || path.join("obj").is_dir()) && has_dotnet_project(path))
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["bin", "obj"]
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        if has_dotnet_project(root) {
+            self.targets()
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        fn walk(dir: &Path, found: &mut Vec<PathBuf>) {
+            if has_dotnet_project(dir) {
+                found.extend(["bin", "obj"].iter().map(|name| dir.join(name)).filter(|path| path.is_dir()));
+            }
+            let Ok(entries) = std::fs::read_dir(dir) else {
+                return;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                let skipped = name.starts_with('.') || ["bin", "obj", "node_modules"].contains(&name.as_ref());
+                if !skipped && entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    walk(&entry.path(), found);
+                }
+            }
+        }
+        let mut found = Vec::new();
+        walk(root, &mut found);
+        found
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        ["Debug", "Release", "project.assets.json"]
+            .iter()
+            .any(|marker| target_path.join(marker).exists())
+    }
+
+    fn is_workspace_root(&self, path: &Path) -> bool {
+        // Projects under a solution root are covered by the target walk
+        DOTNET_SOLUTION_MARKERS.iter().any(|marker| path.join(marker).exists())
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "obj" => "Intermediate build output and restored package assets; `dotnet build` regenerates them",
+            _ => "Compiled assemblies; the next `dotnet build` regenerates them",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "dotnet build",
+            base_secs: 20.0,
+            secs_per_dependency: 1.0,
+            secs_per_mb: 0.3,
+            manifests: &["packages.lock.json"],
+        }
+    }
+}
+
+/// Deno projects, plus the shared module cache (`DENO_DIR`) as a global location
+pub struct DenoStrategy;
+
+impl DenoStrategy {
+    /// Subdirectories of `DENO_DIR` that hold downloaded or compiled modules
+    const GLOBAL_TARGETS: [&'static str; 4] = ["remote", "deps", "npm", "gen"];
+}
+
+impl CleaningStrategy for DenoStrategy {
+    fn name(&self) -> &str {
+        "Deno"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🦕",
+            ascii: "D",
+            nerd: "\u{e628}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("deno.json").exists() || path.join("deno.jsonc").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        let mut targets = vec!["vendor"];
+        targets.extend(Self::GLOBAL_TARGETS);
+        targets
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        if self.global_root().is_some_and(|global| global == root) {
+            Self::GLOBAL_TARGETS.to_vec()
+        } else {
+            vec!["vendor"]
+        }
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        std::env::var_os("DENO_DIR")
+            .map(PathBuf::from)
+            .or_else(|| dirs::cache_dir().map(|dir| dir.join("deno")))
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        // Vendored modules may be committed on purpose for offline builds;
+        // the global cache costs a re-download
+        RiskLevel::Medium
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "vendor" => "Vendored remote modules; re-fetched by `deno install`, but check it isn't committed for offline builds",
+            "gen" => "Transpiled module cache shared by all Deno projects; rebuilt on next run",
+            "npm" => "npm packages shared by all Deno projects; re-downloaded on next run",
+            _ => "Remote modules shared by all Deno projects; re-downloaded on next run",
+        }
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "deno install",
+            base_secs: 5.0,
+            secs_per_dependency: 0.1,
+            secs_per_mb: 0.1,
+            manifests: &["deno.lock"],
+        }
+    }
+}
+
+/// R projects whose packages are managed by renv
+pub struct RenvStrategy;
+
+impl CleaningStrategy for RenvStrategy {
+    fn name(&self) -> &str {
+        "R"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "📊",
+            ascii: "r",
+            nerd: "\u{e68a}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("renv.lock").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["renv/library", "renv/staging"]
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Restoring often compiles packages from source, and old
+            // versions can disappear from repositories
+            "renv/library" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "renv/library" => "Project package library; `renv::restore()` reinstalls it from renv.lock, often compiling from source",
+            _ => "Leftover package downloads from interrupted installs; safe to remove",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "renv::restore()",
+            base_secs: 30.0,
+            secs_per_dependency: 10.0,
+            secs_per_mb: 0.1,
+            manifests: &[],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        count_lines(&root.join("renv.lock"), |line| line.trim_start().starts_with("\"Package\":"))
+    }
+}
+
+/// OCaml projects built with dune
+pub struct DuneStrategy;
+
+impl CleaningStrategy for DuneStrategy {
+    fn name(&self) -> &str {
+        "OCaml"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🐫",
+            ascii: "O",
+            nerd: "\u{e67a}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("dune-project").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["_build", "_opam"]
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // A local opam switch: the compiler and every installed package
+            "_opam" => RiskLevel::High,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "_opam" => "Local opam switch (compiler and packages); recreating it rebuilds the compiler and may pick newer package versions",
+            _ => "dune build output; the next `dune build` regenerates it",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn clean_command(&self, _root: &Path) -> Option<CleanCommand> {
+        Some(CleanCommand::new(&["dune", "clean"], &["_build"]))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "dune build",
+            base_secs: 10.0,
+            secs_per_dependency: 2.0,
+            secs_per_mb: 0.2,
+            manifests: &["dune-project"],
+        }
+    }
+}
+
+/// Elm applications and packages
+pub struct ElmStrategy;
+
+impl CleaningStrategy for ElmStrategy {
+    fn name(&self) -> &str {
+        "Elm"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🌳",
+            ascii: "e",
+            nerd: "\u{e62c}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("elm.json").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["elm-stuff"]
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Compiled module cache; the next `elm make` rebuilds it"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "elm make",
+            base_secs: 5.0,
+            secs_per_dependency: 0.5,
+            secs_per_mb: 0.5,
+            manifests: &["elm.json"],
+        }
+    }
+}
+
+/// Haxe projects: build output and the project-local haxelib repository
+pub struct HaxeStrategy;
+
+impl CleaningStrategy for HaxeStrategy {
+    fn name(&self) -> &str {
+        "Haxe"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🔶",
+            ascii: "h",
+            nerd: "\u{e666}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("build.hxml").exists() || path.join("haxelib.json").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["build", ".haxelib"]
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Libraries set up with `haxelib dev` or git checkouts aren't reinstalled by name
+            ".haxelib" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".haxelib" => "Project-local libraries; `haxelib install` downloads them again, but dev and git libraries must be set up by hand",
+            _ => "Compiled output for each target platform; the next `haxe build.hxml` regenerates it",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "haxe build.hxml",
+            base_secs: 20.0,
+            secs_per_dependency: 2.0,
+            secs_per_mb: 0.3,
+            manifests: &["haxelib.json"],
+        }
+    }
+}
+
+/// Crystal projects: installed shards and the local compiler cache
+pub struct CrystalStrategy;
+
+impl CleaningStrategy for CrystalStrategy {
+    fn name(&self) -> &str {
+        "Crystal"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "💠",
+            ascii: "K",
+            nerd: "\u{e62f}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("shard.yml").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["lib", ".crystal"]
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        // `lib/` only counts once shards has installed into it
+        let installed = root.join("shard.lock").exists() || root.join("lib/.shards.info").exists();
+        self.targets()
+            .into_iter()
+            .filter(|target| *target != "lib" || installed)
+            .collect()
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".crystal" => "Compiler cache; the next `crystal build` recompiles",
+            _ => "Installed shards; `shards install` downloads them again",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "shards install && crystal build",
+            base_secs: 20.0,
+            secs_per_dependency: 3.0,
+            secs_per_mb: 0.2,
+            manifests: &["shard.lock"],
+        }
+    }
+}
+
+/// Gleam projects: compiled modules and downloaded packages under `build/`
+pub struct GleamStrategy;
+
+impl CleaningStrategy for GleamStrategy {
+    fn name(&self) -> &str {
+        "Gleam"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "⭐",
+            ascii: "g",
+            nerd: "\u{f005}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("gleam.toml").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["build"]
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        ["packages", "dev", "prod"]
+            .iter()
+            .any(|marker| target_path.join(marker).exists())
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Compiled modules and downloaded packages; the next `gleam build` fetches and compiles them again"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn clean_command(&self, _root: &Path) -> Option<CleanCommand> {
+        Some(CleanCommand::new(&["gleam", "clean"], &["build"]))
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "gleam build",
+            base_secs: 5.0,
+            secs_per_dependency: 0.5,
+            secs_per_mb: 0.2,
+            manifests: &[],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        count_lines(&root.join("manifest.toml"), |line| line.trim_start().starts_with("{ name = "))
+    }
+}
+
+/// Clojure projects built with Leiningen (`project.clj`) or the Clojure CLI (`deps.edn`)
+pub struct ClojureStrategy;
+
+impl CleaningStrategy for ClojureStrategy {
+    fn name(&self) -> &str {
+        "Clojure"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🔮",
+            ascii: "C",
+            nerd: "\u{e768}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("project.clj").exists() || path.join("deps.edn").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["target", ".cpcache", ".shadow-cljs"]
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        // Leiningen and tools.build compile into `classes`; Leiningen also
+        // keeps `stale` bookkeeping and drops jars at the top level
+        target_path.join("classes").exists()
+            || target_path.join("stale").exists()
+            || std::fs::read_dir(target_path).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.path().extension().is_some_and(|ext| ext == "jar"))
+            })
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // shadow-cljs' compilation cache: the next build recompiles every namespace
+            ".shadow-cljs" => RiskLevel::Medium,
+            _ => RiskLevel::Low,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            ".cpcache" => "Cached classpaths; the Clojure CLI recomputes them on next start",
+            ".shadow-cljs" => "shadow-cljs build cache; the next build recompiles every namespace",
+            _ => "Compiled classes and jars; the next `lein compile` or `clj -T:build` regenerates them",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "lein compile / clj -T:build",
+            base_secs: 30.0,
+            secs_per_dependency: 1.5,
+            secs_per_mb: 0.3,
+            manifests: &["project.clj", "deps.edn"],
+        }
+    }
+}
+
+/// Vagrant environments
+pub struct VagrantStrategy;
+
+impl CleaningStrategy for VagrantStrategy {
+    fn name(&self) -> &str {
+        "Vagrant"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🧳",
+            ascii: "V",
+            nerd: "\u{f1b2}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("Vagrantfile").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec![".vagrant"]
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Machine state linking this project to its VMs; Vagrant loses track of existing machines, which then have to be removed by hand in the provider"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        // Deleting it orphans running or halted VMs
+        RiskLevel::High
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "vagrant up",
+            base_secs: 600.0,
+            secs_per_dependency: 0.0,
+            secs_per_mb: 0.05,
+            manifests: &[],
+        }
+    }
+}
+
+/// Tauri apps: a JavaScript frontend with the Rust backend crate in
+/// `src-tauri/`, reported as one project instead of a Node.js and a Rust one
+pub struct TauriStrategy;
+
+impl CleaningStrategy for TauriStrategy {
+    fn name(&self) -> &str {
+        "Tauri"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🪟",
+            ascii: "T",
+            nerd: "\u{f108}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.join("package.json").exists() && path.join("src-tauri/Cargo.toml").exists()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["node_modules", "dist", "build", "src-tauri/target"]
+    }
+
+    fn member_dirs(&self) -> Vec<&str> {
+        vec!["src-tauri"]
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "node_modules" => "Installed packages; the next `npm install` re-downloads them",
+            "src-tauri/target" => "Compiled backend crates and bundles; the next `tauri build` recompiles everything",
+            _ => "Frontend build output; regenerated by the next `tauri dev` or `tauri build`",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn relocation_vars(&self) -> Vec<(&str, &str)> {
+        vec![("CARGO_TARGET_DIR", "target")]
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Build {
+            command: "npm install && tauri build",
+            base_secs: 60.0,
+            secs_per_dependency: 0.5,
+            secs_per_mb: 0.05,
+            manifests: &["package-lock.json", "yarn.lock", "pnpm-lock.yaml"],
+        }
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        let crates = count_lines(&root.join("src-tauri").join("Cargo.lock"), |line| line == "[[package]]");
+        match (crates, node_dependency_count(root)) {
+            (None, None) => None,
+            (crates, packages) => Some(crates.unwrap_or(0) + packages.unwrap_or(0)),
+        }
+    }
+}
+
+/// Jupyter/IPython notebook folders, whose checkpoints sit next to the notebooks
+pub struct JupyterStrategy;
+
+impl CleaningStrategy for JupyterStrategy {
+    fn name(&self) -> &str {
+        "Jupyter"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "📓",
+            ascii: "J",
+            nerd: "\u{f02d}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        // Only list the folder when checkpoints exist, so most directories
+        // cost a single stat
+        path.join(".ipynb_checkpoints").is_dir()
+            && std::fs::read_dir(path).is_ok_and(|entries| {
+                entries
+                    .flatten()
+                    .any(|entry| entry.path().extension().is_some_and(|ext| ext == "ipynb"))
+            })
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        Vec::new()
+    }
+
+    fn nested_targets(&self) -> Vec<&str> {
+        vec![".ipynb_checkpoints"]
+    }
+
+    fn is_workspace_root(&self, _path: &Path) -> bool {
+        // Nested notebook folders are covered by the recursive target search
+        true
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Notebook autosave checkpoints; Jupyter recreates them on the next save, but earlier checkpoints are lost"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Free("recreated on save")
+    }
+}
+
+/// Files left in a `node_modules` by npm, Yarn, or pnpm
+const NODE_MODULES_MARKERS: [&str; 5] = [".package-lock.json", ".yarn-integrity", ".yarn-state.yml", ".modules.yaml", ".bin"];
+
+/// Files left in a `target` by Cargo or Maven
+const TARGET_MARKERS: [&str; 4] = [".rustc_info.json", "CACHEDIR.TAG", "maven-status", "classes"];
+
+/// Project files of the tools that build into `target`
+const TARGET_OWNERS: [&str; 5] = ["Cargo.toml", "pom.xml", "build.sbt", "project.clj", "deps.edn"];
+
+/// `node_modules` and `target` directories whose project is gone: the
+/// directory holding them has no `package.json` (or `Cargo.toml`, `pom.xml`,
+/// ...) anymore, so no other strategy detects them
+pub struct OrphanedStrategy;
+
+impl CleaningStrategy for OrphanedStrategy {
+    fn name(&self) -> &str {
+        "Orphaned"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "👻",
+            ascii: "o",
+            nerd: "\u{f02a0}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        !self.project_targets(path).is_empty()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["node_modules", "target"]
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        let mut targets = Vec::new();
+        let node_modules = root.join("node_modules");
+        if !root.join("package.json").exists()
+            && NODE_MODULES_MARKERS.iter().any(|marker| node_modules.join(marker).exists())
+        {
+            targets.push("node_modules");
+        }
+        let target = root.join("target");
+        if !TARGET_OWNERS.iter().any(|owner| root.join(owner).exists())
+            && TARGET_MARKERS.iter().any(|marker| target.join(marker).exists())
+        {
+            targets.push("target");
+        }
+        targets
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "node_modules" => "Installed packages of a project whose package.json is gone; nothing uses them",
+            _ => "Build output of a project whose Cargo.toml or pom.xml is gone; nothing rebuilds it",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Free("the project is gone")
+    }
+}
+
+/// Extensions of model weight and checkpoint files
+const MODEL_WEIGHT_EXTENSIONS: [&str; 9] = ["safetensors", "bin", "pt", "pth", "ckpt", "onnx", "h5", "gguf", "keras"];
+
+/// Whether `dir` holds model weights, directly or one level down (e.g.,
+/// `models/<name>/model.safetensors`)
+fn holds_model_weights(dir: &Path, depth: usize) -> bool {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        let path = entry.path();
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => depth > 0 && holds_model_weights(&path, depth - 1),
+            Ok(_) => path
+                .extension()
+                .is_some_and(|ext| MODEL_WEIGHT_EXTENSIONS.iter().any(|known| ext.eq_ignore_ascii_case(known))),
+            Err(_) => false,
+        }
+    })
+}
+
+/// Machine learning models: the Hugging Face and PyTorch download caches
+/// (with `--global`) and projects' `models/` and `checkpoints/` directories.
+/// Opt-in, since trained checkpoints may be the only copy.
+pub struct MlModelsStrategy;
+
+impl MlModelsStrategy {
+    /// Directories that hold weights inside a project
+    const PROJECT_TARGETS: [&'static str; 2] = ["models", "checkpoints"];
+
+    /// Download caches under `~/.cache`
+    const GLOBAL_TARGETS: [&'static str; 2] = ["huggingface", "torch"];
+}
+
+impl CleaningStrategy for MlModelsStrategy {
+    fn name(&self) -> &str {
+        "ML Models"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🧠",
+            ascii: "H",
+            nerd: "\u{f09d1}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        !self.project_targets(path).is_empty()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        let mut targets = Self::PROJECT_TARGETS.to_vec();
+        targets.extend(Self::GLOBAL_TARGETS);
+        targets
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        if self.global_root().is_some_and(|global| global == root) {
+            Self::GLOBAL_TARGETS.to_vec()
+        } else {
+            Self::PROJECT_TARGETS
+                .into_iter()
+                .filter(|target| holds_model_weights(&root.join(target), 1))
+                .collect()
+        }
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        // Both libraries use ~/.cache on every platform, not the OS cache dir
+        std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".cache")))
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        match target {
+            // Training output: retraining may take days, or be impossible
+            "checkpoints" => RiskLevel::High,
+            _ => RiskLevel::Medium,
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        match target {
+            "huggingface" => "Hugging Face hub cache shared by all projects; models and datasets re-download on next use",
+            "torch" => "PyTorch hub and torchvision weights; re-downloaded on next use",
+            "checkpoints" => "Training checkpoints; unless they're saved elsewhere, only retraining brings them back",
+            _ => "Model weights; re-downloaded or re-exported, unless this is the only copy",
+        }
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Varies("re-download, or retrain for checkpoints")
+    }
+}
+
+/// First line of a valid `CACHEDIR.TAG` (https://bford.info/cachedir/)
+pub const CACHEDIR_TAG_SIGNATURE: &str = "Signature: 8a477f597d28d172789f06886806bc55";
+
+/// Directories that declare themselves caches with a `CACHEDIR.TAG` file,
+/// a convention shared by Cargo, pip, ccache, and others
+pub struct CacheDirStrategy;
+
+impl CleaningStrategy for CacheDirStrategy {
+    fn name(&self) -> &str {
+        "Cache"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🗄",
+            ascii: "c",
+            nerd: "\u{f187}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        std::fs::read(path.join("CACHEDIR.TAG"))
+            .is_ok_and(|content| content.starts_with(CACHEDIR_TAG_SIGNATURE.as_bytes()))
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["."]
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Tagged as a cache (CACHEDIR.TAG); the owning tool regenerates it"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Varies("regenerated by the owning tool")
+    }
+}
+
+/// Directory name globs of [`LeftoverTempStrategy`] unless the config sets
+/// `leftover_temp_patterns`
+pub const LEFTOVER_TEMP_PATTERNS: [&str; 4] = ["*-unpacked", "tmp-extract-*", "*.extracted", "_extract_*"];
+
+/// Directories left behind by extracting archives (installers, downloads,
+/// tooling that unpacks and never cleans up). Opt-in: the names say nothing
+/// about whether the contents are still needed.
+pub struct LeftoverTempStrategy {
+    patterns: GlobSet,
+}
+
+impl LeftoverTempStrategy {
+    /// Matches directory names against `patterns` (globs such as `*-unpacked`)
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = pattern.as_ref();
+            builder.add(Glob::new(pattern).with_context(|| format!("Invalid leftover temp pattern '{}'", pattern))?);
+        }
+        Ok(Self {
+            patterns: builder.build().context("Failed to compile leftover temp patterns")?,
+        })
+    }
+}
+
+impl Default for LeftoverTempStrategy {
+    fn default() -> Self {
+        Self::new(&LEFTOVER_TEMP_PATTERNS).expect("built-in leftover temp patterns are valid")
+    }
+}
+
+impl CleaningStrategy for LeftoverTempStrategy {
+    fn name(&self) -> &str {
+        "Leftover temp"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "🧺",
+            ascii: "t",
+            nerd: "\u{f1c6}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        path.is_dir() && path.file_name().is_some_and(|name| self.patterns.is_match(name))
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        vec!["."]
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Extracted archive contents; only the archive (if kept) can bring them back"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Free("re-extract the archive")
+    }
+}
+
+/// Directory names that often hold build output of toolchains without a strategy
+const HEURISTIC_TARGETS: [&str; 4] = ["build", "dist", "out", "tmp"];
+
+/// Heuristic matches smaller than this aren't worth a guess
+const HEURISTIC_MIN_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Whether the files under `dir` add up to more than `limit` bytes. Stops
+/// walking as soon as they do, and doesn't follow symlinks.
+fn exceeds_size(dir: &Path, limit: u64) -> bool {
+    fn walk(dir: &Path, remaining: &mut u64) -> bool {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return false;
+        };
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if walk(&entry.path(), remaining) {
+                    return true;
+                }
+            } else if metadata.is_file() {
+                match remaining.checked_sub(metadata.len()) {
+                    Some(left) => *remaining = left,
+                    None => return true,
+                }
+            }
+        }
+        false
+    }
+    let mut remaining = limit;
+    walk(dir, &mut remaining)
+}
+
+/// Guesses for toolchains spektr doesn't know: large `build`, `dist`, `out`,
+/// and `tmp` directories where no other strategy recognizes a project.
+/// Opt-in (`--heuristic`), high risk, and never selected automatically,
+/// since nothing proves the directory is build output.
+pub struct HeuristicStrategy;
+
+impl CleaningStrategy for HeuristicStrategy {
+    fn name(&self) -> &str {
+        "Heuristic"
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        StrategyIcons {
+            emoji: "❔",
+            ascii: "U",
+            nerd: "\u{f128}",
+        }
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        !self.project_targets(path).is_empty()
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        HEURISTIC_TARGETS.to_vec()
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        HEURISTIC_TARGETS
+            .into_iter()
+            .filter(|name| {
+                let dir = root.join(name);
+                // Not a symlink: its size would belong to whatever it points at
+                dir.symlink_metadata().is_ok_and(|m| m.is_dir()) && exceeds_size(&dir, HEURISTIC_MIN_SIZE)
+            })
+            .collect()
+    }
+
+    fn target_explanation(&self, _target: &str) -> &str {
+        "Guessed from the name only; no known project marker says it's build output"
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        false
+    }
+
+    fn fallback(&self) -> bool {
+        true
+    }
+
+    fn auto_selectable(&self) -> bool {
+        false
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        RebuildModel::Varies("guessed build output")
+    }
+}
+
+/// Factory function to create all built-in strategies
+pub fn default_strategies() -> Vec<Box<dyn CleaningStrategy>> {
+    vec![
+        // Ahead of Node.js and Rust, so it claims the targets they share
+        Box::new(TauriStrategy),
+        Box::new(NodeStrategy),
+        Box::new(WebCacheStrategy),
+        Box::new(ElectronStrategy),
+        Box::new(RustStrategy),
+        Box::new(FlutterStrategy),
+        Box::new(AndroidStrategy),
+        Box::new(MavenStrategy),
+        Box::new(GradleStrategy),
+        Box::new(ElixirStrategy),
+        Box::new(RubyStrategy),
+        Box::new(PythonStrategy),
+        Box::new(ComposerStrategy),
+        Box::new(SbtStrategy),
+        Box::new(DotNetStrategy),
+        Box::new(DenoStrategy),
+        Box::new(RenvStrategy),
+        Box::new(DuneStrategy),
+        Box::new(ElmStrategy),
+        Box::new(HaxeStrategy),
+        Box::new(CrystalStrategy),
+        Box::new(GleamStrategy),
+        Box::new(ClojureStrategy),
+        Box::new(VagrantStrategy),
+        Box::new(JupyterStrategy),
+        Box::new(XcodeStrategy),
+        Box::new(MobileStrategy),
+        Box::new(BinaryCachesStrategy),
+        Box::new(ToolchainsStrategy),
+        Box::new(AndroidSdkStrategy),
+        Box::new(MlModelsStrategy),
+        // Ahead of Cache, which would otherwise claim an orphaned Cargo `target`
+        Box::new(OrphanedStrategy),
+        Box::new(CacheDirStrategy),
+        Box::new(LeftoverTempStrategy::default()),
+        Box::new(HeuristicStrategy),
+    ]
+}
+
+/// A strategy declared in the config (`[[strategies]]` tables), e.g.
+/// `{ name = "Zig", marker_files = ["build.zig"], targets = ["zig-out", ".zig-cache"] }`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomStrategyConfig {
+    pub name: String,
+    /// Files (or directories) whose presence marks a project root
+    pub marker_files: Vec<String>,
+    /// Directories or files under the root to clean
+    pub targets: Vec<String>,
+    #[serde(default = "default_custom_risk")]
+    pub risk: RiskLevel,
+    /// Shown next to the projects in every icon set (the ASCII set uses the
+    /// name's first letter unless this is ASCII)
+    pub icon: Option<String>,
+}
+
+fn default_custom_risk() -> RiskLevel {
+    RiskLevel::Low
+}
+
+impl CustomStrategyConfig {
+    fn validate(&self) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("Custom strategies need a name");
+        }
+        if self.marker_files.is_empty() || self.targets.is_empty() {
+            bail!("Custom strategy '{}' needs marker_files and targets", self.name);
+        }
+        for target in &self.targets {
+            check_target(&self.name, target)?;
+        }
+        Ok(())
+    }
+
+    /// Icons for every set, leaked so they live for the whole run; only
+    /// `icons::register_custom` calls this, once per strategy
+    pub fn leak_icons(&self) -> StrategyIcons {
+        let icon: &'static str = match &self.icon {
+            Some(icon) => Box::leak(icon.clone().into_boxed_str()),
+            None => "📁",
+        };
+        let ascii: &'static str = if icon.is_ascii() {
+            icon
+        } else {
+            let letter = self.name.chars().next().filter(char::is_ascii).unwrap_or('?');
+            Box::leak(letter.to_string().into_boxed_str())
+        };
+        StrategyIcons {
+            emoji: icon,
+            ascii,
+            nerd: icon,
+        }
+    }
+}
+
+/// A strategy declared in the config: marker files in, targets out
+pub struct CustomStrategy {
+    config: CustomStrategyConfig,
+}
+
+impl CustomStrategy {
+    pub fn new(config: CustomStrategyConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl CleaningStrategy for CustomStrategy {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        crate::icons::custom_icons(&self.config.name)
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        self.config.marker_files.iter().any(|marker| path.join(marker).exists())
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        self.config.targets.iter().map(String::as_str).collect()
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        self.config.risk
+    }
+}
+
+/// Whether a target is a glob (e.g., `cmake-build-*`) rather than a fixed path
+pub fn is_target_glob(target: &str) -> bool {
+    target.contains(['*', '?', '[', '{'])
+}
+
+/// Checks a target from the config: a path inside the project (not the
+/// project itself), and a valid glob where `**` only appears as a leading
+/// `**/` and the last component isn't only wildcards, which would match
+/// everything in its directory (sources included)
+fn check_target(strategy_name: &str, target: &str) -> Result<()> {
+    let components: Vec<&str> = target.strip_suffix('/').unwrap_or(target).split('/').collect();
+    if components.iter().any(|c| c.is_empty() || *c == ".") {
+        bail!(
+            "Target '{}' of strategy '{}' must name a directory or file under the project, without empty or '.' components",
+            target,
+            strategy_name
+        );
+    }
+    let last = components.last().copied().unwrap_or_default();
+    if last.chars().all(|c| matches!(c, '*' | '?')) {
+        bail!(
+            "Target '{}' of strategy '{}' would match everything in its directory; name the artifacts instead",
+            target,
+            strategy_name
+        );
+    }
+    let path = Path::new(target);
+    if path.is_absolute() || path.components().any(|c| matches!(c, std::path::Component::ParentDir)) {
+        bail!(
+            "Target '{}' of strategy '{}' must be a path inside the project",
+            target,
+            strategy_name
+        );
+    }
+    if is_target_glob(target) {
+        Glob::new(target).with_context(|| format!("Invalid target glob '{}' of strategy '{}'", target, strategy_name))?;
+        if target.trim_start_matches("**/").contains("**") {
+            bail!(
+                "Target '{}' of strategy '{}' can only use '**' as a leading '**/'",
+                target,
+                strategy_name
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Existing paths under `root` that a target names: `.` is the root itself,
+/// a fixed path is returned whether it exists or not, and a glob is matched
+/// one path component at a time. Globs starting with `**/` match at any
+/// depth and are found by the scanner's nested walk instead.
+pub fn expand_target(root: &Path, target: &str) -> Vec<PathBuf> {
+    if target == "." {
+        return vec![root.to_path_buf()];
+    }
+    if !is_target_glob(target) {
+        return vec![root.join(target)];
+    }
+    if target.starts_with("**/") {
+        return Vec::new();
+    }
+
+    let mut paths = vec![root.to_path_buf()];
+    for component in target.split('/').filter(|c| !c.is_empty()) {
+        if !is_target_glob(component) {
+            paths = paths.into_iter().map(|path| path.join(component)).collect();
+            continue;
+        }
+        let Ok(glob) = Glob::new(component) else {
+            return Vec::new();
+        };
+        let matcher = glob.compile_matcher();
+        paths = paths
+            .iter()
+            .filter_map(|dir| std::fs::read_dir(dir).ok())
+            .flat_map(|entries| entries.flatten())
+            .filter(|entry| matcher.is_match(entry.file_name()))
+            .map(|entry| entry.path())
+            .collect();
+    }
+    paths.sort();
+    paths
+}
+
+/// Lowercase name without punctuation or spaces, so "node", "nodejs",
+/// and "Node.js" all refer to the same strategy
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Whether a user-supplied name refers to `strategy_name`
+pub fn matches_strategy(strategy_name: &str, name: &str) -> bool {
+    let name = normalize_name(name);
+    // "Node.js" is also known as "node"
+    let short = strategy_name.split('.').next().unwrap_or(strategy_name);
+    normalize_name(strategy_name) == name || normalize_name(short) == name
+}
+
+/// Targets added to or removed from a built-in strategy in the config
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TargetOverride {
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+}
+
+/// User adjustments to the built-in strategies, from the config and CLI
+#[derive(Debug, Clone, Default)]
+pub struct StrategySettings {
+    /// Names of strategies that never run
+    pub disabled: Vec<String>,
+    /// Names of opt-in strategies that run
+    pub enabled: Vec<String>,
+    /// Target overrides, keyed by strategy name
+    pub overrides: HashMap<String, TargetOverride>,
+    /// Directory name globs replacing [`LEFTOVER_TEMP_PATTERNS`]
+    pub leftover_temp_patterns: Vec<String>,
+    /// Strategies declared in the config, run after the built-in ones
+    pub custom: Vec<CustomStrategyConfig>,
+}
+
+impl StrategySettings {
+    /// Checks that every disabled, enabled, or overridden name refers to a
+    /// known strategy, that custom strategies are complete and don't reuse a
+    /// name, that configured targets stay inside the project, and that the
+    /// leftover temp patterns are valid globs
+    pub fn validate(&self) -> Result<()> {
+        let builtin = default_strategies();
+        for (idx, custom) in self.custom.iter().enumerate() {
+            custom.validate()?;
+            let taken = builtin.iter().map(|s| s.name()).chain(self.custom[..idx].iter().map(|c| c.name.as_str()));
+            if let Some(existing) = taken.into_iter().find(|existing| matches_strategy(existing, &custom.name)) {
+                bail!("Custom strategy '{}' has the same name as '{}'", custom.name, existing);
+            }
+        }
+
+        for (name, target_override) in &self.overrides {
+            for target in &target_override.add {
+                check_target(name, target)?;
+            }
+        }
+
+        let strategies = self.all_strategies();
+        for name in self.disabled.iter().chain(&self.enabled).chain(self.overrides.keys()) {
+            if !strategies.iter().any(|s| matches_strategy(s.name(), name)) {
+                let available: Vec<&str> = strategies.iter().map(|s| s.name()).collect();
+                bail!("Unknown strategy '{}' (available: {})", name, available.join(", "));
+            }
+        }
+        LeftoverTempStrategy::new(&self.leftover_temp_patterns)?;
+        Ok(())
+    }
+
+    /// Built-in and custom strategies, before disabling and overrides
+    pub fn all_strategies(&self) -> Vec<Box<dyn CleaningStrategy>> {
+        let mut strategies = default_strategies();
+        strategies.extend(
+            self.custom
+                .iter()
+                .map(|config| Box::new(CustomStrategy::new(config.clone())) as Box<dyn CleaningStrategy>),
+        );
+        strategies
+    }
+
+    pub fn is_disabled(&self, strategy_name: &str) -> bool {
+        self.disabled.iter().any(|name| matches_strategy(strategy_name, name))
+    }
+
+    /// Whether `strategy` runs: not disabled, and enabled if it's opt-in
+    pub fn is_active(&self, strategy: &dyn CleaningStrategy) -> bool {
+        !self.is_disabled(strategy.name())
+            && (strategy.enabled_by_default() || self.enabled.iter().any(|name| matches_strategy(strategy.name(), name)))
+    }
+
+    fn override_for(&self, strategy_name: &str) -> Option<&TargetOverride> {
+        self.overrides
+            .iter()
+            .find(|(name, _)| matches_strategy(strategy_name, name))
+            .map(|(_, target_override)| target_override)
+    }
+}
+
+/// A built-in strategy with targets added or removed by the config
+struct OverriddenStrategy {
+    inner: Box<dyn CleaningStrategy>,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl OverriddenStrategy {
+    fn adjust<'a>(&'a self, targets: Vec<&'a str>) -> Vec<&'a str> {
+        let mut targets: Vec<&str> = targets
+            .into_iter()
+            .filter(|target| !self.is_removed(target))
+            .collect();
+        for added in &self.added {
+            if !targets.contains(&added.as_str()) {
+                targets.push(added);
+            }
+        }
+        targets
+    }
+
+    fn is_added(&self, target: &str) -> bool {
+        self.added.iter().any(|added| added == target)
+    }
+
+    fn is_removed(&self, target: &str) -> bool {
+        self.removed.iter().any(|removed| removed == target)
+    }
+}
+
+impl CleaningStrategy for OverriddenStrategy {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn icons(&self) -> StrategyIcons {
+        self.inner.icons()
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        self.inner.detect(path)
+    }
+
+    fn targets(&self) -> Vec<&str> {
+        self.adjust(self.inner.targets())
+    }
+
+    fn nested_targets(&self) -> Vec<&str> {
+        self.inner.nested_targets().into_iter().filter(|target| !self.is_removed(target)).collect()
+    }
+
+    fn discover_targets(&self, root: &Path) -> Vec<PathBuf> {
+        // Discovered targets go by their directory name, as for their risk
+        self.inner
+            .discover_targets(root)
+            .into_iter()
+            .filter(|path| !path.file_name().is_some_and(|name| self.is_removed(&name.to_string_lossy())))
+            .collect()
+    }
+
+    fn project_targets(&self, root: &Path) -> Vec<&str> {
+        self.adjust(self.inner.project_targets(root))
+    }
+
+    fn owns_target(&self, target_path: &Path) -> bool {
+        self.inner.owns_target(target_path)
+    }
+
+    fn global_root(&self) -> Option<PathBuf> {
+        self.inner.global_root()
+    }
+
+    fn member_dirs(&self) -> Vec<&str> {
+        self.inner.member_dirs()
+    }
+
+    fn is_workspace_root(&self, path: &Path) -> bool {
+        self.inner.is_workspace_root(path)
+    }
+
+    fn risk_level(&self) -> RiskLevel {
+        self.inner.risk_level()
+    }
+
+    fn target_risk(&self, target: &str) -> RiskLevel {
+        if self.is_added(target) {
+            self.inner.risk_level()
+        } else {
+            self.inner.target_risk(target)
+        }
+    }
+
+    fn target_explanation(&self, target: &str) -> &str {
+        if self.is_added(target) {
+            // The built-in explanations describe other targets
+            self.target_risk(target).explanation()
+        } else {
+            self.inner.target_explanation(target)
+        }
+    }
+
+    fn relocation_vars(&self) -> Vec<(&str, &str)> {
+        self.inner.relocation_vars()
+    }
+
+    fn target_version(&self, target: &Path) -> Option<(String, Vec<u64>)> {
+        self.inner.target_version(target)
+    }
+
+    fn last_used(&self, target: &Path) -> Option<SystemTime> {
+        self.inner.last_used(target)
+    }
+
+    fn clean_command(&self, root: &Path) -> Option<CleanCommand> {
+        self.inner.clean_command(root)
+    }
+
+    fn prefers_clean_command(&self) -> bool {
+        self.inner.prefers_clean_command()
+    }
+
+    fn enabled_by_default(&self) -> bool {
+        self.inner.enabled_by_default()
+    }
+
+    fn fallback(&self) -> bool {
+        self.inner.fallback()
+    }
+
+    fn auto_selectable(&self) -> bool {
+        self.inner.auto_selectable()
+    }
+
+    fn rebuild_model(&self) -> RebuildModel {
+        self.inner.rebuild_model()
+    }
+
+    fn dependency_count(&self, root: &Path) -> Option<usize> {
+        self.inner.dependency_count(root)
+    }
+}
+
+/// Built-in and custom strategies adjusted by the user's settings: disabled
+/// and not-enabled opt-in ones are dropped, and custom patterns and target
+/// overrides are applied
+pub fn configured_strategies(settings: &StrategySettings) -> Vec<Box<dyn CleaningStrategy>> {
+    settings
+        .all_strategies()
+        .into_iter()
+        .filter(|strategy| settings.is_active(strategy.as_ref()))
+        .map(|strategy| {
+            if strategy.name() == "Leftover temp" && !settings.leftover_temp_patterns.is_empty() {
+                // Validated up front; keeps the built-in patterns otherwise
+                if let Ok(custom) = LeftoverTempStrategy::new(&settings.leftover_temp_patterns) {
+                    return Box::new(custom) as Box<dyn CleaningStrategy>;
+                }
+            }
+            strategy
+        })
+        .map(|strategy| match settings.override_for(strategy.name()) {
+            Some(target_override) => Box::new(OverriddenStrategy {
+                inner: strategy,
+                added: target_override.add.clone(),
+                removed: target_override.remove.clone(),
+            }) as Box<dyn CleaningStrategy>,
+            None => strategy,
+        })
+        .collect()
 }
@@ -0,0 +1,231 @@
+use crate::cleaner::CleanProfile;
+use anyhow::{bail, Context, Result};
+use clap::ValueEnum;
+use std::path::Path;
+use std::process::Command;
+
+/// Name used for the scheduler unit/task on every platform
+const TASK_NAME: &str = "spektr-clean";
+
+/// How often the scheduled clean runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+}
+
+/// Installs a recurring unattended clean of `root` with the platform scheduler
+/// (systemd user timer, launchd agent, or Windows Task Scheduler)
+pub fn install(root: &Path, frequency: Frequency, profile: CleanProfile) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the spektr executable")?;
+    let report = dirs::data_dir()
+        .map(|dir| dir.join("spektr").join("last-report.txt"))
+        .context("Failed to determine data directory")?;
+    if let Some(parent) = report.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let args = vec![
+        "--mode".to_string(),
+        "clean".to_string(),
+        "--profile".to_string(),
+        profile.label().to_string(),
+        "--report".to_string(),
+        report.display().to_string(),
+        root.display().to_string(),
+    ];
+
+    platform::install(&exe, &args, frequency)?;
+    println!(
+        "✅ Scheduled {} {} clean of {}",
+        match frequency {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+        },
+        profile.label(),
+        root.display()
+    );
+    println!("   Reports will be written to {}", report.display());
+    Ok(())
+}
+
+/// Removes the recurring clean installed by [`install`]
+pub fn uninstall() -> Result<()> {
+    platform::uninstall()?;
+    println!("✅ Removed scheduled clean");
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !status.success() {
+        bail!("{:?} exited with {}", command.get_program(), status);
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn unit_dir() -> Result<PathBuf> {
+        dirs::config_dir()
+            .map(|dir| dir.join("systemd").join("user"))
+            .context("Failed to determine systemd user unit directory")
+    }
+
+    pub fn install(exe: &Path, args: &[String], frequency: Frequency) -> Result<()> {
+        let dir = unit_dir()?;
+        std::fs::create_dir_all(&dir)?;
+
+        let command_line = std::iter::once(exe.display().to_string())
+            .chain(args.iter().cloned())
+            .map(|a| quote(&a))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let service = format!(
+            "[Unit]\nDescription=spektr artifact cleanup\n\n[Service]\nType=oneshot\nExecStart={}\n",
+            command_line
+        );
+        let timer = format!(
+            "[Unit]\nDescription=Recurring spektr artifact cleanup\n\n[Timer]\nOnCalendar={}\nPersistent=true\n\n[Install]\nWantedBy=timers.target\n",
+            match frequency {
+                Frequency::Daily => "daily",
+                Frequency::Weekly => "weekly",
+            }
+        );
+
+        std::fs::write(dir.join(format!("{}.service", TASK_NAME)), service)?;
+        std::fs::write(dir.join(format!("{}.timer", TASK_NAME)), timer)?;
+
+        run(Command::new("systemctl").args(["--user", "daemon-reload"]))?;
+        run(Command::new("systemctl").args(["--user", "enable", "--now", &format!("{}.timer", TASK_NAME)]))
+    }
+
+    /// Quotes an `ExecStart` word, escaping what systemd would otherwise
+    /// unescape (`\`, `"`), expand as a specifier (`%`), or substitute as
+    /// a variable (`$`)
+    fn quote(arg: &str) -> String {
+        let escaped = arg
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('%', "%%")
+            .replace('$', "$$");
+        format!("\"{}\"", escaped)
+    }
+
+    pub fn uninstall() -> Result<()> {
+        let dir = unit_dir()?;
+        let _ = run(Command::new("systemctl").args(["--user", "disable", "--now", &format!("{}.timer", TASK_NAME)]));
+
+        for ext in ["service", "timer"] {
+            let path = dir.join(format!("{}.{}", TASK_NAME, ext));
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+
+        run(Command::new("systemctl").args(["--user", "daemon-reload"]))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use std::path::PathBuf;
+
+    const LABEL: &str = "com.github.jcyrus.spektr-clean";
+
+    fn plist_path() -> Result<PathBuf> {
+        dirs::home_dir()
+            .map(|home| home.join("Library").join("LaunchAgents").join(format!("{}.plist", LABEL)))
+            .context("Failed to determine LaunchAgents directory")
+    }
+
+    pub fn install(exe: &Path, args: &[String], frequency: Frequency) -> Result<()> {
+        let path = plist_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let program_args = std::iter::once(exe.display().to_string())
+            .chain(args.iter().cloned())
+            .map(|a| format!("        <string>{}</string>\n", xml_escape(&a)))
+            .collect::<String>();
+        // Runs at 03:00, on Sundays for weekly schedules
+        let interval = match frequency {
+            Frequency::Daily => "        <key>Hour</key><integer>3</integer>\n".to_string(),
+            Frequency::Weekly => {
+                "        <key>Weekday</key><integer>0</integer>\n        <key>Hour</key><integer>3</integer>\n".to_string()
+            }
+        };
+        let plist = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+<plist version=\"1.0\">\n<dict>\n    <key>Label</key>\n    <string>{}</string>\n\
+    <key>ProgramArguments</key>\n    <array>\n{}    </array>\n\
+    <key>StartCalendarInterval</key>\n    <dict>\n{}    </dict>\n</dict>\n</plist>\n",
+            LABEL, program_args, interval
+        );
+
+        std::fs::write(&path, plist)?;
+        run(Command::new("launchctl").arg("load").arg("-w").arg(&path))
+    }
+
+    fn xml_escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")
+    }
+
+    pub fn uninstall() -> Result<()> {
+        let path = plist_path()?;
+        if path.exists() {
+            let _ = run(Command::new("launchctl").arg("unload").arg("-w").arg(&path));
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(windows)]
+mod platform {
+    use super::*;
+
+    pub fn install(exe: &Path, args: &[String], frequency: Frequency) -> Result<()> {
+        let command_line = std::iter::once(format!("\"{}\"", exe.display()))
+            .chain(args.iter().map(|a| format!("\"{}\"", a)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let schedule = match frequency {
+            Frequency::Daily => "DAILY",
+            Frequency::Weekly => "WEEKLY",
+        };
+
+        run(Command::new("schtasks").args([
+            "/Create", "/F", "/SC", schedule, "/ST", "03:00", "/TN", TASK_NAME, "/TR", &command_line,
+        ]))
+    }
+
+    pub fn uninstall() -> Result<()> {
+        run(Command::new("schtasks").args(["/Delete", "/F", "/TN", TASK_NAME]))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+mod platform {
+    use super::*;
+
+    pub fn install(_exe: &Path, _args: &[String], _frequency: Frequency) -> Result<()> {
+        bail!("Scheduling is not supported on this platform")
+    }
+
+    pub fn uninstall() -> Result<()> {
+        bail!("Scheduling is not supported on this platform")
+    }
+}
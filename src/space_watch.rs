@@ -0,0 +1,285 @@
+use crate::audit::{self, BudgetEnforcement, SpaceSnapshot};
+use crate::cleaner::{self, CleanProfile, ProtectedPaths};
+use crate::format::{format_size, SizeUnits};
+use crate::lock::{LockHolder, LockStatus, RootLock};
+use crate::mounts::mount_point;
+use crate::retention::built_at;
+use crate::rules::PathRules;
+use crate::scanner::strategy::{configured_strategies, StrategySettings};
+use crate::scanner::{CleanableProject, Scanner, SizeCache};
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+/// What to do when a watched volume runs low on space, and which directory
+/// trees to keep within an artifact budget
+#[derive(Debug, Clone)]
+pub struct SpaceWatch {
+    pub paths: Vec<PathBuf>,
+    pub threshold_percent: f64,
+    pub notify_only: bool,
+    pub profile: CleanProfile,
+    pub interval: Duration,
+    pub units: SizeUnits,
+    pub budgets: Vec<Budget>,
+    /// Percentage of a budget that enforcing it cleans down to
+    pub low_water_percent: f64,
+    /// Strategies and path rules used to scan budgeted trees
+    pub strategies: StrategySettings,
+    pub path_rules: PathRules,
+    /// Prefer the toolchains' clean commands when enforcing budgets
+    pub native_clean: bool,
+}
+
+/// Most artifact bytes allowed under a directory
+#[derive(Debug, Clone)]
+pub struct Budget {
+    pub path: PathBuf,
+    pub max_total: u64,
+}
+
+/// Free space on the volume holding `path`, as (available bytes, percent free)
+fn free_space(path: &Path) -> Result<(u64, f64)> {
+    let stats = fs4::statvfs(path)
+        .with_context(|| format!("Failed to read free space for {}", path.display()))?;
+    let percent = if stats.total_space() == 0 {
+        100.0
+    } else {
+        stats.available_space() as f64 * 100.0 / stats.total_space() as f64
+    };
+    Ok((stats.available_space(), percent))
+}
+
+/// Checks the watched volumes and budgets every `interval` until interrupted.
+/// A volume triggers once when it drops below the threshold, and again only
+/// after it has recovered above it.
+pub fn run(watch: &SpaceWatch) -> Result<()> {
+    if watch.paths.is_empty() && watch.budgets.is_empty() {
+        bail!("No paths to watch: pass them as arguments or set `[space_watch] paths` or `budgets` in the config");
+    }
+
+    for path in &watch.paths {
+        println!(
+            "👀 Watching {} ({}, below {:.0}% free → {})",
+            path.display(),
+            mount_point(path).display(),
+            watch.threshold_percent,
+            if watch.notify_only { "notify".to_string() } else { format!("{} clean", watch.profile.label()) }
+        );
+    }
+
+    for budget in &watch.budgets {
+        println!(
+            "💰 Budget for {}: {} of artifacts (over it → {} clean down to {:.0}%)",
+            budget.path.display(),
+            format_size(budget.max_total, watch.units),
+            watch.profile.label(),
+            watch.low_water_percent
+        );
+    }
+
+    let mut triggered: HashSet<PathBuf> = HashSet::new();
+    let mut stalled: HashMap<PathBuf, u64> = HashMap::new();
+    loop {
+        for path in &watch.paths {
+            let (available, percent) = match free_space(path) {
+                Ok(space) => space,
+                Err(e) => {
+                    eprintln!("⚠️  {:#}", e);
+                    continue;
+                }
+            };
+
+            if percent >= watch.threshold_percent {
+                triggered.remove(path);
+                continue;
+            }
+            if !triggered.insert(path.clone()) {
+                continue;
+            }
+
+            let message = format!(
+                "{} has {} free ({:.1}%)",
+                mount_point(path).display(),
+                format_size(available, watch.units),
+                percent
+            );
+            println!("🚨 {}", message);
+
+            if watch.notify_only {
+                notify(
+                    "spektr: low disk space",
+                    &format!("{}. Run `spektr --mode tui {}` to free space.", message, path.display()),
+                );
+            } else if let Err(e) = run_clean(path, watch.profile) {
+                eprintln!("❌ {:#}", e);
+            }
+        }
+
+        for budget in &watch.budgets {
+            if let Err(e) = enforce_budget(budget, watch, &mut stalled) {
+                eprintln!("❌ {:#}", e);
+            }
+        }
+
+        std::thread::sleep(watch.interval);
+    }
+}
+
+/// Scans a budgeted tree and, when its artifacts exceed the budget, deletes
+/// the least recently built projects until the total is back under the
+/// low-water mark. `stalled` holds the totals that earlier enforcements
+/// couldn't get below (the rest is off-limits to the profile); those roots
+/// are retried only once their artifacts grow again.
+fn enforce_budget(budget: &Budget, watch: &SpaceWatch, stalled: &mut HashMap<PathBuf, u64>) -> Result<()> {
+    // Events are not consumed; the receiver only has to outlive the scan
+    let (tx, _rx) = mpsc::channel();
+    let projects = Scanner::new(configured_strategies(&watch.strategies))
+        .with_size_cache(SizeCache::load())
+        .with_path_rules(watch.path_rules.clone())
+        .scan(&budget.path, tx)?;
+
+    let total_before: u64 = projects.iter().map(|p| p.total_size).sum();
+    if total_before <= budget.max_total {
+        stalled.remove(&budget.path);
+        return Ok(());
+    }
+    if stalled.get(&budget.path).is_some_and(|&after| total_before <= after) {
+        return Ok(());
+    }
+
+    let _lock = match RootLock::try_acquire(&budget.path, "budget")? {
+        LockStatus::Acquired(lock) => lock,
+        LockStatus::Held(holder) => {
+            println!(
+                "⏳ {} is over budget but locked by {}; retrying next check",
+                budget.path.display(),
+                LockHolder::describe(holder.as_ref())
+            );
+            return Ok(());
+        }
+    };
+
+    let low_water = (budget.max_total as f64 * watch.low_water_percent / 100.0) as u64;
+    println!(
+        "🚨 {} holds {} of artifacts, over its {} budget",
+        budget.path.display(),
+        format_size(total_before, watch.units),
+        format_size(budget.max_total, watch.units)
+    );
+
+    let mut candidates: Vec<CleanableProject> = projects
+        .iter()
+        .filter(|p| !p.manual_only && watch.path_rules.auto_select(&p.root_path) != Some(false))
+        .filter_map(|p| p.restricted_to(watch.profile.max_risk()))
+        .collect();
+    candidates.sort_by_key(built_at);
+
+    let mut plan = Vec::new();
+    let mut remaining = total_before;
+    for project in candidates {
+        if remaining <= low_water {
+            break;
+        }
+        remaining -= project.total_size;
+        plan.push(project);
+    }
+
+    let protected = ProtectedPaths::new(&budget.path);
+    let before = SpaceSnapshot::take(&plan);
+    let mut cleaned = Vec::new();
+    let mut failures = 0;
+    for project in &plan {
+        match cleaner::delete_project(project, &protected, watch.native_clean) {
+            Ok(deletion) => {
+                for target in &deletion.drifted {
+                    eprintln!("   ⚠️  Skipped {}: {}", target.path.display(), target.reason);
+                }
+                if let Some(native) = deletion.native.filter(|native| !native.success) {
+                    eprintln!("   ⚠️  `{}` failed; deleted the targets directly", native.command);
+                }
+                let project = cleaner::without_drifted(project, &deletion.drifted);
+                println!(
+                    "   Deleted {} from {}",
+                    format_size(project.total_size, watch.units),
+                    project.root_path.display()
+                );
+                cleaned.push(project);
+            }
+            Err(e) => {
+                eprintln!("   ❌ {:#}", e);
+                failures += 1;
+            }
+        }
+    }
+
+    let total_after = total_before - cleaned.iter().map(|p| p.total_size).sum::<u64>();
+    // Best effort: the history is informational
+    let _ = audit::record_space("delete", &before, &cleaned);
+    audit::record_budget(&BudgetEnforcement {
+        timestamp: SystemTime::now(),
+        action: "budget".to_string(),
+        root: budget.path.clone(),
+        budget: budget.max_total,
+        low_water,
+        total_before,
+        total_after,
+        cleaned: cleaned.len(),
+        failures,
+    })?;
+
+    if total_after > low_water {
+        stalled.insert(budget.path.clone(), total_after);
+        println!(
+            "⚠️  {} is still at {}: the rest is above the {} profile's risk limit or kept by path rules",
+            budget.path.display(),
+            format_size(total_after, watch.units),
+            watch.profile.label()
+        );
+    } else {
+        stalled.remove(&budget.path);
+        println!("✅ {} is down to {}", budget.path.display(), format_size(total_after, watch.units));
+    }
+    Ok(())
+}
+
+/// Runs an unattended clean of `root` in a child process, like scheduled cleans
+fn run_clean(root: &Path, profile: CleanProfile) -> Result<()> {
+    let exe = std::env::current_exe().context("Failed to locate the spektr executable")?;
+    println!("🧹 Running {} clean of {}", profile.label(), root.display());
+
+    let status = Command::new(exe)
+        .args(["--mode", "clean", "--profile", profile.label()])
+        .arg(root)
+        .status()
+        .context("Failed to start clean")?;
+    if !status.success() {
+        bail!("Clean of {} exited with {}", root.display(), status);
+    }
+    Ok(())
+}
+
+/// Shows a desktop notification (best effort; falls back to stdout only)
+fn notify(title: &str, body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {:?} with title {:?}",
+            body, title
+        ));
+        command
+    } else if cfg!(windows) {
+        let mut command = Command::new("msg");
+        command.args(["*", &format!("{}: {}", title, body)]);
+        command
+    } else {
+        let mut command = Command::new("notify-send");
+        command.args([title, body]);
+        command
+    };
+
+    let _ = command.status();
+}
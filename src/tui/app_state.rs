@@ -1,5 +1,16 @@
-use crate::scanner::CleanableProject;
-use std::collections::HashSet;
+use crate::archive::{estimate_compression, CompressionEstimate};
+use crate::audit::CleanHistory;
+use crate::format::SizeUnits;
+use crate::icons::IconSet;
+use crate::report::{summarize_by_age, AgeSummary};
+use crate::retention::RetentionPolicy;
+use crate::rules::PathRules;
+use crate::scanner::{CleanableProject, RiskLevel, ScanPhase, SkipSizing};
+use crate::tui::events::{HintContext, Layer};
+use crate::tui::spill::Spill;
+use crate::tui::theme::{Palette, Theme};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortMode {
@@ -13,19 +24,81 @@ pub enum SortMode {
 pub enum FilterMode {
     All,
     NodeJs,
+    WebCache,
+    Electron,
     Rust,
     Flutter,
     Android,
+    Maven,
+    Gradle,
+    Elixir,
+    Ruby,
+    Python,
+    Php,
+    Scala,
+    DotNet,
+    Deno,
+    R,
+    OCaml,
+    Elm,
+    Haxe,
+    Crystal,
+    Gleam,
+    Clojure,
+    Vagrant,
+    Tauri,
+    Jupyter,
+    Xcode,
+    Mobile,
+    Binaries,
+    Toolchains,
+    AndroidSdk,
+    MlModels,
+    Orphaned,
+    Cache,
+    LeftoverTemp,
+    Heuristic,
 }
 
 impl FilterMode {
     pub fn next(&self) -> Self {
         match self {
             Self::All => Self::NodeJs,
-            Self::NodeJs => Self::Rust,
+            Self::NodeJs => Self::WebCache,
+            Self::WebCache => Self::Electron,
+            Self::Electron => Self::Rust,
             Self::Rust => Self::Flutter,
             Self::Flutter => Self::Android,
-            Self::Android => Self::All,
+            Self::Android => Self::Maven,
+            Self::Maven => Self::Gradle,
+            Self::Gradle => Self::Elixir,
+            Self::Elixir => Self::Ruby,
+            Self::Ruby => Self::Python,
+            Self::Python => Self::Php,
+            Self::Php => Self::Scala,
+            Self::Scala => Self::DotNet,
+            Self::DotNet => Self::Deno,
+            Self::Deno => Self::R,
+            Self::R => Self::OCaml,
+            Self::OCaml => Self::Elm,
+            Self::Elm => Self::Haxe,
+            Self::Haxe => Self::Crystal,
+            Self::Crystal => Self::Gleam,
+            Self::Gleam => Self::Clojure,
+            Self::Clojure => Self::Vagrant,
+            Self::Vagrant => Self::Tauri,
+            Self::Tauri => Self::Jupyter,
+            Self::Jupyter => Self::Xcode,
+            Self::Xcode => Self::Mobile,
+            Self::Mobile => Self::Binaries,
+            Self::Binaries => Self::Toolchains,
+            Self::Toolchains => Self::AndroidSdk,
+            Self::AndroidSdk => Self::MlModels,
+            Self::MlModels => Self::Orphaned,
+            Self::Orphaned => Self::Cache,
+            Self::Cache => Self::LeftoverTemp,
+            Self::LeftoverTemp => Self::Heuristic,
+            Self::Heuristic => Self::All,
         }
     }
 
@@ -33,15 +106,97 @@ impl FilterMode {
         match self {
             Self::All => "All",
             Self::NodeJs => "Node.js",
+            Self::WebCache => "Web Cache",
+            Self::Electron => "Electron",
             Self::Rust => "Rust",
             Self::Flutter => "Flutter",
             Self::Android => "Android",
+            Self::Maven => "Maven",
+            Self::Gradle => "Gradle",
+            Self::Elixir => "Elixir",
+            Self::Ruby => "Ruby",
+            Self::Python => "Python",
+            Self::Php => "PHP",
+            Self::Scala => "Scala",
+            Self::DotNet => ".NET",
+            Self::Deno => "Deno",
+            Self::R => "R",
+            Self::OCaml => "OCaml",
+            Self::Elm => "Elm",
+            Self::Haxe => "Haxe",
+            Self::Crystal => "Crystal",
+            Self::Gleam => "Gleam",
+            Self::Clojure => "Clojure",
+            Self::Vagrant => "Vagrant",
+            Self::Tauri => "Tauri",
+            Self::Jupyter => "Jupyter",
+            Self::Xcode => "Xcode",
+            Self::Mobile => "Mobile",
+            Self::Binaries => "Binaries",
+            Self::Toolchains => "Toolchains",
+            Self::AndroidSdk => "Android SDK",
+            Self::MlModels => "ML Models",
+            Self::Orphaned => "Orphaned",
+            Self::Cache => "Cache",
+            Self::LeftoverTemp => "Leftover Temp",
+            Self::Heuristic => "Heuristic",
+        }
+    }
+
+    /// Whether the filter shows `project`
+    pub fn matches(&self, project: &CleanableProject) -> bool {
+        match self {
+            Self::All => true,
+            Self::NodeJs => project.strategy_name == "Node.js",
+            Self::WebCache => project.strategy_name == "Web Cache",
+            Self::Electron => project.strategy_name == "Electron",
+            Self::Rust => project.strategy_name == "Rust",
+            Self::Flutter => project.strategy_name == "Flutter",
+            Self::Android => project.strategy_name == "Android",
+            Self::Maven => project.strategy_name == "Maven",
+            Self::Gradle => project.strategy_name == "Gradle",
+            Self::Elixir => project.strategy_name == "Elixir",
+            Self::Ruby => project.strategy_name == "Ruby",
+            Self::Python => project.strategy_name == "Python",
+            Self::Php => project.strategy_name == "PHP",
+            Self::Scala => project.strategy_name == "Scala",
+            Self::DotNet => project.strategy_name == ".NET",
+            Self::Deno => project.strategy_name == "Deno",
+            Self::R => project.strategy_name == "R",
+            Self::OCaml => project.strategy_name == "OCaml",
+            Self::Elm => project.strategy_name == "Elm",
+            Self::Haxe => project.strategy_name == "Haxe",
+            Self::Crystal => project.strategy_name == "Crystal",
+            Self::Gleam => project.strategy_name == "Gleam",
+            Self::Clojure => project.strategy_name == "Clojure",
+            Self::Vagrant => project.strategy_name == "Vagrant",
+            Self::Tauri => project.strategy_name == "Tauri",
+            Self::Jupyter => project.strategy_name == "Jupyter",
+            Self::Xcode => project.strategy_name == "Xcode",
+            Self::Mobile => project.strategy_name == "Mobile",
+            Self::Binaries => project.strategy_name == "Binaries",
+            Self::Toolchains => project.strategy_name == "Toolchains",
+            Self::AndroidSdk => project.strategy_name == "Android SDK",
+            Self::MlModels => project.strategy_name == "ML Models",
+            Self::Orphaned => project.strategy_name == "Orphaned",
+            Self::Cache => project.strategy_name == "Cache",
+            Self::LeftoverTemp => project.strategy_name == "Leftover temp",
+            Self::Heuristic => project.strategy_name == "Heuristic",
         }
     }
 }
 
 use crate::tui::tree::{TreeNode, build_tree, flatten_tree};
 
+/// What happens to the selected projects once confirmed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanAction {
+    /// Remove targets permanently
+    Delete,
+    /// Compress targets into the archive directory, then remove them
+    Archive,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViewMode {
     List,
@@ -50,6 +205,58 @@ pub enum ViewMode {
 
 use std::path::PathBuf;
 
+/// Startup options for the TUI, resolved from CLI flags and config
+#[derive(Debug, Clone)]
+pub struct TuiOptions {
+    pub units: SizeUnits,
+    pub icons: IconSet,
+    pub archive_dir: PathBuf,
+    /// Browse only: cleaning actions are disabled
+    pub read_only: bool,
+    /// Projects from the background index, shown until the scan confirms them
+    pub indexed: Vec<CleanableProject>,
+    /// Rules deciding which projects are preselected when the scan completes
+    pub path_rules: PathRules,
+    /// Retention rules whose plan is preselected when the scan completes (`--policy`)
+    pub retention: Option<RetentionPolicy>,
+    pub theme: Theme,
+    /// Projects kept in memory; the smallest beyond this move to disk
+    pub max_projects: usize,
+}
+
+/// Project list rows as rendered text, cached between frames
+#[derive(Debug, Default)]
+pub struct RowCache {
+    /// `AppState::rows_revision` the rows were built at
+    pub revision: Option<u64>,
+    pub rows: Vec<CachedRow>,
+}
+
+#[derive(Debug)]
+pub struct CachedRow {
+    pub text: String,
+    pub checked: bool,
+}
+
+/// Count and total size of the selected projects
+#[derive(Debug, Clone, Copy, Default)]
+struct SelectionTotals {
+    count: usize,
+    size: u64,
+}
+
+impl SelectionTotals {
+    fn add(&mut self, count: usize, size: u64) {
+        self.count += count;
+        self.size += size;
+    }
+
+    fn remove(&mut self, count: usize, size: u64) {
+        self.count = self.count.saturating_sub(count);
+        self.size = self.size.saturating_sub(size);
+    }
+}
+
 pub struct AppState {
     /// The root path of the scan
     pub scan_path: PathBuf,
@@ -63,10 +270,16 @@ pub struct AppState {
     /// Currently selected index in visible_projects (or flattened tree)
     pub selected_index: usize,
     
-    /// Set of selected project indices (for multi-selection in List mode)
-    /// In Tree mode, the TreeNode itself holds Checked state
-    selected_projects: HashSet<usize>,
+    /// Selected projects (root, strategy) in List mode, kept by project so
+    /// the selection follows them as rows move (re-sorting, scan results
+    /// replacing indexed entries). In Tree mode, the TreeNode itself holds
+    /// Checked state
+    selected_projects: HashSet<(PathBuf, String)>,
     
+    /// Running count and size of selected projects, updated per toggle
+    /// instead of re-summed every frame
+    selection: SelectionTotals,
+
     /// Current sort mode
     pub sort_mode: SortMode,
     
@@ -84,44 +297,338 @@ pub struct AppState {
     
     /// User confirmed deletion (set when 'y' is pressed)
     pub deletion_confirmed: bool,
+
+    /// Action shown in the confirmation modal
+    pub pending_action: CleanAction,
+
+    /// Per-project compression estimates for the archive confirmation
+    pub archive_estimates: Vec<(PathBuf, CompressionEstimate)>,
     
     /// Scan is still running
     pub scanning: bool,
 
     /// Current path being scanned
     pub scanning_path: String,
+
+    /// Current scan phase, shown in the title while scanning
+    pub scan_phase: Option<ScanPhase>,
+
+    /// Error that stopped the scan, shown in a modal until retried
+    pub scan_error: Option<String>,
     
     /// Spinner animation index
     pub spinner_index: usize,
+
+    /// Display units for sizes
+    pub units: SizeUnits,
+
+    /// Icons next to projects
+    pub icons: IconSet,
+
+    /// Destination for archived targets
+    pub archive_dir: PathBuf,
+
+    /// Cleaning actions are disabled (dry run, or another instance holds the lock)
+    pub read_only: bool,
+
+    /// Details pane shows the reclaimable-space-by-age chart instead
+    pub show_age_graph: bool,
+
+    /// List view shows paths relative to the scan root instead of the final component
+    show_relative_paths: bool,
+
+    /// Previous cleans, for "cleaned N days ago" notes in the details pane
+    pub history: CleanHistory,
+
+    /// Bumped whenever row contents (text or checkboxes) change
+    rows_revision: u64,
+
+    /// Rows built by the last frame, reused until `rows_revision` changes
+    pub row_cache: RefCell<RowCache>,
+
+    /// A macro is being recorded (`m`)
+    pub recording_macro: bool,
+
+    /// Colors for the terminal's background
+    pub palette: Palette,
+
+    /// Indexed projects (root, strategy) the running scan hasn't confirmed yet
+    unverified: HashSet<(PathBuf, String)>,
+
+    /// Preselects projects when the scan completes
+    path_rules: PathRules,
+
+    /// Preselects its plan when the scan completes
+    retention: Option<RetentionPolicy>,
+
+    /// Why the retention plan cleans a project (root, strategy)
+    retention_reasons: HashMap<(PathBuf, String), String>,
+
+    /// Projects (root, strategy) listed by the running scan but not yet sized
+    sizing: HashSet<(PathBuf, String)>,
+
+    /// Lets the running scan abandon sizing of a project (`S`)
+    pub skip_sizing: SkipSizing,
+
+    /// Projects kept in `all_projects` before the smallest are spilled
+    max_projects: usize,
+
+    /// Projects moved to disk because there were more than `max_projects`
+    spill: Spill,
+
+    /// Spilled projects the current filter shows, read back from disk
+    paged_in: Vec<CleanableProject>,
 }
 
 impl AppState {
-    pub fn new(scan_path: PathBuf) -> Self {
-        Self {
+    pub fn new(scan_path: PathBuf, options: TuiOptions) -> Self {
+        let unverified = options
+            .indexed
+            .iter()
+            .map(|p| (p.root_path.clone(), p.strategy_name.clone()))
+            .collect();
+
+        let mut state = Self {
             scan_path,
-            all_projects: Vec::new(),
+            all_projects: options.indexed,
             visible_projects: Vec::new(),
             selected_index: 0,
             selected_projects: HashSet::new(),
+            selection: SelectionTotals::default(),
             sort_mode: SortMode::SizeDesc,
             filter_mode: FilterMode::All,
             view_mode: ViewMode::List,
             tree_roots: Vec::new(),
             show_confirmation: false,
             deletion_confirmed: false,
+            pending_action: CleanAction::Delete,
+            archive_estimates: Vec::new(),
             scanning: true,
             scanning_path: String::new(),
+            scan_phase: None,
+            scan_error: None,
             spinner_index: 0,
+            units: options.units,
+            icons: options.icons,
+            archive_dir: options.archive_dir,
+            read_only: options.read_only,
+            show_age_graph: false,
+            show_relative_paths: false,
+            history: CleanHistory::load(),
+            rows_revision: 0,
+            row_cache: RefCell::new(RowCache::default()),
+            recording_macro: false,
+            palette: Palette::DARK,
+            unverified,
+            path_rules: options.path_rules,
+            retention: options.retention,
+            retention_reasons: HashMap::new(),
+            sizing: HashSet::new(),
+            skip_sizing: SkipSizing::default(),
+            max_projects: options.max_projects.max(1),
+            spill: Spill::default(),
+            paged_in: Vec::new(),
+        };
+        state.refresh_visible();
+        state
+    }
+
+    /// Lists a project the scan is about to size, unless it's already
+    /// listed (e.g., from the index)
+    pub fn add_pending(&mut self, project: CleanableProject) {
+        self.sizing
+            .insert((project.root_path.clone(), project.strategy_name.clone()));
+
+        let listed = self
+            .all_projects
+            .iter()
+            .any(|p| p.root_path == project.root_path && p.strategy_name == project.strategy_name);
+        if !listed {
+            self.all_projects.push(project);
+            self.spill_excess();
         }
+        self.refresh_visible();
     }
 
+    /// Adds a scanned project, replacing its indexed entry if there is one
     pub fn add_project(&mut self, project: CleanableProject) {
-        self.all_projects.push(project);
+        let key = (project.root_path.clone(), project.strategy_name.clone());
+        self.unverified.remove(&key);
+        self.sizing.remove(&key);
+
+        match self
+            .all_projects
+            .iter_mut()
+            .find(|p| p.root_path == project.root_path && p.strategy_name == project.strategy_name)
+        {
+            Some(existing) => *existing = project,
+            None => {
+                self.all_projects.push(project);
+                self.spill_excess();
+            }
+        }
         self.refresh_visible();
     }
 
+    /// Moves the smallest projects to disk once more than `max_projects` are
+    /// in memory, down to nine tenths of it so this doesn't run per project.
+    /// Projects still being sized or confirmed by the scan stay in memory.
+    fn spill_excess(&mut self) {
+        if self.all_projects.len() <= self.max_projects {
+            return;
+        }
+        let (mut settled, pinned): (Vec<CleanableProject>, Vec<CleanableProject>) =
+            std::mem::take(&mut self.all_projects).into_iter().partition(|p| {
+                let key = (p.root_path.clone(), p.strategy_name.clone());
+                !self.sizing.contains(&key) && !self.unverified.contains(&key)
+            });
+        settled.sort_by_key(|p| std::cmp::Reverse(p.total_size));
+        let keep = (self.max_projects - self.max_projects / 10).saturating_sub(pinned.len());
+        let spilled = settled.split_off(keep.min(settled.len()));
+
+        // Spilled projects the filter shows stay on screen
+        if self.filter_mode != FilterMode::All {
+            let room = self.max_projects.saturating_sub(self.paged_in.len());
+            let filter = self.filter_mode;
+            self.paged_in
+                .extend(spilled.iter().filter(|p| filter.matches(p)).take(room).cloned());
+        }
+        self.spill.push(spilled);
+        self.all_projects = pinned;
+        self.all_projects.extend(settled);
+    }
+
+    /// Projects moved to disk to stay within `max_projects`
+    pub fn spill(&self) -> &Spill {
+        &self.spill
+    }
+
+    /// Reads back the spilled projects the current filter shows, as many as
+    /// fit in memory. Browsing every project at once would defeat the limit.
+    fn page_in_spilled(&mut self) {
+        self.paged_in = if self.filter_mode == FilterMode::All || self.spill.count == 0 {
+            Vec::new()
+        } else {
+            let filter = self.filter_mode;
+            self.spill.load(|p| filter.matches(p), self.max_projects)
+        };
+    }
+
+    /// Whether the running scan is still sizing this project
+    pub fn is_sizing(&self, project: &CleanableProject) -> bool {
+        !self.sizing.is_empty()
+            && self
+                .sizing
+                .contains(&(project.root_path.clone(), project.strategy_name.clone()))
+    }
+
+    /// Which part of the TUI receives input
+    pub fn layer(&self) -> Layer {
+        if self.scan_error.is_some() {
+            Layer::Error
+        } else if self.show_confirmation {
+            Layer::Confirm
+        } else {
+            Layer::Browse
+        }
+    }
+
+    /// What the hint bar needs to know to show the keys that work right now
+    pub fn hint_context(&self) -> HintContext {
+        HintContext {
+            layer: self.layer(),
+            tree_view: self.view_mode == ViewMode::Tree,
+            read_only: self.read_only,
+            on_project: self.current_project().is_some(),
+            sizing: self.current_project().is_some_and(|p| self.is_sizing(p)),
+            recording: self.recording_macro,
+            age_graph: self.show_age_graph,
+            nothing_selected: self.selected_count() == 0,
+            unverified_selected: self.selection_unverified(),
+        }
+    }
+
+    /// Abandons sizing of the current project; the scan then reports it
+    /// with an unknown size
+    pub fn skip_current_sizing(&mut self) {
+        if let Some(project) = self.current_project().filter(|p| self.is_sizing(p)) {
+            self.skip_sizing.skip(&project.root_path);
+        }
+    }
+
     pub fn finish_scan(&mut self) {
         self.scanning = false;
+        self.sizing.clear();
+
+        // Indexed projects the scan didn't find are gone (cleaned or deleted)
+        let unverified = std::mem::take(&mut self.unverified);
+        self.all_projects
+            .retain(|p| !unverified.contains(&(p.root_path.clone(), p.strategy_name.clone())));
+        if let Some(policy) = &self.retention {
+            self.retention_reasons = policy
+                .plan(&self.all_projects)
+                .into_iter()
+                .map(|planned| {
+                    let key = (planned.project.root_path.clone(), planned.project.strategy_name.clone());
+                    (key, planned.reason)
+                })
+                .collect();
+        }
+        self.refresh_visible();
+        self.apply_auto_selection();
+    }
+
+    /// Why the retention plan cleans `project`, if it does
+    pub fn retention_reason(&self, project: &CleanableProject) -> Option<&str> {
+        self.retention_reasons
+            .get(&(project.root_path.clone(), project.strategy_name.clone()))
+            .map(String::as_str)
+    }
+
+    /// Selects projects that a path rule marks for auto-selection, and those
+    /// in the retention plan unless a path rule keeps them out
+    fn apply_auto_selection(&mut self) {
+        let rules = &self.path_rules;
+        let reasons = &self.retention_reasons;
+        let auto_selected = |project: &CleanableProject| match rules.auto_select(&project.root_path) {
+            _ if project.manual_only => false,
+            Some(select) => select,
+            None => reasons.contains_key(&(project.root_path.clone(), project.strategy_name.clone())),
+        };
+        match self.view_mode {
+            ViewMode::List => {
+                for project in &self.visible_projects {
+                    if auto_selected(project) {
+                        self.selected_projects
+                            .insert((project.root_path.clone(), project.strategy_name.clone()));
+                    }
+                }
+            }
+            ViewMode::Tree => check_auto_selected(&mut self.tree_roots, &auto_selected),
+        }
+        self.recount_selection();
+        self.rows_revision += 1;
+    }
+
+    pub fn fail_scan(&mut self, error: String) {
+        self.scanning = false;
+        self.scan_error = Some(error);
+    }
+
+    /// Discards partial results before scanning again
+    pub fn restart_scan(&mut self) {
+        self.all_projects.clear();
+        self.spill.clear();
+        self.paged_in.clear();
+        self.selected_projects.clear();
+        self.unverified.clear();
+        self.sizing.clear();
+        self.skip_sizing = SkipSizing::default();
+        self.selected_index = 0;
+        self.scan_error = None;
+        self.scan_phase = None;
+        self.scanning = true;
+        self.scanning_path.clear();
         self.refresh_visible();
     }
 
@@ -145,6 +652,16 @@ impl AppState {
                 node.collapsed = !node.collapsed;
             }
         }
+        self.rows_revision += 1;
+    }
+
+    pub fn toggle_relative_paths(&mut self) {
+        self.show_relative_paths = !self.show_relative_paths;
+        self.rows_revision += 1;
+    }
+
+    pub fn rows_revision(&self) -> u64 {
+        self.rows_revision
     }
 
     pub fn get_flat_tree(&self) -> Vec<crate::tui::tree::TreeFlatNode<'_>> {
@@ -181,24 +698,37 @@ impl AppState {
                 if self.visible_projects.is_empty() {
                     return;
                 }
-                if self.selected_projects.contains(&self.selected_index) {
-                    self.selected_projects.remove(&self.selected_index);
+                let project = &self.visible_projects[self.selected_index];
+                let size = project.total_size;
+                let key = (project.root_path.clone(), project.strategy_name.clone());
+                if self.selected_projects.remove(&key) {
+                    self.selection.remove(1, size);
                 } else {
-                    self.selected_projects.insert(self.selected_index);
+                    self.selected_projects.insert(key);
+                    self.selection.add(1, size);
                 }
             }
             ViewMode::Tree => {
                 if let Some(node) = self.get_node_at_mut(self.selected_index) {
                     let new_state = !node.checked;
-                    node.set_checked(new_state);
+                    let (count, size) = node.set_checked(new_state);
+                    if new_state {
+                        self.selection.add(count, size);
+                    } else {
+                        self.selection.remove(count, size);
+                    }
                 }
             }
         }
+        self.rows_revision += 1;
     }
 
     pub fn is_selected(&self, index: usize) -> bool {
         match self.view_mode {
-            ViewMode::List => self.selected_projects.contains(&index),
+            ViewMode::List => self
+                .visible_projects
+                .get(index)
+                .is_some_and(|p| self.is_project_selected(p)),
             ViewMode::Tree => {
                 // For rendering tree, we need to know if the Nth visible node is checked.
                 // This is a bit inefficient to traverse O(N) for every line render.
@@ -211,34 +741,86 @@ impl AppState {
     }
 
     pub fn selected_count(&self) -> usize {
-        match self.view_mode {
-            ViewMode::List => self.selected_projects.len(),
-            ViewMode::Tree => {
-                // Count all checked projects in the tree (recursively)
-                count_checked_projects(&self.tree_roots)
-            }
+        self.selection.count
+    }
+
+    /// Name of a project in List view: its final path component, or its path
+    /// relative to the scan root when relative paths are on
+    pub fn display_path(&self, project: &CleanableProject) -> String {
+        let file_name = || {
+            project
+                .root_path
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        };
+
+        if !self.show_relative_paths {
+            return file_name();
         }
+
+        match project.root_path.strip_prefix(&self.scan_path) {
+            Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => project.root_path.display().to_string(),
+        }
+    }
+
+    /// Reclaimable space of all discovered projects, bucketed by last-modified age
+    pub fn age_summary(&self) -> Vec<AgeSummary> {
+        summarize_by_age(&self.all_projects, std::time::SystemTime::now())
     }
 
     pub fn total_selected_size(&self) -> u64 {
-        match self.view_mode {
-            ViewMode::List => self.selected_projects
-                .iter()
-                .filter_map(|&idx| self.visible_projects.get(idx))
-                .map(|p| p.total_size)
-                .sum(),
-            ViewMode::Tree => {
-                // Sum size of all checked projects in tree
-                sum_checked_size(&self.tree_roots)
+        self.selection.size
+    }
+
+    /// Recomputes the running selection totals after the list or tree is rebuilt
+    fn recount_selection(&mut self) {
+        self.selection = match self.view_mode {
+            ViewMode::List => {
+                let (count, size) = self
+                    .visible_projects
+                    .iter()
+                    .filter(|p| self.is_project_selected(p))
+                    .fold((0, 0), |(count, size), p| (count + 1, size + p.total_size));
+                SelectionTotals { count, size }
             }
-        }
+            ViewMode::Tree => SelectionTotals {
+                count: count_checked_projects(&self.tree_roots),
+                size: sum_checked_size(&self.tree_roots),
+            },
+        };
     }
 
+    /// Whether the selection includes indexed projects the running scan
+    /// hasn't confirmed yet. Their targets may have changed since they were
+    /// indexed, and have no snapshot to catch that, so they can't be cleaned.
+    pub fn selection_unverified(&self) -> bool {
+        !self.unverified.is_empty()
+            && self
+                .get_selected_projects()
+                .iter()
+                .any(|p| self.unverified.contains(&(p.root_path.clone(), p.strategy_name.clone())))
+    }
+
+    /// Whether a List-mode project is selected
+    fn is_project_selected(&self, project: &CleanableProject) -> bool {
+        !self.selected_projects.is_empty()
+            && self
+                .selected_projects
+                .contains(&(project.root_path.clone(), project.strategy_name.clone()))
+    }
+
+    /// Selected projects as they are now listed; selected projects no longer
+    /// shown (filtered out, spilled, or gone) aren't included
     pub fn get_selected_projects(&self) -> Vec<CleanableProject> {
         match self.view_mode {
-            ViewMode::List => self.selected_projects
+            ViewMode::List => self
+                .visible_projects
                 .iter()
-                .filter_map(|&idx| self.visible_projects.get(idx))
+                .filter(|p| self.is_project_selected(p))
                 .cloned()
                 .collect(),
             ViewMode::Tree => {
@@ -249,6 +831,26 @@ impl AppState {
         }
     }
 
+    /// Samples selected projects to estimate archive size and time
+    pub fn estimate_archive(&mut self) {
+        self.archive_estimates = self
+            .get_selected_projects()
+            .iter()
+            .map(|p| (p.root_path.clone(), estimate_compression(p)))
+            .collect();
+        self.archive_estimates
+            .sort_by_key(|(_, e)| std::cmp::Reverse(e.original_size));
+    }
+
+    /// Highest target risk among the selected projects
+    pub fn selected_max_risk(&self) -> RiskLevel {
+        self.get_selected_projects()
+            .iter()
+            .map(|p| p.risk_level)
+            .max()
+            .unwrap_or(RiskLevel::Low)
+    }
+
     pub fn confirm_deletion(&mut self) {
         self.deletion_confirmed = true;
     }
@@ -279,6 +881,7 @@ impl AppState {
         self.filter_mode = self.filter_mode.next();
         self.selected_index = 0;
         self.selected_projects.clear();
+        self.page_in_spilled();
         self.refresh_visible();
     }
 
@@ -288,13 +891,8 @@ impl AppState {
         let mut filtered: Vec<CleanableProject> = self
             .all_projects
             .iter()
-            .filter(|p| match self.filter_mode {
-                FilterMode::All => true,
-                FilterMode::NodeJs => p.strategy_name == "Node.js",
-                FilterMode::Rust => p.strategy_name == "Rust",
-                FilterMode::Flutter => p.strategy_name == "Flutter",
-                FilterMode::Android => p.strategy_name == "Android",
-            })
+            .chain(&self.paged_in)
+            .filter(|p| self.filter_mode.matches(p))
             .cloned()
             .collect();
 
@@ -327,6 +925,9 @@ impl AppState {
             }
         }
 
+        self.recount_selection();
+        self.rows_revision += 1;
+
         // Clamp selected index
         let count = self.visible_count();
         if self.selected_index >= count && count > 0 {
@@ -398,3 +999,13 @@ fn collect_checked_projects(nodes: &[TreeNode], out: &mut Vec<CleanableProject>)
         collect_checked_projects(&node.children, out);
     }
 }
+
+/// Checks every project node that is auto-selected
+fn check_auto_selected(nodes: &mut [TreeNode], auto_selected: &dyn Fn(&CleanableProject) -> bool) {
+    for node in nodes {
+        if node.project.as_ref().is_some_and(auto_selected) {
+            node.checked = true;
+        }
+        check_auto_selected(&mut node.children, auto_selected);
+    }
+}
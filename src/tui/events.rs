@@ -2,18 +2,44 @@ use anyhow::Result;
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use std::time::Duration;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum AppEvent {
     Quit,
     MoveUp,
     MoveDown,
     ToggleSelection,
     ConfirmAction,
+    ArchiveAction,
     ToggleSort,
     CycleFilter,
     CloseModal,
     ToggleViewMode,
     ToggleExpand,
+    /// Restart a failed scan
+    Retry,
+    /// Terminal resized; only triggers a redraw
+    Resize,
+    ToggleAgeGraph,
+    ToggleRelativePaths,
+    RepeatLast,
+    ToggleRecording,
+    ReplayMacro,
+    /// Stop sizing the current project, leaving its size unknown
+    SkipSizing,
+    /// Suspend the TUI for a shell in the current project
+    OpenShell,
+}
+
+impl AppEvent {
+    /// Events that can be recorded in a macro and replayed
+    pub fn is_replayable(&self) -> bool {
+        matches!(self, Self::MoveUp | Self::MoveDown | Self::ToggleSelection | Self::ToggleExpand)
+    }
+
+    /// Events that `.` repeats
+    pub fn changes_selection(&self) -> bool {
+        matches!(self, Self::ToggleSelection)
+    }
 }
 
 pub fn poll_event(timeout: Duration) -> Result<Option<AppEvent>> {
@@ -21,40 +47,244 @@ pub fn poll_event(timeout: Duration) -> Result<Option<AppEvent>> {
         return Ok(None);
     }
 
-    if let Event::Key(key) = event::read()? {
-        return Ok(handle_key(key));
+    match event::read()? {
+        Event::Key(key) => Ok(handle_key(key)),
+        Event::Resize(_, _) => Ok(Some(AppEvent::Resize)),
+        _ => Ok(None),
     }
-
-    Ok(None)
 }
 
 fn handle_key(key: KeyEvent) -> Option<AppEvent> {
-    match (key.code, key.modifiers) {
-        // Quit
-        (KeyCode::Char('q'), _) | (KeyCode::Esc, _) => Some(AppEvent::Quit),
-        (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(AppEvent::Quit),
+    KEYMAP
+        .iter()
+        .find(|binding| {
+            binding
+                .keys
+                .iter()
+                .any(|&(code, modifiers)| key.code == code && key.modifiers.contains(modifiers))
+        })
+        .map(|binding| binding.event.clone())
+}
 
-        // Navigation
-        (KeyCode::Up, _) | (KeyCode::Char('k'), _) => Some(AppEvent::MoveUp),
-        (KeyCode::Down, _) | (KeyCode::Char('j'), _) => Some(AppEvent::MoveDown),
+/// Keys that trigger an event; modifiers listed here must be held, others are ignored
+struct Binding {
+    keys: &'static [(KeyCode, KeyModifiers)],
+    event: AppEvent,
+}
 
-        // Selection
-        (KeyCode::Char(' '), _) => Some(AppEvent::ToggleSelection),
+const ANY: KeyModifiers = KeyModifiers::NONE;
 
-        // Actions
-        (KeyCode::Enter, _) | (KeyCode::Char('y'), _) => Some(AppEvent::ConfirmAction),
+/// Every key the TUI handles. Input handling and the hint bar both read it,
+/// so the hints can't drift from the bindings.
+const KEYMAP: &[Binding] = &[
+    // Navigation
+    Binding {
+        keys: &[(KeyCode::Up, ANY), (KeyCode::Char('k'), ANY)],
+        event: AppEvent::MoveUp,
+    },
+    Binding {
+        keys: &[(KeyCode::Down, ANY), (KeyCode::Char('j'), ANY)],
+        event: AppEvent::MoveDown,
+    },
+    // Selection
+    Binding {
+        keys: &[(KeyCode::Char(' '), ANY)],
+        event: AppEvent::ToggleSelection,
+    },
+    // Actions
+    Binding {
+        keys: &[(KeyCode::Enter, ANY), (KeyCode::Char('y'), ANY)],
+        event: AppEvent::ConfirmAction,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('a'), ANY)],
+        event: AppEvent::ArchiveAction,
+    },
+    // Tree View controls
+    Binding {
+        keys: &[(KeyCode::Tab, ANY)],
+        event: AppEvent::ToggleViewMode,
+    },
+    Binding {
+        keys: &[(KeyCode::Right, ANY), (KeyCode::Char('l'), ANY)],
+        event: AppEvent::ToggleExpand,
+    },
+    // Filters & Sorts
+    Binding {
+        keys: &[(KeyCode::Char('s'), ANY)],
+        event: AppEvent::ToggleSort,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('S'), ANY)],
+        event: AppEvent::SkipSizing,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('f'), ANY)],
+        event: AppEvent::CycleFilter,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('g'), ANY)],
+        event: AppEvent::ToggleAgeGraph,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('p'), ANY)],
+        event: AppEvent::ToggleRelativePaths,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('o'), ANY)],
+        event: AppEvent::OpenShell,
+    },
+    // Repeat & macros
+    Binding {
+        keys: &[(KeyCode::Char('.'), ANY)],
+        event: AppEvent::RepeatLast,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('m'), ANY)],
+        event: AppEvent::ToggleRecording,
+    },
+    Binding {
+        keys: &[(KeyCode::Char('M'), ANY)],
+        event: AppEvent::ReplayMacro,
+    },
+    // Error modal
+    Binding {
+        keys: &[(KeyCode::Char('r'), ANY)],
+        event: AppEvent::Retry,
+    },
+    // Modal close
+    Binding {
+        keys: &[(KeyCode::Char('n'), ANY)],
+        event: AppEvent::CloseModal,
+    },
+    // Quit
+    Binding {
+        keys: &[
+            (KeyCode::Char('q'), ANY),
+            (KeyCode::Esc, ANY),
+            (KeyCode::Char('c'), KeyModifiers::CONTROL),
+        ],
+        event: AppEvent::Quit,
+    },
+];
 
-        // Filters & Sorts
-        (KeyCode::Char('s'), _) => Some(AppEvent::ToggleSort),
-        (KeyCode::Char('f'), _) => Some(AppEvent::CycleFilter),
+/// Which part of the TUI receives input; each gives keys their own meaning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// The project list or tree
+    Browse,
+    /// The clean/archive confirmation modal
+    Confirm,
+    /// The failed scan modal
+    Error,
+}
 
-        // Modal close
-        (KeyCode::Char('n'), _) => Some(AppEvent::CloseModal),
+/// What the TUI shows right now, which decides the keys worth hinting at
+#[derive(Debug, Clone, Copy)]
+pub struct HintContext {
+    pub layer: Layer,
+    pub tree_view: bool,
+    pub read_only: bool,
+    /// A project is highlighted (not a folder, or nothing)
+    pub on_project: bool,
+    /// The highlighted project is being sized
+    pub sizing: bool,
+    pub recording: bool,
+    pub age_graph: bool,
+    /// Nothing is selected, so confirming only closes the modal
+    pub nothing_selected: bool,
+    /// The selection includes indexed projects the scan hasn't confirmed
+    /// yet, so cleaning waits for it
+    pub unverified_selected: bool,
+}
+
+/// A hint bar entry: keys, and what they do
+pub struct Hint {
+    pub keys: String,
+    pub action: &'static str,
+}
+
+impl AppEvent {
+    /// What the event does in `context`, or `None` if nothing
+    fn describe(&self, context: &HintContext) -> Option<&'static str> {
+        match context.layer {
+            Layer::Error => match self {
+                Self::Retry => Some("Retry scan"),
+                Self::Quit => Some("Quit"),
+                _ => None,
+            },
+            Layer::Confirm => match self {
+                Self::ConfirmAction | Self::CloseModal | Self::Quit if context.nothing_selected => Some("Close"),
+                Self::ConfirmAction => Some("Confirm"),
+                Self::CloseModal | Self::Quit => Some("Cancel"),
+                _ => None,
+            },
+            Layer::Browse => match self {
+                Self::MoveUp => Some("Up"),
+                Self::MoveDown => Some("Down"),
+                Self::ToggleSelection => Some("Toggle selection"),
+                Self::ConfirmAction | Self::ArchiveAction if context.unverified_selected && !context.read_only => {
+                    Some("Wait for scan to confirm selection")
+                }
+                Self::ConfirmAction => (!context.read_only).then_some("Clean selected"),
+                Self::ArchiveAction => (!context.read_only).then_some("Archive selected"),
+                Self::ToggleViewMode if context.tree_view => Some("List view"),
+                Self::ToggleViewMode => Some("Tree view"),
+                Self::ToggleExpand => context.tree_view.then_some("Expand/collapse"),
+                Self::ToggleSort => Some("Toggle sort"),
+                Self::SkipSizing => context.sizing.then_some("Skip sizing"),
+                Self::CycleFilter => Some("Cycle filter"),
+                Self::ToggleAgeGraph if context.age_graph => Some("Project details"),
+                Self::ToggleAgeGraph => Some("Space by age"),
+                Self::ToggleRelativePaths => Some("Toggle relative paths"),
+                Self::OpenShell => context.on_project.then_some("Shell here"),
+                Self::RepeatLast => Some("Repeat last toggle"),
+                Self::ToggleRecording if context.recording => Some("Stop recording"),
+                Self::ToggleRecording => Some("Record macro"),
+                // Replaying while recording is ignored
+                Self::ReplayMacro => (!context.recording).then_some("Replay macro"),
+                Self::Quit => Some("Quit"),
+                Self::CloseModal | Self::Retry | Self::Resize => None,
+            },
+        }
+    }
+}
 
-        // Tree View controls
-        (KeyCode::Tab, _) => Some(AppEvent::ToggleViewMode),
-        (KeyCode::Right, _) | (KeyCode::Char('l'), _) => Some(AppEvent::ToggleExpand),
+/// Keys that do something in `context`, in keymap order. Keys with the same
+/// effect share a hint.
+pub fn hints(context: &HintContext) -> Vec<Hint> {
+    let mut hints: Vec<Hint> = Vec::new();
+    for binding in KEYMAP {
+        let Some(action) = binding.event.describe(context) else {
+            continue;
+        };
+        let keys = binding
+            .keys
+            .iter()
+            .map(|&(code, modifiers)| key_label(code, modifiers))
+            .collect::<Vec<_>>()
+            .join("/");
+        match hints.iter_mut().find(|hint| hint.action == action) {
+            Some(hint) => hint.keys = format!("{}/{}", hint.keys, keys),
+            None => hints.push(Hint { keys, action }),
+        }
+    }
+    hints
+}
 
-        _ => None,
+fn key_label(code: KeyCode, modifiers: KeyModifiers) -> String {
+    let key = match code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        other => other.to_string(),
+    };
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl+{}", key)
+    } else {
+        key
     }
 }
@@ -4,15 +4,22 @@ pub struct AppLayout {
     pub project_tree: Rect,
     pub details_pane: Rect,
     pub action_pane: Rect,
+    pub hint_bar: Rect,
 }
 
 impl AppLayout {
-    pub fn new(area: Rect) -> Self {
+    /// Layout with `hint_lines` rows at the bottom for the hint bar
+    pub fn new(area: Rect, hint_lines: u16) -> Self {
+        let outer_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(hint_lines)])
+            .split(area);
+
         // Main horizontal split: 60% left (tree), 40% right (details + action)
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-            .split(area);
+            .split(outer_chunks[0]);
 
         // Right side vertical split: 50% details, 50% action
         let right_chunks = Layout::default()
@@ -24,6 +31,7 @@ impl AppLayout {
             project_tree: main_chunks[0],
             details_pane: right_chunks[0],
             action_pane: right_chunks[1],
+            hint_bar: outer_chunks[1],
         }
     }
 }
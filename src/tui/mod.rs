@@ -2,75 +2,153 @@ mod tree;
 mod app_state;
 mod events;
 mod layout;
+mod repeat;
+mod spill;
+mod theme;
 mod widgets;
 
-pub use app_state::AppState;
+pub use app_state::{AppState, CleanAction, TuiOptions};
+pub use spill::DEFAULT_MAX_PROJECTS;
+pub use theme::Theme;
 use anyhow::Result;
 use crossterm::{
+    event::{self, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use events::{poll_event, AppEvent};
+use events::{poll_event, AppEvent, Layer};
 use layout::AppLayout;
+use repeat::Repeater;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::{
-    io,
+    ffi::OsString,
+    io::{self, IsTerminal, Write},
+    process::Command,
     sync::mpsc::Receiver,
     time::Duration,
 };
-use crate::scanner::ScanEvent;
+use crate::crash;
+use crate::scanner::{ScanEvent, SkipSizing};
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-pub fn run_tui(rx: Receiver<ScanEvent>, scan_path: PathBuf) -> Result<AppState> {
+/// Input poll timeout while scanning (drives the spinner and live results)
+const ACTIVE_POLL: Duration = Duration::from_millis(100);
+
+/// Input poll timeout when idle; nothing changes without input
+const IDLE_POLL: Duration = Duration::from_millis(500);
+
+/// Whether the TUI can run here: stdout is a terminal that isn't `TERM=dumb`
+/// and supports raw mode. Stdin may be a pipe (`--paths-from -`); input is
+/// then read from the controlling terminal.
+pub fn is_supported() -> bool {
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+    if std::env::var("TERM").is_ok_and(|term| term == "dumb") {
+        return false;
+    }
+    enable_raw_mode().and_then(|()| disable_raw_mode()).is_ok()
+}
+
+/// Runs the TUI until the user quits. `start_scan` starts a background scan
+/// that can be told to skip sizing projects, and is called again when the
+/// user retries a failed scan.
+pub fn run_tui(
+    start_scan: impl Fn(SkipSizing) -> Receiver<ScanEvent>,
+    scan_path: PathBuf,
+    options: TuiOptions,
+) -> Result<AppState> {
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    crash::set_tui_active(true);
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut state = AppState::new(scan_path);
+    // Before any input is read: the terminal's answer arrives on stdin
+    let palette = theme::Palette::resolve(options.theme);
+    let mut state = AppState::new(scan_path, options);
+    state.palette = palette;
+    let mut rx = start_scan(state.skip_sizing.clone());
     let mut should_quit = false;
+    let mut repeater = Repeater::default();
+    let mut needs_redraw = true;
 
     // Main event loop
     while !should_quit {
         // Check for scan events (non-blocking) - Drain all pending events to avoid lag
         while let Ok(scan_event) = rx.try_recv() {
+            needs_redraw = true;
+            crash::record_event(&scan_event);
             match scan_event {
+                ScanEvent::ProjectPending(project) => state.add_pending(project),
                 ScanEvent::ProjectFound(project) => {
                     state.add_project(project);
                 }
+                ScanEvent::Phase(phase) => state.scan_phase = Some(phase),
+                ScanEvent::Discovered(_) | ScanEvent::AlreadyClean(_) => {}
                 ScanEvent::Scanning(path) => {
                     state.scanning_path = path;
                 }
+                ScanEvent::Analyzing(path) => {
+                    state.scanning_path = format!("Analyzing: {}", path.display());
+                }
                 ScanEvent::Complete => {
                     state.finish_scan();
                 }
+                ScanEvent::Failed(error) => state.fail_scan(error),
             }
         }
 
-        // Render UI
-        terminal.draw(|f| {
-            let app_layout = AppLayout::new(f.area());
+        // The spinner animates while scanning; otherwise only redraw on changes
+        if state.scanning {
+            needs_redraw = true;
+            state.spinner_index = state.spinner_index.wrapping_add(1);
+        }
 
-            widgets::render_project_tree(f, app_layout.project_tree, &state);
-            widgets::render_details_pane(f, app_layout.details_pane, &state);
-            widgets::render_action_pane(f, app_layout.action_pane, &state);
+        // Render UI
+        if needs_redraw {
+            terminal.draw(|f| {
+                let hints = widgets::hint_lines(&state, f.area().width);
+                let app_layout = AppLayout::new(f.area(), hints.len() as u16);
 
-            if state.show_confirmation {
-                widgets::render_confirmation_modal(f, &state);
-            }
-        })?;
+                widgets::render_project_tree(f, app_layout.project_tree, &state);
+                if state.show_age_graph {
+                    widgets::render_age_graph(f, app_layout.details_pane, &state);
+                } else {
+                    widgets::render_details_pane(f, app_layout.details_pane, &state);
+                }
+                widgets::render_action_pane(f, app_layout.action_pane, &state);
+                widgets::render_hint_bar(f, app_layout.hint_bar, hints);
 
-        // Update spinner (simple ticker)
-        state.spinner_index = state.spinner_index.wrapping_add(1);
+                if state.show_confirmation {
+                    widgets::render_confirmation_modal(f, &state);
+                }
+                if state.scan_error.is_some() {
+                    widgets::render_error_modal(f, &state);
+                }
+            })?;
+            needs_redraw = false;
+        }
 
         // Handle input
-        if let Some(app_event) = poll_event(Duration::from_millis(100))? {
-            if state.show_confirmation {
+        let timeout = if state.scanning { ACTIVE_POLL } else { IDLE_POLL };
+        if let Some(app_event) = poll_event(timeout)? {
+            needs_redraw = true;
+            match state.layer() {
+                // Scan failed: retry or quit
+                Layer::Error => match app_event {
+                    AppEvent::Retry => {
+                        state.restart_scan();
+                        rx = start_scan(state.skip_sizing.clone());
+                    }
+                    AppEvent::Quit => should_quit = true,
+                    _ => {}
+                },
                 // In confirmation modal
-                match app_event {
+                Layer::Confirm => match app_event {
                     AppEvent::ConfirmAction => {
                         // User pressed 'y' or Enter - confirm deletion
                         if state.selected_count() > 0 {
@@ -84,22 +162,56 @@ pub fn run_tui(rx: Receiver<ScanEvent>, scan_path: PathBuf) -> Result<AppState>
                         state.show_confirmation = false;
                     }
                     _ => {}
-                }
-            } else {
+                },
                 // Normal navigation
-                match app_event {
-                    AppEvent::Quit => should_quit = true,
-                    AppEvent::MoveUp => state.move_up(),
-                    AppEvent::MoveDown => state.move_down(),
-                    AppEvent::ToggleSelection => state.toggle_selection(),
-                    AppEvent::ConfirmAction => {
-                        state.show_confirmation = true;
+                Layer::Browse => {
+                    repeater.observe(&app_event);
+                    match app_event {
+                        AppEvent::Quit => should_quit = true,
+                        AppEvent::MoveUp
+                        | AppEvent::MoveDown
+                        | AppEvent::ToggleSelection
+                        | AppEvent::ToggleExpand => apply_replayable(&mut state, &app_event),
+                        AppEvent::RepeatLast => {
+                            for event in repeater.repeat_last() {
+                                apply_replayable(&mut state, &event);
+                            }
+                        }
+                        AppEvent::ToggleRecording => {
+                            repeater.toggle_recording();
+                            state.recording_macro = repeater.is_recording();
+                        }
+                        // Replaying while recording would make the macro recursive
+                        AppEvent::ReplayMacro if repeater.is_recording() => {}
+                        AppEvent::ReplayMacro => {
+                            for event in repeater.replay_macro() {
+                                apply_replayable(&mut state, &event);
+                            }
+                        }
+                        AppEvent::ConfirmAction | AppEvent::ArchiveAction
+                            if state.read_only || state.selection_unverified() => {}
+                        AppEvent::ConfirmAction => {
+                            state.pending_action = CleanAction::Delete;
+                            state.show_confirmation = true;
+                        }
+                        AppEvent::ArchiveAction => {
+                            state.pending_action = CleanAction::Archive;
+                            state.estimate_archive();
+                            state.show_confirmation = true;
+                        }
+                        AppEvent::ToggleSort => state.toggle_sort(),
+                        AppEvent::CycleFilter => state.cycle_filter(),
+                        AppEvent::ToggleViewMode => state.toggle_view_mode(),
+                        AppEvent::ToggleAgeGraph => state.show_age_graph = !state.show_age_graph,
+                        AppEvent::ToggleRelativePaths => state.toggle_relative_paths(),
+                        AppEvent::SkipSizing => state.skip_current_sizing(),
+                        AppEvent::OpenShell => {
+                            if let Some(root) = state.current_project().map(|p| p.root_path.clone()) {
+                                open_shell(&mut terminal, &root)?;
+                            }
+                        }
+                        _ => {}
                     }
-                    AppEvent::ToggleSort => state.toggle_sort(),
-                    AppEvent::CycleFilter => state.cycle_filter(),
-                    AppEvent::ToggleViewMode => state.toggle_view_mode(),
-                    AppEvent::ToggleExpand => state.toggle_expand(),
-                    _ => {}
                 }
             }
         }
@@ -108,7 +220,51 @@ pub fn run_tui(rx: Receiver<ScanEvent>, scan_path: PathBuf) -> Result<AppState>
     // Cleanup
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    crash::set_tui_active(false);
     terminal.show_cursor()?;
 
     Ok(state)
 }
+
+/// Suspends the TUI and runs the user's shell in `dir` until it exits. The
+/// scan keeps running; its events wait in the channel.
+fn open_shell(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, dir: &Path) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    crash::set_tui_active(false);
+    terminal.show_cursor()?;
+
+    let shell = user_shell();
+    println!("spektr: shell in {} (exit to return)", dir.display());
+    let status = Command::new(&shell).current_dir(dir).status();
+
+    enable_raw_mode()?;
+    if let Err(e) = status {
+        print!("Couldn't start {}: {}\r\nPress any key to return\r\n", shell.to_string_lossy(), e);
+        io::stdout().flush()?;
+        while !matches!(event::read()?, Event::Key(_)) {}
+    }
+    execute!(terminal.backend_mut(), EnterAlternateScreen)?;
+    crash::set_tui_active(true);
+    terminal.clear()?;
+    Ok(())
+}
+
+/// `$SHELL`, or the platform's default shell
+fn user_shell() -> OsString {
+    let (variable, fallback) = if cfg!(windows) { ("COMSPEC", "cmd.exe") } else { ("SHELL", "/bin/sh") };
+    std::env::var_os(variable)
+        .filter(|shell| !shell.is_empty())
+        .unwrap_or_else(|| fallback.into())
+}
+
+/// Applies an event that can be repeated or replayed from a macro
+fn apply_replayable(state: &mut AppState, event: &AppEvent) {
+    match event {
+        AppEvent::MoveUp => state.move_up(),
+        AppEvent::MoveDown => state.move_down(),
+        AppEvent::ToggleSelection => state.toggle_selection(),
+        AppEvent::ToggleExpand => state.toggle_expand(),
+        _ => {}
+    }
+}
@@ -0,0 +1,51 @@
+use crate::tui::events::AppEvent;
+
+/// Repeat-last-action (`.`) and macro record/replay (`m`/`M`) for long
+/// review sessions
+#[derive(Default)]
+pub struct Repeater {
+    /// Events replayed by `.`: the last selection change, or the last macro run
+    last_edit: Vec<AppEvent>,
+    /// Events captured since `m` started recording
+    recording: Option<Vec<AppEvent>>,
+    /// Last completed recording, replayed by `M`
+    recorded: Vec<AppEvent>,
+}
+
+impl Repeater {
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Starts recording, or stops and keeps the recording as the macro
+    pub fn toggle_recording(&mut self) {
+        match self.recording.take() {
+            Some(events) => self.recorded = events,
+            None => self.recording = Some(Vec::new()),
+        }
+    }
+
+    /// Notes an event the user performed
+    pub fn observe(&mut self, event: &AppEvent) {
+        if !event.is_replayable() {
+            return;
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.push(event.clone());
+        }
+        if event.changes_selection() {
+            self.last_edit = vec![event.clone()];
+        }
+    }
+
+    /// Events to replay for `M`; the macro also becomes the target of `.`
+    pub fn replay_macro(&mut self) -> Vec<AppEvent> {
+        self.last_edit = self.recorded.clone();
+        self.recorded.clone()
+    }
+
+    /// Events to replay for `.`
+    pub fn repeat_last(&self) -> Vec<AppEvent> {
+        self.last_edit.clone()
+    }
+}
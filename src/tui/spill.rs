@@ -0,0 +1,129 @@
+use crate::scanner::{CleanableProject, TargetSnapshot};
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+
+/// Projects the TUI keeps in memory unless `--max-projects` says otherwise
+pub const DEFAULT_MAX_PROJECTS: usize = 50_000;
+
+/// Projects moved out of memory when a scan finds more than the TUI keeps.
+/// They are written as JSON lines to a file in the cache directory, which is
+/// removed when the TUI exits.
+#[derive(Default)]
+pub struct Spill {
+    /// Created on the first spill
+    writer: Option<(PathBuf, BufWriter<File>)>,
+    /// Projects spilled so far
+    pub count: usize,
+    /// Their combined size
+    pub total_size: u64,
+    /// Projects that couldn't be written, and are gone until the next scan
+    pub lost: usize,
+    /// Snapshots of the spilled targets, which the file doesn't keep, so
+    /// projects read back are still checked for drift before cleaning
+    snapshots: HashMap<PathBuf, TargetSnapshot>,
+}
+
+impl Spill {
+    /// Moves `projects` to disk
+    pub fn push(&mut self, projects: Vec<CleanableProject>) {
+        self.count += projects.len();
+        self.total_size += projects.iter().map(|p| p.total_size).sum::<u64>();
+        let written = self.open().and_then(|writer| {
+            for project in &projects {
+                writeln!(writer, "{}", serde_json::to_string(project)?)?;
+            }
+            Ok(())
+        });
+        if written.is_err() {
+            self.lost += projects.len();
+            return;
+        }
+        for target in projects.iter().flat_map(|p| &p.targets) {
+            if let Some(snapshot) = target.snapshot {
+                self.snapshots.insert(target.path.clone(), snapshot);
+            }
+        }
+    }
+
+    /// Reads back up to `limit` spilled projects that `keep` accepts
+    pub fn load(&mut self, keep: impl Fn(&CleanableProject) -> bool, limit: usize) -> Vec<CleanableProject> {
+        let snapshots = &self.snapshots;
+        let Some((path, writer)) = &mut self.writer else {
+            return Vec::new();
+        };
+        if writer.flush().is_err() {
+            return Vec::new();
+        }
+        let Ok(file) = File::open(path) else {
+            return Vec::new();
+        };
+        BufReader::new(file)
+            .lines()
+            .map_while(|line| line.ok())
+            .filter_map(|line| serde_json::from_str::<CleanableProject>(&line).ok())
+            .filter(|project| keep(project))
+            .take(limit)
+            .map(|mut project| {
+                for target in &mut project.targets {
+                    target.snapshot = snapshots.get(&target.path).copied();
+                }
+                project
+            })
+            .collect()
+    }
+
+    /// Forgets everything spilled, before scanning again
+    pub fn clear(&mut self) {
+        self.remove_file();
+        *self = Self::default();
+    }
+
+    fn open(&mut self) -> Result<&mut BufWriter<File>> {
+        let (_, writer) = match self.writer.take() {
+            Some(open) => self.writer.insert(open),
+            None => self.writer.insert(create_file()?),
+        };
+        Ok(writer)
+    }
+
+    fn remove_file(&mut self) {
+        if let Some((path, _)) = self.writer.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+impl Drop for Spill {
+    fn drop(&mut self) {
+        self.remove_file();
+    }
+}
+
+/// `<cache dir>/spektr/spill-<pid>.jsonl`, so concurrent instances don't collide
+fn create_file() -> Result<(PathBuf, BufWriter<File>)> {
+    let dir = dirs::cache_dir()
+        .map(|dir| dir.join("spektr"))
+        .unwrap_or_else(std::env::temp_dir);
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!("spill-{}.jsonl", std::process::id()));
+    let file = File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+    Ok((path, BufWriter::new(file)))
+}
+
+/// Resident memory of this process in bytes, where the platform reports it
+#[cfg(target_os = "linux")]
+pub fn resident_memory() -> Option<u64> {
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    // SAFETY: sysconf only reads a configuration value
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+    Some(pages * u64::try_from(page_size).ok()?)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn resident_memory() -> Option<u64> {
+    None
+}
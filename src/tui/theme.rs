@@ -0,0 +1,167 @@
+use clap::ValueEnum;
+use ratatui::style::Color;
+use serde::Deserialize;
+
+/// How long to wait for the terminal to report its background color
+#[cfg(unix)]
+const QUERY_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Which palette the TUI uses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    /// Ask the terminal for its background color (OSC 11, then `COLORFGBG`),
+    /// falling back to dark
+    #[default]
+    Auto,
+    /// For dark backgrounds
+    Dark,
+    /// For light backgrounds, where yellow and light gray are illegible
+    Light,
+}
+
+/// Colors of the TUI, by role
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    /// Borders, workspace members, and hint keys
+    pub accent: Color,
+    /// The highlighted row
+    pub highlight: Color,
+    /// Checked rows, sizes to reclaim, confirm keys
+    pub success: Color,
+    /// Risk badges, warnings, the scanning spinner
+    pub warning: Color,
+    /// Targets, errors, cancel keys
+    pub danger: Color,
+    /// "Will be deleted"
+    pub danger_soft: Color,
+    /// Secondary text: sizes, labels
+    pub muted: Color,
+    /// Explanations and hints
+    pub faint: Color,
+    /// Headings that stand out from the text
+    pub emphasis: Color,
+    /// Cloud sync notes
+    pub info: Color,
+    /// Retention policy and heuristic notes
+    pub policy: Color,
+    /// Values printed on the age graph's bars
+    pub bar_label: Color,
+}
+
+impl Palette {
+    pub const DARK: Palette = Palette {
+        accent: Color::Cyan,
+        highlight: Color::Yellow,
+        success: Color::Green,
+        warning: Color::Yellow,
+        danger: Color::Red,
+        danger_soft: Color::LightRed,
+        muted: Color::Gray,
+        faint: Color::DarkGray,
+        emphasis: Color::White,
+        info: Color::LightBlue,
+        policy: Color::LightMagenta,
+        bar_label: Color::Black,
+    };
+
+    pub const LIGHT: Palette = Palette {
+        accent: Color::Blue,
+        highlight: Color::Blue,
+        success: Color::Green,
+        // Dark orange in the 256-color palette; ANSI yellow washes out on white
+        warning: Color::Indexed(130),
+        danger: Color::Red,
+        danger_soft: Color::Red,
+        muted: Color::DarkGray,
+        faint: Color::DarkGray,
+        emphasis: Color::Black,
+        info: Color::Blue,
+        policy: Color::Magenta,
+        bar_label: Color::White,
+    };
+
+    /// The palette for `theme`. `Auto` queries the terminal, so call it with
+    /// raw mode enabled and before reading input.
+    pub fn resolve(theme: Theme) -> Palette {
+        let light = match theme {
+            Theme::Dark => false,
+            Theme::Light => true,
+            Theme::Auto => query_background()
+                .map(is_light)
+                .or_else(colorfgbg_is_light)
+                .unwrap_or(false),
+        };
+        if light {
+            Self::LIGHT
+        } else {
+            Self::DARK
+        }
+    }
+}
+
+/// Whether an RGB color (0.0-1.0 per channel) is a light background
+fn is_light((r, g, b): (f64, f64, f64)) -> bool {
+    0.2126 * r + 0.7152 * g + 0.0722 * b > 0.5
+}
+
+/// Background from `COLORFGBG` ("15;0": light text on black), set by rxvt,
+/// Konsole, and others. Colors 7 and 9-15 are light.
+fn colorfgbg_is_light() -> Option<bool> {
+    let value = std::env::var("COLORFGBG").ok()?;
+    let background: u8 = value.rsplit(';').next()?.parse().ok()?;
+    Some(background == 7 || (9..=15).contains(&background))
+}
+
+/// Asks the terminal for its background color with OSC 11. Terminals that
+/// don't answer cost `QUERY_TIMEOUT`.
+#[cfg(unix)]
+fn query_background() -> Option<(f64, f64, f64)> {
+    use std::io::{Read, Write};
+    use std::os::unix::io::AsRawFd;
+    use std::time::Instant;
+
+    let mut tty = std::fs::OpenOptions::new().read(true).write(true).open("/dev/tty").ok()?;
+    tty.write_all(b"\x1b]11;?\x1b\\").ok()?;
+    tty.flush().ok()?;
+
+    let deadline = Instant::now() + QUERY_TIMEOUT;
+    let mut reply = Vec::new();
+    while !(reply.ends_with(b"\x07") || reply.ends_with(b"\x1b\\")) {
+        let remaining = deadline.checked_duration_since(Instant::now())?;
+        let mut poll_fd = libc::pollfd {
+            fd: tty.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: `poll_fd` is a valid pollfd for an open descriptor, and the count is 1
+        let ready = unsafe { libc::poll(&mut poll_fd, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 || reply.len() > 64 {
+            return None;
+        }
+        let mut buf = [0u8; 32];
+        match tty.read(&mut buf) {
+            Ok(0) | Err(_) => return None,
+            Ok(n) => reply.extend_from_slice(&buf[..n]),
+        }
+    }
+    parse_osc11(&String::from_utf8_lossy(&reply))
+}
+
+#[cfg(not(unix))]
+fn query_background() -> Option<(f64, f64, f64)> {
+    None
+}
+
+/// Reads "ESC ] 11 ; rgb:RRRR/GGGG/BBBB" (1-4 hex digits per channel)
+#[cfg(unix)]
+fn parse_osc11(reply: &str) -> Option<(f64, f64, f64)> {
+    let rgb = reply.split("rgb:").nth(1)?;
+    let rgb = rgb.trim_end_matches(['\x07', '\\']).trim_end_matches('\x1b');
+    let mut channels = rgb.split('/').map(|channel| {
+        let value = u32::from_str_radix(channel, 16).ok()?;
+        let max = (1u32 << (4 * channel.len().clamp(1, 4))) - 1;
+        Some(value as f64 / max as f64)
+    });
+    Some((channels.next()??, channels.next()??, channels.next()??))
+}
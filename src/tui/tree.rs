@@ -44,12 +44,21 @@ impl TreeNode {
         self_size + children_size
     }
 
-    /// Recursively set checked state
-    pub fn set_checked(&mut self, checked: bool) {
+    /// Recursively set checked state. Returns the number and total size of
+    /// projects whose state changed, so callers can keep running totals.
+    pub fn set_checked(&mut self, checked: bool) -> (usize, u64) {
+        let mut changed = match &self.project {
+            Some(project) if self.checked != checked => (1, project.total_size),
+            _ => (0, 0),
+        };
+
         self.checked = checked;
         for child in &mut self.children {
-            child.set_checked(checked);
+            let (count, size) = child.set_checked(checked);
+            changed.0 += count;
+            changed.1 += size;
         }
+        changed
     }
 
 
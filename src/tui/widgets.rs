@@ -1,107 +1,49 @@
-use crate::tui::app_state::{AppState, SortMode};
+use crate::archive::CompressionEstimate;
+use crate::cloud_sync::SyncService;
+use crate::format::{format_age, format_bytes_exact, format_count, format_elapsed, format_size};
+use crate::icons::{folder_icon, strategy_icon};
+use crate::report::SLOW_SIZING;
+use crate::scanner::{CleanableProject, RiskLevel};
+use crate::tui::app_state::{AppState, CachedRow, CleanAction, SortMode};
+use crate::tui::events::hints;
+use crate::tui::spill::resident_memory;
 use ratatui::{
     layout::{Alignment, Constraint, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Bar, BarChart, BarGroup, Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
     Frame,
 };
+use std::time::SystemTime;
 
 use crate::tui::app_state::{ViewMode};
 
 pub fn render_project_tree(f: &mut Frame, area: Rect, state: &AppState) {
-    let items: Vec<ListItem> = match state.view_mode {
-        ViewMode::List => { 
-            state.visible_projects()
-                .iter()
-                .enumerate()
-                .map(|(idx, project)| {
-                    let emoji = match project.strategy_name.as_str() {
-                        "Rust" => "🦀",
-                        "Node.js" => "📦",
-                        "Flutter" => "💙",
-                        "Android" => "🤖",
-                        _ => "📁",
-                    };
-        
-                    let size = format_size(project.total_size);
-                    let path = project
-                        .root_path
-                        .file_name()
-                        .unwrap_or_default()
-                        .to_string_lossy();
-        
-                    let checkbox = if state.is_selected(idx) { "[✓]" } else { "[ ]" };
-        
-                    let text = format!("{} {} {} - {}", checkbox, emoji, path, size);
-        
-                    let style = if idx == state.selected_index {
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::BOLD)
-                    } else if state.is_selected(idx) {
-                        Style::default().fg(Color::Green)
-                    } else {
-                        Style::default()
-                    };
-        
-                    ListItem::new(text).style(style)
-                })
-                .collect()
-        },
-        ViewMode::Tree => {
-            state.get_flat_tree()
-                .iter()
-                .enumerate()
-                .map(|(idx, flat_node)| {
-                    let node = flat_node.node;
-                    
-                    // Use pre-computed guide prefix for proper tree lines
-                    let guide = &flat_node.guide_prefix;
-                    
-                    // Collapse/Expand marker
-                    let fold_marker = if !node.children.is_empty() {
-                        if node.collapsed { "▶" } else { "▼" }
-                    } else {
-                        " " 
-                    };
-
-                    // Checkbox
-                    let checkbox = if node.checked { "[✓]" } else { "[ ]" };
-                    
-                    // Icon
-                    let emoji = if let Some(p) = &node.project {
-                         match p.strategy_name.as_str() {
-                            "Rust" => "🦀",
-                            "Node.js" => "📦",
-                            "Flutter" => "💙",
-                            "Android" => "🤖",
-                            _ => "📦",
-                        }
-                    } else {
-                        "📁"
-                    };
-
-                    let name = node.label();
-                    let size = format_size(node.total_size());
-                    
-                    let text = format!("{}{} {} {} {} - {}", guide, fold_marker, checkbox, emoji, name, size);
-                    
-                    let style = if idx == state.selected_index {
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::BOLD)
-                    } else if node.checked {
-                        Style::default().fg(Color::Green)
-                    } else {
-                         Style::default()
-                    };
+    // Row text only changes with the underlying state; reuse it between frames
+    let mut cache = state.row_cache.borrow_mut();
+    if cache.revision != Some(state.rows_revision()) {
+        cache.rows = build_rows(state);
+        cache.revision = Some(state.rows_revision());
+    }
 
-                    ListItem::new(text).style(style)
-                })
-                .collect()
-        }
-    };
+    let items: Vec<ListItem> = cache
+        .rows
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            let style = if idx == state.selected_index {
+                Style::default()
+                    .fg(state.palette.highlight)
+                    .add_modifier(Modifier::BOLD)
+            } else if row.checked {
+                Style::default().fg(state.palette.success)
+            } else {
+                Style::default()
+            };
+
+            ListItem::new(row.text.as_str()).style(style)
+        })
+        .collect();
 
     let sort_label = match state.sort_mode {
         SortMode::SizeDesc => "Size ↓",
@@ -114,28 +56,35 @@ pub fn render_project_tree(f: &mut Frame, area: Rect, state: &AppState) {
         ViewMode::List => "List",
         ViewMode::Tree => "Tree",
     };
+    let read_only_label = if state.read_only { " | 🔒 Read-only" } else { "" };
 
     let title = if state.scanning {
+        let phase = state
+            .scan_phase
+            .map_or_else(|| "Scanning...".to_string(), |phase| format!("{}...", phase.label()));
         format!(
-            " Projects (Scanning...) | {} | Sort: {} | Filter: {} ",
+            " Projects ({}) | {} | Sort: {} | Filter: {}{} ",
+            phase,
             view_label,
             sort_label,
-            state.filter_mode.label()
+            state.filter_mode.label(),
+            read_only_label
         )
     } else {
         format!(
-            " Projects ({}) | {} | Sort: {} | Filter: {} ",
+            " Projects ({}) | {} | Sort: {} | Filter: {}{} ",
             state.visible_count(),
             view_label,
             sort_label,
-            state.filter_mode.label()
+            state.filter_mode.label(),
+            read_only_label
         )
     };
 
     let mut block = Block::default()
         .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(state.palette.accent));
 
     if state.scanning {
          let spinner = vec!["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
@@ -153,7 +102,7 @@ pub fn render_project_tree(f: &mut Frame, area: Rect, state: &AppState) {
          };
 
          block = block.title_bottom(Line::from(vec![
-             Span::styled(format!(" {} Scanning: ", frame), Style::default().fg(Color::Yellow)),
+             Span::styled(format!(" {} Scanning: ", frame), Style::default().fg(state.palette.warning)),
              Span::raw(display_path),
              Span::raw(" "),
          ]).alignment(Alignment::Right));
@@ -164,6 +113,90 @@ pub fn render_project_tree(f: &mut Frame, area: Rect, state: &AppState) {
     f.render_widget(list, area);
 }
 
+/// Builds the text of every row in the project list or tree
+fn build_rows(state: &AppState) -> Vec<CachedRow> {
+    match state.view_mode {
+        ViewMode::List => state
+            .visible_projects()
+            .iter()
+            .enumerate()
+            .map(|(idx, project)| {
+                let emoji = strategy_icon(&project.strategy_name, state.icons);
+                let size = size_label(state, project);
+                let path = state.display_path(project);
+                let checked = state.is_selected(idx);
+                let checkbox = if checked { "[✓]" } else { "[ ]" };
+
+                let text = format!(
+                    "{} {} {} - {}{}{}{}",
+                    checkbox,
+                    emoji,
+                    path,
+                    size,
+                    workspace_badge(project),
+                    risk_badge(project.risk_level),
+                    sync_badge(&project.root_path)
+                );
+
+                CachedRow { text, checked }
+            })
+            .collect(),
+        ViewMode::Tree => state
+            .get_flat_tree()
+            .iter()
+            .map(|flat_node| {
+                let node = flat_node.node;
+
+                // Use pre-computed guide prefix for proper tree lines
+                let guide = &flat_node.guide_prefix;
+
+                // Collapse/Expand marker
+                let fold_marker = if !node.children.is_empty() {
+                    if node.collapsed { "▶" } else { "▼" }
+                } else {
+                    " "
+                };
+
+                // Checkbox
+                let checkbox = if node.checked { "[✓]" } else { "[ ]" };
+
+                // Icon
+                let emoji = if let Some(p) = &node.project {
+                    strategy_icon(&p.strategy_name, state.icons)
+                } else {
+                    folder_icon(state.icons)
+                };
+
+                let name = node.label();
+                let size = match &node.project {
+                    Some(project) => size_label(state, project),
+                    None => format_size(node.total_size(), state.units),
+                };
+
+                let badge = node
+                    .project
+                    .as_ref()
+                    .map_or(String::new(), |p| format!("{}{}", workspace_badge(p), risk_badge(p.risk_level)));
+
+                let text = format!("{}{} {} {} {} - {}{}", guide, fold_marker, checkbox, emoji, name, size, badge);
+
+                CachedRow { text, checked: node.checked }
+            })
+            .collect(),
+    }
+}
+
+/// A project's size, or why it isn't known
+fn size_label(state: &AppState, project: &CleanableProject) -> String {
+    if state.is_sizing(project) {
+        "sizing…".to_string()
+    } else if project.size_unknown {
+        "size unknown".to_string()
+    } else {
+        format_size(project.total_size, state.units)
+    }
+}
+
 pub fn render_details_pane(f: &mut Frame, area: Rect, state: &AppState) {
     let text = if let Some(project) = state.current_project() {
         let path_str = project.root_path.display().to_string();
@@ -178,33 +211,175 @@ pub fn render_details_pane(f: &mut Frame, area: Rect, state: &AppState) {
                 Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(project.strategy_name.clone()),
             ]),
+            Line::from(vec![
+                Span::styled("History: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(
+                    state
+                        .history
+                        .describe(project, state.units)
+                        .unwrap_or_else(|| "never cleaned".to_string()),
+                ),
+            ]),
+        ];
+        if let Some(last_used) = project.last_used {
+            let age = SystemTime::now().duration_since(last_used).unwrap_or_default();
+            lines.push(Line::from(vec![
+                Span::styled("Last used: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{} ago", format_age(age))),
+            ]));
+        }
+        if let Some(reason) = state.retention_reason(project) {
+            lines.push(Line::from(vec![
+                Span::styled("Policy: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(format!("clean ({})", reason), Style::default().fg(state.palette.policy)),
+            ]));
+        }
+        if project.manual_only {
+            lines.push(Line::from(vec![
+                Span::styled("Policy: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("a guess; only cleaned when picked by hand", Style::default().fg(state.palette.policy)),
+            ]));
+        }
+        let sizing = state.is_sizing(project);
+        let sized = !sizing && !project.size_unknown;
+        if !project.members.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!("Workspace: {} member packages", project.members.len()),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for (member, size) in project.member_sizes() {
+                let name = member.strip_prefix(&project.root_path).unwrap_or(member);
+                let size = if sized && size > 0 {
+                    format!(" ({})", format_size(size, state.units))
+                } else {
+                    String::new()
+                };
+                lines.push(Line::from(vec![
+                    Span::raw("  └─ "),
+                    Span::styled(name.display().to_string(), Style::default().fg(state.palette.accent)),
+                    Span::styled(size, Style::default().fg(state.palette.muted)),
+                ]));
+            }
+        }
+        lines.extend([
             Line::from(""),
             Line::from(vec![
                 Span::styled("Targets: ", Style::default().add_modifier(Modifier::BOLD)),
-                Span::styled("(Will be deleted)", Style::default().fg(Color::LightRed)),
+                Span::styled("(Will be deleted)", Style::default().fg(state.palette.danger_soft)),
             ]),
-        ];
+        ]);
 
         for target in &project.targets {
-            let display_text = if let Ok(relative) = target.strip_prefix(&project.root_path) {
+            let display_text = if let Ok(relative) = target.path.strip_prefix(&project.root_path) {
                 relative.display().to_string()
             } else {
-                target.display().to_string()
+                target.path.display().to_string()
             };
 
+            let size = if sized {
+                format!(" ({})", format_size(target.size, state.units))
+            } else {
+                String::new()
+            };
             lines.push(Line::from(vec![
                 Span::raw("  • "),
-                Span::styled(display_text, Style::default().fg(Color::Red)),
+                Span::styled(display_text, Style::default().fg(state.palette.danger)),
+                Span::styled(size, Style::default().fg(state.palette.muted)),
+                Span::styled(
+                    match target.risk_level {
+                        RiskLevel::Low => String::new(),
+                        risk => format!(" {} {} risk", risk.badge(), risk.label()),
+                    },
+                    Style::default().fg(state.palette.warning),
+                ),
             ]));
+            if !target.explanation.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    format!("    {}", target.explanation),
+                    Style::default().fg(state.palette.faint),
+                )));
+            }
+        }
+
+        if let Some(service) = SyncService::detect(&project.root_path) {
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(
+                format!(
+                    "☁ Inside {}: deleting these churns the sync client, and the next build re-uploads them",
+                    service.label()
+                ),
+                Style::default().fg(state.palette.info),
+            )));
+
+            let hints: Vec<String> = project
+                .targets
+                .iter()
+                .filter_map(|target| service.ignore_hint(&target.path))
+                .collect();
+            let hint_lines = if hints.is_empty() {
+                vec![format!(
+                    "  {} can't ignore single folders; consider moving the project out of it",
+                    service.label()
+                )]
+            } else {
+                std::iter::once("  To keep the targets out of sync:".to_string())
+                    .chain(hints.into_iter().map(|hint| format!("    {}", hint)))
+                    .collect()
+            };
+            for hint in hint_lines {
+                lines.push(Line::from(Span::styled(hint, Style::default().fg(state.palette.faint))));
+            }
         }
 
+        let size_line = if sized {
+            Line::from(vec![
+                Span::styled("Size: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format_size(project.total_size, state.units),
+                    Style::default().fg(state.palette.warning),
+                ),
+                Span::styled(
+                    format!(" ({})", format_bytes_exact(project.total_size)),
+                    Style::default().fg(state.palette.muted),
+                ),
+                Span::styled(
+                    if project.size_estimated { ", estimated by an earlier scan" } else { "" },
+                    Style::default().fg(state.palette.muted),
+                ),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("Size: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    if sizing { "sizing… (S to skip)" } else { "unknown (sizing skipped)" },
+                    Style::default().fg(state.palette.muted),
+                ),
+            ])
+        };
+
         lines.extend(vec![
             Line::from(""),
+            size_line,
             Line::from(vec![
-                Span::styled("Size: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Sizing: ", Style::default().add_modifier(Modifier::BOLD)),
                 Span::styled(
-                    format_size(project.total_size),
-                    Style::default().fg(Color::Yellow),
+                    if project.size_unknown {
+                        format!("skipped after {}", format_elapsed(project.sizing_time))
+                    } else {
+                        format!(
+                            "{} — {} {}",
+                            format_elapsed(project.sizing_time),
+                            format_count(project.file_count),
+                            if project.file_count == 1 { "file" } else { "files" }
+                        )
+                    },
+                    // Outliers often hide surprising contents (e.g., a dataset inside target/)
+                    if project.sizing_time >= SLOW_SIZING {
+                        Style::default().fg(state.palette.danger)
+                    } else {
+                        Style::default().fg(state.palette.muted)
+                    },
                 ),
             ]),
             Line::from(""),
@@ -213,12 +388,9 @@ pub fn render_details_pane(f: &mut Frame, area: Rect, state: &AppState) {
                     "Rebuild Cost: ",
                     Style::default().add_modifier(Modifier::BOLD),
                 ),
-                Span::raw(match project.strategy_name.as_str() {
-                    "Rust" => "~2-5 mins (cargo build)",
-                    "Node.js" => "~1-2 mins (npm install)",
-                    "Flutter" => "~1-3 mins (flutter pub get)",
-                    "Android" => "~3-10 mins (gradle build)",
-                    _ => "~1-3 mins",
+                Span::raw(match &project.rebuild {
+                    Some(estimate) => estimate.label(),
+                    None => "estimated once sized".to_string(),
                 }),
             ]),
         ]);
@@ -233,62 +405,150 @@ pub fn render_details_pane(f: &mut Frame, area: Rect, state: &AppState) {
             Block::default()
                 .title(" Details ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(state.palette.accent)),
         )
         .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, area);
 }
 
+/// Bar chart of reclaimable space by last-modified age, to help pick an age threshold
+pub fn render_age_graph(f: &mut Frame, area: Rect, state: &AppState) {
+    let summaries = state.age_summary();
+    let bars: Vec<Bar> = summaries
+        .iter()
+        .map(|summary| {
+            Bar::default()
+                .value(summary.total_size)
+                .label(Line::from(summary.label))
+                .text_value(format_size(summary.total_size, state.units))
+                .style(Style::default().fg(state.palette.warning))
+                .value_style(Style::default().fg(state.palette.bar_label).bg(state.palette.warning))
+        })
+        .collect();
+
+    // Spread the four bars across the pane, leaving room for borders and gaps
+    let bar_width = (area.width.saturating_sub(2) / summaries.len().max(1) as u16)
+        .saturating_sub(1)
+        .max(1);
+
+    let chart = BarChart::default()
+        .block(
+            Block::default()
+                .title(" Reclaimable by Age (g: back) ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.palette.accent)),
+        )
+        .data(BarGroup::default().bars(&bars))
+        .bar_width(bar_width)
+        .bar_gap(1);
+
+    f.render_widget(chart, area);
+}
+
 pub fn render_action_pane(f: &mut Frame, area: Rect, state: &AppState) {
     let total_size = state.total_selected_size();
     let selected_count = state.selected_count();
 
-    let text = vec![
+    let mut text = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
             "Total Reclaimable:",
             Style::default()
-                .fg(Color::White)
+                .fg(state.palette.emphasis)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
-            format_size(total_size),
+            format_size(total_size, state.units),
             Style::default()
-                .fg(Color::Green)
+                .fg(state.palette.success)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             format!("Selected: {} projects", selected_count),
-            Style::default().fg(Color::Gray),
-        )]),
-        Line::from(""),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Controls:",
-            Style::default().add_modifier(Modifier::BOLD),
+            Style::default().fg(state.palette.muted),
         )]),
-        Line::from("  ↑/↓ or j/k: Navigate"),
-        Line::from("  Space: Toggle selection"),
-        Line::from("  Enter: Clean selected"),
-        Line::from("  s: Toggle sort"),
-        Line::from("  f: Cycle filter"),
-        Line::from("  q/Esc: Quit"),
+        Line::from(if state.recording_macro {
+            Span::styled("● Recording macro (m to stop)", Style::default().fg(state.palette.danger))
+        } else {
+            Span::raw("")
+        }),
     ];
+    if let Some(memory) = resident_memory() {
+        text.push(Line::from(Span::styled(
+            format!("Memory: {}", format_size(memory, state.units)),
+            Style::default().fg(state.palette.faint),
+        )));
+    }
+    let spill = state.spill();
+    if spill.count > 0 {
+        text.push(Line::from(Span::styled(
+            format!(
+                "{} smaller projects ({}) moved to disk",
+                spill.count,
+                format_size(spill.total_size, state.units)
+            ),
+            Style::default().fg(state.palette.warning),
+        )));
+        text.push(Line::from(Span::styled(
+            if spill.lost > 0 {
+                format!("{} couldn't be written and are hidden", spill.lost)
+            } else {
+                "Filter (f) to browse them".to_string()
+            },
+            Style::default().fg(state.palette.faint),
+        )));
+    }
 
     let paragraph = Paragraph::new(text)
         .block(
             Block::default()
                 .title(" Actions ")
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Cyan)),
+                .border_style(Style::default().fg(state.palette.accent)),
         )
         .alignment(Alignment::Center);
 
     f.render_widget(paragraph, area);
 }
 
+/// Hint bar rows for a terminal `width` columns wide: the keys that do
+/// something right now, wrapped between hints rather than inside one
+pub fn hint_lines(state: &AppState, width: u16) -> Vec<Line<'static>> {
+    const SEPARATOR: &str = "  ";
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut used = 0;
+    for hint in hints(&state.hint_context()) {
+        let hint_width = hint.keys.chars().count() + 1 + hint.action.chars().count();
+        let needed = if spans.is_empty() { hint_width } else { SEPARATOR.len() + hint_width };
+        if !spans.is_empty() && used + needed > width as usize {
+            lines.push(Line::from(std::mem::take(&mut spans)));
+            used = 0;
+        }
+        if !spans.is_empty() {
+            spans.push(Span::raw(SEPARATOR));
+            used += SEPARATOR.len();
+        }
+        spans.push(Span::styled(
+            hint.keys,
+            Style::default().fg(state.palette.accent).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled(format!(" {}", hint.action), Style::default().fg(state.palette.muted)));
+        used += hint_width;
+    }
+    if !spans.is_empty() {
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+pub fn render_hint_bar(f: &mut Frame, area: Rect, lines: Vec<Line<'static>>) {
+    f.render_widget(Paragraph::new(lines), area);
+}
+
 pub fn render_confirmation_modal(f: &mut Frame, state: &AppState) {
     let selected_count = state.selected_count();
     let total_size = state.total_selected_size();
@@ -301,7 +561,7 @@ pub fn render_confirmation_modal(f: &mut Frame, state: &AppState) {
             Line::from(vec![Span::styled(
                 "⚠️  No Projects Selected",
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(state.palette.warning)
                     .add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
@@ -310,7 +570,7 @@ pub fn render_confirmation_modal(f: &mut Frame, state: &AppState) {
             Line::from(""),
             Line::from(vec![Span::styled(
                 "Press any key to continue...",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(state.palette.muted),
             )]),
         ];
 
@@ -319,61 +579,91 @@ pub fn render_confirmation_modal(f: &mut Frame, state: &AppState) {
                 Block::default()
                     .title(" Warning ")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Yellow)),
+                    .border_style(Style::default().fg(state.palette.warning)),
             )
             .alignment(Alignment::Center);
 
         f.render_widget(Clear, area);
         f.render_widget(paragraph, area);
     } else {
-        let area = centered_rect(60, 40, f.area());
+        let area = match state.pending_action {
+            CleanAction::Delete => centered_rect(60, 40, f.area()),
+            CleanAction::Archive => centered_rect(60, 60, f.area()),
+        };
 
-        let text = vec![
+        let (heading, verb, warning) = match state.pending_action {
+            CleanAction::Delete => (
+                "⚠️  Confirm Deletion",
+                "Delete ",
+                match state.selected_max_risk() {
+                    RiskLevel::Low => "This action cannot be undone!".to_string(),
+                    risk => format!(
+                        "{} Includes {}-risk targets. This action cannot be undone!",
+                        risk.badge(),
+                        risk.label().to_lowercase()
+                    ),
+                },
+            ),
+            CleanAction::Archive => (
+                "📦 Confirm Archive",
+                "Archive ",
+                format!("Targets will be compressed to {}", state.archive_dir.display()),
+            ),
+        };
+
+        let mut text = vec![
             Line::from(""),
             Line::from(vec![Span::styled(
-                "⚠️  Confirm Deletion",
+                heading,
                 Style::default()
-                    .fg(Color::Red)
+                    .fg(state.palette.danger)
                     .add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
             Line::from(vec![
-                Span::raw("Delete "),
+                Span::raw(verb),
                 Span::styled(
                     format!("{} projects", selected_count),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(state.palette.warning),
                 ),
                 Span::raw(" totaling "),
                 Span::styled(
-                    format_size(total_size),
-                    Style::default().fg(Color::Green),
+                    format_size(total_size, state.units),
+                    Style::default().fg(state.palette.success),
                 ),
                 Span::raw("?"),
             ]),
             Line::from(""),
             Line::from(vec![Span::styled(
-                "This action cannot be undone!",
+                warning,
                 Style::default()
-                    .fg(Color::Red)
+                    .fg(state.palette.danger)
                     .add_modifier(Modifier::BOLD),
             )]),
+        ];
+
+        if state.pending_action == CleanAction::Archive {
+            text.extend(render_archive_estimates(state));
+        }
+
+        text.extend(vec![
             Line::from(""),
             Line::from(""),
             Line::from(vec![
-                Span::styled("Press ", Style::default().fg(Color::Gray)),
-                Span::styled("y", Style::default().fg(Color::Green)),
-                Span::styled(" to confirm, ", Style::default().fg(Color::Gray)),
-                Span::styled("n", Style::default().fg(Color::Red)),
-                Span::styled(" to cancel", Style::default().fg(Color::Gray)),
+                Span::styled("Press ", Style::default().fg(state.palette.muted)),
+                Span::styled("y", Style::default().fg(state.palette.success)),
+                Span::styled(" to confirm, ", Style::default().fg(state.palette.muted)),
+                Span::styled("n", Style::default().fg(state.palette.danger)),
+                Span::styled(" to cancel", Style::default().fg(state.palette.muted)),
             ]),
-        ];
+        ]);
 
         let paragraph = Paragraph::new(text)
             .block(
                 Block::default()
                     .title(" Confirmation ")
                     .borders(Borders::ALL)
-                    .border_style(Style::default().fg(Color::Red)),
+                    .border_style(Style::default().fg(state.palette.danger)),
             )
             .alignment(Alignment::Center);
 
@@ -382,6 +672,116 @@ pub fn render_confirmation_modal(f: &mut Frame, state: &AppState) {
     }
 }
 
+/// Lines summarizing estimated archive size/time, overall and per project
+/// Modal shown when the scan fails, offering to retry or quit
+pub fn render_error_modal(f: &mut Frame, state: &AppState) {
+    let area = centered_rect(60, 30, f.area());
+    let error = state.scan_error.as_deref().unwrap_or_default();
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            "❌ Scan Failed",
+            Style::default().fg(state.palette.danger).add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(error.to_string()),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("r", Style::default().fg(state.palette.success).add_modifier(Modifier::BOLD)),
+            Span::raw(": Retry  "),
+            Span::styled("q", Style::default().fg(state.palette.danger).add_modifier(Modifier::BOLD)),
+            Span::raw(": Quit"),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(" Error ")
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(state.palette.danger)),
+        )
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+fn render_archive_estimates(state: &AppState) -> Vec<Line<'static>> {
+    const MAX_PROJECT_LINES: usize = 5;
+
+    let total = state
+        .archive_estimates
+        .iter()
+        .fold(CompressionEstimate::default(), |acc, (_, e)| acc.combine(*e));
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("Estimated archive: "),
+            Span::styled(
+                format!(
+                    "~{} ({:.0}%), ~{:.0}s",
+                    format_size(total.compressed_size, state.units),
+                    total.ratio_percent(),
+                    total.seconds.ceil()
+                ),
+                Style::default().fg(state.palette.warning),
+            ),
+        ]),
+    ];
+
+    for (path, estimate) in state.archive_estimates.iter().take(MAX_PROJECT_LINES) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        lines.push(Line::from(Span::styled(
+            format!(
+                "{}: {} → ~{} ({:.0}%)",
+                name,
+                format_size(estimate.original_size, state.units),
+                format_size(estimate.compressed_size, state.units),
+                estimate.ratio_percent()
+            ),
+            Style::default().fg(state.palette.muted),
+        )));
+    }
+
+    if state.archive_estimates.len() > MAX_PROJECT_LINES {
+        lines.push(Line::from(Span::styled(
+            format!("… and {} more", state.archive_estimates.len() - MAX_PROJECT_LINES),
+            Style::default().fg(state.palette.muted),
+        )));
+    }
+
+    lines
+}
+
+/// Suffix marking a project's highest target risk (empty for low risk)
+fn risk_badge(risk: RiskLevel) -> String {
+    match risk {
+        RiskLevel::Low => String::new(),
+        risk => format!(" {}", risk.badge()),
+    }
+}
+
+/// Member count of a workspace root, e.g. " [4 members]"
+fn workspace_badge(project: &CleanableProject) -> String {
+    match project.members.len() {
+        0 => String::new(),
+        1 => " [1 member]".to_string(),
+        n => format!(" [{} members]", n),
+    }
+}
+
+fn sync_badge(path: &std::path::Path) -> &'static str {
+    if SyncService::detect(path).is_some() {
+        " ☁"
+    } else {
+        ""
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = ratatui::layout::Layout::default()
         .direction(ratatui::layout::Direction::Vertical)
@@ -401,19 +801,3 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
-
-fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
-    }
-}